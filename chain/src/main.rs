@@ -26,18 +26,19 @@ use shared::balance::TokenSupply;
 use shared::block::Block;
 use shared::block_result::BlockResult;
 use shared::checksums::Checksums;
+use shared::client::RpcClient;
 use shared::crawler::crawl;
-use shared::crawler_state::ChainCrawlerState;
+use shared::crawler_state::{ChainCrawlerState, CrawlerName};
 use shared::error::{
     AsDbError, AsRpcError, AsTaskJoinError, ContextDbInteractError, MainError,
 };
 use shared::futures::AwaitContainer;
 use shared::id::Id;
+use shared::metrics::{self, Stage};
+use shared::proposal::GovernanceProposalEvent;
 use shared::token::Token;
 use shared::utils::BalanceChange;
 use shared::validator::ValidatorSet;
-use tendermint_rpc::HttpClient;
-use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 use tokio::time::Instant;
 use tokio_retry::Retry;
@@ -47,11 +48,7 @@ use tokio_retry::strategy::{ExponentialBackoff, jitter};
 async fn main() -> Result<(), MainError> {
     let config = AppConfig::parse();
 
-    let client =
-        HttpClient::builder(config.tendermint_url.as_str().parse().unwrap())
-            .compat_mode(CompatMode::V0_37)
-            .build()
-            .unwrap();
+    let client = RpcClient::new(&config.tendermint_url).unwrap();
 
     let mut checksums = Checksums::default();
     for code_path in Checksums::code_paths() {
@@ -64,6 +61,7 @@ async fn main() -> Result<(), MainError> {
     }
 
     config.log.init();
+    config.metrics.init(CrawlerName::Chain);
 
     let client = Arc::new(client);
 
@@ -196,7 +194,7 @@ async fn main() -> Result<(), MainError> {
 
 async fn crawling_fn(
     block_height: u32,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
     conn: Arc<Object>,
     checksums: Checksums,
     should_update_crawler_state: bool,
@@ -229,9 +227,15 @@ async fn crawling_fn(
     let native_token_address: namada_sdk::address::Address =
         native_token.clone().into();
 
-    let (block, tm_block_response, epoch) =
-        get_block(block_height, &client, checksums, &native_token_address)
-            .await?;
+    let (block, tm_block_response, block_results, epoch) = get_block(
+        block_height,
+        &client,
+        checksums.clone(),
+        &native_token_address,
+    )
+    .await?;
+
+    check_block_mismatches(&conn, &block).await?;
 
     let rate_limits = first_block_in_epoch.eq(&block_height).then(|| {
         let client = Arc::clone(&client);
@@ -256,6 +260,18 @@ async fn crawling_fn(
         block.transactions.len()
     );
 
+    // Channels are refreshed every epoch to pick up changes we can't tell from
+    // the transactions, e.g. clients of the counterparty being upgraded
+    let ibc_channels = if first_block_in_epoch.eq(&block_height)
+        || block.has_ibc_channel_updates()
+    {
+        namada_service::query_ibc_channels(&client, block_height)
+            .await
+            .into_rpc_error()?
+    } else {
+        vec![]
+    };
+
     let ibc_tokens = block
         .ibc_tokens()
         .into_iter()
@@ -341,13 +357,24 @@ async fn crawling_fn(
             .await
             .into_rpc_error()?;
 
-    let proposals = block.governance_proposal(next_governance_proposal_id);
+    let proposals =
+        block.governance_proposal(next_governance_proposal_id, &checksums);
     tracing::debug!(
         block = block_height,
         "Creating {} governance proposals...",
         proposals.len()
     );
 
+    let proposal_events = proposals
+        .iter()
+        .map(|proposal| {
+            GovernanceProposalEvent::created(proposal, epoch, block_height)
+        })
+        .chain(block_results.ended_proposals().iter().map(|proposal| {
+            GovernanceProposalEvent::activated(proposal, epoch, block_height)
+        }))
+        .collect::<Vec<_>>();
+
     let proposals_with_tally =
         namada_service::query_tallies(&client, proposals)
             .await
@@ -411,9 +438,16 @@ async fn crawling_fn(
         revealed_pks.len()
     );
 
+    let account_updates = block.account_updates();
+    tracing::debug!(
+        block = block_height,
+        "Updating {} established accounts",
+        account_updates.len()
+    );
+
     let metadata_change = block.validator_metadata();
 
-    let reward_claimers = block.pos_rewards();
+    let reward_claimers = block.pos_rewards(&block_results);
 
     let timestamp_in_sec = DateTimeUtc::now().0.timestamp();
 
@@ -449,6 +483,8 @@ async fn crawling_fn(
         withdraws = withdraw_addreses.len(),
         claimed_rewards = reward_claimers.len(),
         revealed_pks = revealed_pks.len(),
+        accounts = account_updates.len(),
+        ibc_channels = ibc_channels.len(),
         validator_state = validators_state_change.len(),
         epoch = epoch,
         first_block_in_epoch = first_block_in_epoch,
@@ -456,6 +492,23 @@ async fn crawling_fn(
         time_taken = first_checkpoint.duration_since(start).as_secs_f64(),
         "Queried block successfully",
     );
+    metrics::record_stage_duration(
+        Stage::Rpc,
+        first_checkpoint.duration_since(start),
+    );
+
+    let inserted_rows = [
+        ("blocks", 1),
+        ("token", ibc_tokens.len()),
+        ("balance_changes", balances.len()),
+        ("governance_proposals", proposals_with_tally.len()),
+        ("governance_votes", proposals_votes.len()),
+        ("bonds", bonds_updates.len()),
+        ("unbonds", unbonds.len()),
+        ("revealed_pk", revealed_pks.len()),
+        ("accounts", account_updates.len()),
+        ("ibc_channels", ibc_channels.len()),
+    ];
 
     conn.interact(move |conn| {
         conn.build_transaction()
@@ -476,6 +529,12 @@ async fn crawling_fn(
                     rate_limits,
                 )?;
 
+                repository::ibc::upsert_ibc_channels(
+                    transaction_conn,
+                    ibc_channels,
+                    block_height,
+                )?;
+
                 repository::block::upsert_block(
                     transaction_conn,
                     block,
@@ -491,6 +550,10 @@ async fn crawling_fn(
                     transaction_conn,
                     proposals_with_tally,
                 )?;
+                repository::gov::insert_proposal_events(
+                    transaction_conn,
+                    proposal_events,
+                )?;
                 repository::gov::insert_votes(
                     transaction_conn,
                     proposals_votes,
@@ -523,6 +586,7 @@ async fn crawling_fn(
                 repository::pos::delete_claimed_rewards(
                     transaction_conn,
                     reward_claimers,
+                    epoch,
                 )?;
 
                 repository::pos::update_validator_metadata(
@@ -535,6 +599,11 @@ async fn crawling_fn(
                     revealed_pks,
                 )?;
 
+                repository::account::upsert_accounts(
+                    transaction_conn,
+                    account_updates,
+                )?;
+
                 if should_update_crawler_state {
                     repository::crawler_state::upsert_crawler_state(
                         transaction_conn,
@@ -560,12 +629,59 @@ async fn crawling_fn(
             .as_secs_f64(),
         "Inserted block into database"
     );
+    metrics::record_stage_duration(
+        Stage::Db,
+        second_checkpoint.duration_since(first_checkpoint),
+    );
+    for (table, rows) in inserted_rows {
+        metrics::record_inserted_rows(table, rows);
+    }
+    metrics::record_last_processed(block_height as u64);
 
     Ok(())
 }
 
+/// Halts the crawler when the block returned by the RPC disagrees with what
+/// was already indexed, so a misbehaving node can't overwrite our data
+async fn check_block_mismatches(
+    conn: &Object,
+    block: &Block,
+) -> Result<(), MainError> {
+    let block_height = block.header.height;
+    let block = block.clone();
+
+    let mismatches = conn
+        .interact(move |conn| {
+            repository::block::find_block_mismatches(conn, &block)
+        })
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()?;
+
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    tracing::error!(
+        block = block_height,
+        ?mismatches,
+        "Indexed block doesn't match the RPC node, halting..."
+    );
+
+    conn.interact(move |conn| {
+        repository::block::insert_block_mismatches(conn, mismatches)
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()?;
+
+    Err(MainError::BlockMismatch)
+}
+
 async fn initial_query(
-    client: &HttpClient,
+    client: &RpcClient,
     conn: &Object,
     checksums: Checksums,
     retry_time: u64,
@@ -581,7 +697,7 @@ async fn initial_query(
 }
 
 async fn try_initial_query(
-    client: &HttpClient,
+    client: &RpcClient,
     conn: &Object,
     checksums: Checksums,
 ) -> Result<(), MainError> {
@@ -598,7 +714,7 @@ async fn try_initial_query(
             .await
             .into_rpc_error()?
             .into();
-    let (block, tm_block_response, epoch) =
+    let (block, tm_block_response, _, epoch) =
         get_block(block_height, client, checksums.clone(), &native_token)
             .await?;
 
@@ -623,6 +739,10 @@ async fn try_initial_query(
     let (rate_limits, token_supplies) =
         futures::try_join!(rate_limits_fut, token_supplies_fut)?;
 
+    let ibc_channels = namada_service::query_ibc_channels(client, block_height)
+        .await
+        .into_rpc_error()?;
+
     // This can sometimes fail if the last block height in the node has moved
     // forward after we queried for it. In that case, query_all_balances
     // returns an Err indicating that it can only be used for
@@ -650,7 +770,9 @@ async fn try_initial_query(
         .into_rpc_error()?;
 
     tracing::debug!(block = block_height, "Querying proposals...");
-    let proposals = query_all_proposals(client).await.into_rpc_error()?;
+    let proposals = query_all_proposals(client, &checksums)
+        .await
+        .into_rpc_error()?;
     let proposals_with_tally =
         namada_service::query_tallies(client, proposals.clone())
             .await
@@ -663,6 +785,11 @@ async fn try_initial_query(
     .await
     .into_rpc_error()?;
 
+    let proposal_events = proposals
+        .iter()
+        .map(|proposal| GovernanceProposalEvent::indexed(proposal, epoch))
+        .collect::<Vec<_>>();
+
     let timestamp = DateTimeUtc::now().0.timestamp();
 
     let crawler_state = ChainCrawlerState {
@@ -696,6 +823,12 @@ async fn try_initial_query(
                     rate_limits,
                 )?;
 
+                repository::ibc::upsert_ibc_channels(
+                    transaction_conn,
+                    ibc_channels,
+                    block_height,
+                )?;
+
                 tracing::debug!(
                     block = block_height,
                     "Inserting {} balances...",
@@ -716,6 +849,11 @@ async fn try_initial_query(
                     proposals_votes,
                 )?;
 
+                repository::gov::insert_proposal_events(
+                    transaction_conn,
+                    proposal_events,
+                )?;
+
                 repository::pos::upsert_validators(
                     transaction_conn,
                     validator_set,
@@ -740,7 +878,7 @@ async fn try_initial_query(
 
 async fn can_process(
     block_height: u32,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
 ) -> Result<bool, MainError> {
     let last_block_height = namada_service::query_last_block_height(&client)
         .await
@@ -751,6 +889,7 @@ async fn can_process(
             );
             MainError::RpcError
         })?;
+    metrics::record_chain_tip(last_block_height as u64);
 
     Ok(last_block_height >= block_height)
 }
@@ -775,10 +914,10 @@ async fn update_crawler_timestamp(
 
 async fn get_block(
     block_height: u32,
-    client: &HttpClient,
+    client: &RpcClient,
     checksums: Checksums,
     native_token: &namada_sdk::address::Address,
-) -> Result<(Block, TendermintBlockResponse, u32), MainError> {
+) -> Result<(Block, TendermintBlockResponse, BlockResult, u32), MainError> {
     tracing::debug!(block = block_height, "Query block...");
     let tm_block_response =
         tendermint_service::query_raw_block_at_height(client, block_height)
@@ -829,11 +968,11 @@ async fn get_block(
         native_token,
     );
 
-    Ok((block, tm_block_response, epoch))
+    Ok((block, tm_block_response, block_results, epoch))
 }
 
 async fn query_non_native_supplies(
-    client: &HttpClient,
+    client: &RpcClient,
     conn: &Object,
     epoch: u32,
 ) -> Result<Vec<TokenSupply>, MainError> {
@@ -860,7 +999,7 @@ async fn query_non_native_supplies(
 }
 
 async fn query_token_supplies(
-    client: &HttpClient,
+    client: &RpcClient,
     conn: &Object,
     native_token: &Id,
     epoch: u32,
@@ -0,0 +1,26 @@
+use anyhow::Context;
+use diesel::{PgConnection, RunQueryDsl};
+use orm::accounts::AccountInsertDb;
+use orm::schema::accounts;
+use shared::account::AccountUpdate;
+
+/// Applies the updates one by one, as the same account can be changed more
+/// than once in a block
+pub fn upsert_accounts(
+    transaction_conn: &mut PgConnection,
+    account_updates: Vec<AccountUpdate>,
+) -> anyhow::Result<()> {
+    for account_update in account_updates {
+        let account = AccountInsertDb::from(account_update);
+
+        diesel::insert_into(accounts::table)
+            .values(&account)
+            .on_conflict(accounts::address)
+            .do_update()
+            .set(&account)
+            .execute(transaction_conn)
+            .context("Failed to upsert account in db")?;
+    }
+
+    anyhow::Ok(())
+}
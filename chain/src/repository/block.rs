@@ -1,9 +1,12 @@
 use anyhow::Context;
 use diesel::upsert::excluded;
-use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
-use orm::blocks::BlockInsertDb;
-use orm::schema::blocks;
-use shared::block::Block;
+use diesel::{
+    ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
+use orm::blocks::{BlockDb, BlockInsertDb, BlockMismatchInsertDb};
+use orm::schema::{block_mismatches, blocks};
+use shared::block::{Block, BlockMismatch};
 use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 
 pub fn upsert_block(
@@ -30,3 +33,60 @@ pub fn upsert_block(
 
     anyhow::Ok(())
 }
+
+/// Compares the block with the indexed block at the same height and its
+/// indexed parent
+pub fn find_block_mismatches(
+    conn: &mut PgConnection,
+    block: &Block,
+) -> anyhow::Result<Vec<BlockMismatch>> {
+    let height = block.header.height as i32;
+
+    let indexed_block = find_block(conn, height)?;
+    let indexed_parent = match height.checked_sub(1) {
+        Some(parent_height) => find_block(conn, parent_height)?,
+        None => None,
+    };
+
+    Ok(block.mismatches(
+        indexed_block
+            .as_ref()
+            .and_then(|block| block.hash.as_deref()),
+        indexed_block
+            .as_ref()
+            .and_then(|block| block.app_hash.as_deref()),
+        indexed_parent
+            .as_ref()
+            .and_then(|block| block.hash.as_deref()),
+    ))
+}
+
+pub fn insert_block_mismatches(
+    transaction_conn: &mut PgConnection,
+    mismatches: Vec<BlockMismatch>,
+) -> anyhow::Result<()> {
+    diesel::insert_into(block_mismatches::table)
+        .values::<Vec<BlockMismatchInsertDb>>(
+            mismatches
+                .into_iter()
+                .map(BlockMismatchInsertDb::from)
+                .collect(),
+        )
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert block mismatches in db")?;
+
+    anyhow::Ok(())
+}
+
+fn find_block(
+    conn: &mut PgConnection,
+    height: i32,
+) -> anyhow::Result<Option<BlockDb>> {
+    blocks::table
+        .filter(blocks::height.eq(height))
+        .select(BlockDb::as_select())
+        .first(conn)
+        .optional()
+        .context("Failed to query block from db")
+}
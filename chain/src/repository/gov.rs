@@ -5,8 +5,11 @@ use diesel::upsert::excluded;
 use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
 use orm::governance_proposal::GovernanceProposalInsertDb;
 use orm::governance_votes::GovernanceProposalVoteInsertDb;
-use orm::schema::{governance_proposals, governance_votes};
-use shared::proposal::{GovernanceProposal, TallyType};
+use orm::proposal_events::ProposalEventInsertDb;
+use orm::schema::{governance_proposals, governance_votes, proposal_events};
+use shared::proposal::{
+    GovernanceProposal, GovernanceProposalEvent, TallyType,
+};
 use shared::tuple_len::TupleLen;
 use shared::vote::GovernanceVote;
 
@@ -86,3 +89,37 @@ fn insert_votes_chunk(
 
     anyhow::Ok(())
 }
+
+pub fn insert_proposal_events(
+    transaction_conn: &mut PgConnection,
+    events: Vec<GovernanceProposalEvent>,
+) -> anyhow::Result<()> {
+    diesel::insert_into(proposal_events::table)
+        .values::<Vec<ProposalEventInsertDb>>(
+            events
+                .into_iter()
+                .map(ProposalEventInsertDb::from)
+                .collect(),
+        )
+        .on_conflict((
+            proposal_events::proposal_id,
+            proposal_events::kind,
+            proposal_events::epoch,
+        ))
+        .do_update()
+        .set((
+            proposal_events::block_height
+                .eq(excluded(proposal_events::block_height)),
+            proposal_events::inner_tx_id
+                .eq(excluded(proposal_events::inner_tx_id)),
+            proposal_events::result.eq(excluded(proposal_events::result)),
+            proposal_events::has_proposal_code
+                .eq(excluded(proposal_events::has_proposal_code)),
+            proposal_events::proposal_code_succeeded
+                .eq(excluded(proposal_events::proposal_code_succeeded)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update proposal events in db")?;
+
+    anyhow::Ok(())
+}
@@ -0,0 +1,26 @@
+use anyhow::Context;
+use diesel::{PgConnection, RunQueryDsl};
+use orm::ibc::IbcChannelInsertDb;
+use orm::schema::ibc_channels;
+use shared::block::BlockHeight;
+use shared::ibc::IbcChannel;
+
+pub fn upsert_ibc_channels(
+    transaction_conn: &mut PgConnection,
+    channels: Vec<IbcChannel>,
+    block_height: BlockHeight,
+) -> anyhow::Result<()> {
+    for channel in channels {
+        let channel = IbcChannelInsertDb::from(channel, block_height);
+
+        diesel::insert_into(ibc_channels::table)
+            .values(&channel)
+            .on_conflict((ibc_channels::port_id, ibc_channels::channel_id))
+            .do_update()
+            .set(&channel)
+            .execute(transaction_conn)
+            .context("Failed to upsert ibc channel in db")?;
+    }
+
+    anyhow::Ok(())
+}
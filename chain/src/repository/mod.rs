@@ -1,7 +1,9 @@
+pub mod account;
 pub mod balance;
 pub mod block;
 pub mod crawler_state;
 pub mod gov;
+pub mod ibc;
 pub mod pgf;
 pub mod pos;
 pub mod revealed_pk;
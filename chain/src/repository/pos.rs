@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Context;
 use diesel::upsert::excluded;
@@ -7,7 +7,8 @@ use diesel::{
     PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
 };
 use orm::bond::BondInsertDb;
-use orm::schema::{bonds, pos_rewards, unbonds, validators};
+use orm::pos_rewards::PosRewardLedgerInsertDb;
+use orm::schema::{bonds, pos_reward_ledger, pos_rewards, unbonds, validators};
 use orm::unbond::UnbondInsertDb;
 use orm::validators::{
     ValidatorDb, ValidatorStateDb, ValidatorUpdateMetadataDb,
@@ -16,6 +17,7 @@ use orm::validators::{
 use shared::block::Epoch;
 use shared::bond::Bonds;
 use shared::id::Id;
+use shared::rewards::RewardClaim;
 use shared::tuple_len::TupleLen;
 use shared::unbond::{UnbondAddresses, Unbonds};
 use shared::validator::{
@@ -197,28 +199,53 @@ pub fn remove_withdraws(
     anyhow::Ok(())
 }
 
+/// Removes the unclaimed rewards of the delegations that claimed them and
+/// records the claims in the rewards ledger. Rewards are only credited at
+/// the end of an epoch, so a second claim of the same delegation within an
+/// epoch claims nothing and only the first one is recorded.
 pub fn delete_claimed_rewards(
     transaction_conn: &mut PgConnection,
-    reward_claimers: HashSet<(Id, Id)>,
+    reward_claims: HashSet<RewardClaim>,
+    epoch: Epoch,
 ) -> anyhow::Result<()> {
-    // If there are no rewards to claimm return early, to not clear the whole
-    // table
-    if reward_claimers.is_empty() {
+    if reward_claims.is_empty() {
+        return Ok(());
+    }
+
+    let validator_addresses = reward_claims
+        .iter()
+        .map(|claim| claim.delegation_pair.validator_address.to_string())
+        .collect::<HashSet<_>>();
+
+    let validator_ids = validators::table
+        .filter(validators::columns::namada_address.eq_any(validator_addresses))
+        .select((validators::columns::namada_address, validators::columns::id))
+        .load::<(String, i32)>(transaction_conn)
+        .context("Failed to query validators from db")?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    let claims = reward_claims
+        .into_iter()
+        .filter_map(|claim| {
+            let validator_id = validator_ids
+                .get(&claim.delegation_pair.validator_address.to_string())?;
+            Some((claim, *validator_id))
+        })
+        .collect::<Vec<_>>();
+
+    // Without this check, the query would delete all the rewards
+    if claims.is_empty() {
         return Ok(());
     }
 
     let mut query = diesel::delete(pos_rewards::table).into_boxed();
 
-    for (owner, validator_id) in reward_claimers {
+    for (claim, validator_id) in &claims {
         query = query.or_filter(
-            pos_rewards::owner.eq(owner.to_string()).and(
-                pos_rewards::validator_id.eq_any(
-                    validators::table.select(validators::columns::id).filter(
-                        validators::columns::namada_address
-                            .eq(validator_id.to_string()),
-                    ),
-                ),
-            ),
+            pos_rewards::owner
+                .eq(claim.delegation_pair.delegator_address.to_string())
+                .and(pos_rewards::validator_id.eq(*validator_id)),
         );
     }
 
@@ -226,6 +253,25 @@ pub fn delete_claimed_rewards(
         .execute(transaction_conn)
         .context("Failed to remove pos rewards from db")?;
 
+    diesel::insert_into(pos_reward_ledger::table)
+        .values(
+            claims
+                .into_iter()
+                .map(|(claim, validator_id)| {
+                    PosRewardLedgerInsertDb::claimed(claim, validator_id, epoch)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict((
+            pos_reward_ledger::owner,
+            pos_reward_ledger::validator_id,
+            pos_reward_ledger::epoch,
+            pos_reward_ledger::kind,
+        ))
+        .do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert claimed rewards in db")?;
+
     anyhow::Ok(())
 }
 
@@ -318,12 +364,17 @@ pub fn upsert_validators(
 
 #[cfg(test)]
 mod tests {
+    use bigdecimal::BigDecimal;
     use orm::bond::BondDb;
+    use orm::pos_rewards::{
+        PosRewardInsertDb, PosRewardLedgerDb, PosRewardLedgerKindDb,
+    };
     use orm::unbond::UnbondDb;
     use orm::validators::ValidatorInsertDb;
     use shared::balance::Amount;
     use shared::bond::Bond;
     use shared::unbond::Unbond;
+    use shared::utils::DelegationPair;
     use shared::validator::Validator;
     use test_helpers::db::TestDb;
 
@@ -680,6 +731,149 @@ mod tests {
         .expect("Failed to run test");
     }
 
+    /// Test that the delete_claimed_rewards function removes the unclaimed
+    /// rewards of the claimers only and records the claimed amounts.
+    #[tokio::test]
+    async fn test_delete_claimed_rewards() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator = Validator::fake();
+            let validator_db: ValidatorDb =
+                diesel::insert_into(validators::table)
+                    .values(ValidatorInsertDb::from_validator(
+                        validator.clone(),
+                    ))
+                    .get_result(conn)
+                    .context("Failed to insert validator")?;
+
+            let claimer = Id::Account("tnam1claimer".to_string());
+            let other = Id::Account("tnam1other".to_string());
+
+            diesel::insert_into(pos_rewards::table)
+                .values(
+                    [&claimer, &other]
+                        .into_iter()
+                        .map(|owner| PosRewardInsertDb {
+                            owner: owner.to_string(),
+                            validator_id: validator_db.id,
+                            raw_amount: BigDecimal::from(100),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .context("Failed to insert pos rewards")?;
+
+            let claim = RewardClaim {
+                delegation_pair: DelegationPair {
+                    validator_address: validator.address.clone(),
+                    delegator_address: claimer.clone(),
+                },
+                inner_tx_id: Id::Hash("a".repeat(64)),
+                amount: Amount::from(BigDecimal::from(120)),
+            };
+
+            delete_claimed_rewards(conn, HashSet::from([claim]), 12)?;
+
+            let owners = pos_rewards::table
+                .select(pos_rewards::owner)
+                .load::<String>(conn)?;
+            assert_eq!(owners, vec![other.to_string()]);
+
+            let ledger = pos_reward_ledger::table
+                .select(PosRewardLedgerDb::as_select())
+                .load::<PosRewardLedgerDb>(conn)?;
+            assert_eq!(ledger.len(), 1);
+            assert_eq!(ledger[0].owner, claimer.to_string());
+            assert_eq!(ledger[0].validator_id, validator_db.id);
+            assert_eq!(ledger[0].epoch, 12);
+            assert!(matches!(ledger[0].kind, PosRewardLedgerKindDb::Claimed));
+            assert_eq!(ledger[0].raw_amount, BigDecimal::from(120));
+            assert_eq!(ledger[0].inner_tx_id, Some("a".repeat(64)));
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that the delete_claimed_rewards function records claims of
+    /// delegations whose rewards were not indexed yet.
+    #[tokio::test]
+    async fn test_delete_claimed_rewards_without_unclaimed_rewards() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator = Validator::fake();
+            seed_validator(conn, validator.clone())?;
+
+            let claim = RewardClaim {
+                delegation_pair: DelegationPair {
+                    validator_address: validator.address.clone(),
+                    delegator_address: Id::Account("tnam1claimer".to_string()),
+                },
+                inner_tx_id: Id::Hash("b".repeat(64)),
+                amount: Amount::from(BigDecimal::from(5)),
+            };
+
+            delete_claimed_rewards(conn, HashSet::from([claim]), 3)?;
+
+            let ledger = pos_reward_ledger::table
+                .select(PosRewardLedgerDb::as_select())
+                .load::<PosRewardLedgerDb>(conn)?;
+            assert_eq!(ledger.len(), 1);
+            assert_eq!(ledger[0].raw_amount, BigDecimal::from(5));
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that the delete_claimed_rewards function records a single claim
+    /// per delegation and epoch, and ignores claims of unknown validators.
+    #[tokio::test]
+    async fn test_delete_claimed_rewards_twice_in_epoch() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator = Validator::fake();
+            seed_validator(conn, validator.clone())?;
+
+            let claim =
+                |validator_address: Id, inner_tx_id: &str| RewardClaim {
+                    delegation_pair: DelegationPair {
+                        validator_address,
+                        delegator_address: Id::Account(
+                            "tnam1claimer".to_string(),
+                        ),
+                    },
+                    inner_tx_id: Id::Hash(inner_tx_id.repeat(64)),
+                    amount: Amount::from(BigDecimal::from(5)),
+                };
+
+            delete_claimed_rewards(
+                conn,
+                HashSet::from([
+                    claim(validator.address.clone(), "c"),
+                    claim(validator.address.clone(), "d"),
+                    claim(Id::Account("tnam1unknown".to_string()), "e"),
+                ]),
+                3,
+            )?;
+
+            let ledger = pos_reward_ledger::table
+                .select(PosRewardLedgerDb::as_select())
+                .load::<PosRewardLedgerDb>(conn)?;
+            assert_eq!(ledger.len(), 1);
+            assert_eq!(ledger[0].epoch, 3);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
     fn seed_bonds(
         conn: &mut PgConnection,
         validator: Validator,
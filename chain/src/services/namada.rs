@@ -10,7 +10,14 @@ use namada_sdk::address::{Address as NamadaSdkAddress, InternalAddress};
 use namada_sdk::collections::HashMap;
 use namada_sdk::hash::Hash;
 use namada_sdk::ibc::IbcTokenHash;
-use namada_sdk::ibc::storage::{ibc_trace_key_prefix, is_ibc_trace_key};
+use namada_sdk::ibc::clients::tendermint::types::ClientState as TmClientState;
+use namada_sdk::ibc::core::channel::types::channel::ChannelEnd;
+use namada_sdk::ibc::core::connection::types::ConnectionEnd;
+use namada_sdk::ibc::core::host::types::identifiers::ConnectionId;
+use namada_sdk::ibc::primitives::proto::{Any, Protobuf};
+use namada_sdk::ibc::storage::{
+    self as ibc_storage, ibc_trace_key_prefix, is_ibc_trace_key,
+};
 use namada_sdk::queries::RPC;
 use namada_sdk::rpc::{
     bonds_and_unbonds, query_native_token, query_proposal_by_id,
@@ -21,22 +28,24 @@ use namada_sdk::{rpc, token};
 use shared::balance::{Amount, Balance, Balances, TokenSupply};
 use shared::block::{BlockHeight, Epoch};
 use shared::bond::{Bond, BondAddresses, Bonds};
+use shared::checksums::Checksums;
+use shared::client::RpcClient;
+use shared::ibc::{IbcChannel, IbcChannelState};
 use shared::id::Id;
-use shared::proposal::{GovernanceProposal, TallyType};
+use shared::proposal::{GovernanceProposal, ProposalWasmCode, TallyType};
 use shared::token::{IbcRateLimit, IbcToken, Token};
 use shared::unbond::{Unbond, UnbondAddresses, Unbonds};
 use shared::utils::BalanceChange;
 use shared::validator::{Validator, ValidatorSet, ValidatorState};
 use shared::vote::{GovernanceVote, ProposalVoteKind};
 use subtle_encoding::hex;
-use tendermint_rpc::HttpClient;
 
 use super::utils::{
     default_retry, query_storage_bytes, query_storage_prefix,
-    query_storage_value,
+    query_storage_prefix_bytes, query_storage_value,
 };
 
-pub async fn get_native_token(client: &HttpClient) -> anyhow::Result<Id> {
+pub async fn get_native_token(client: &RpcClient) -> anyhow::Result<Id> {
     let operation = || async {
         RPC.shell()
             .native_token(client)
@@ -49,7 +58,7 @@ pub async fn get_native_token(client: &HttpClient) -> anyhow::Result<Id> {
 }
 
 pub async fn query_native_token_total_supply(
-    client: &HttpClient,
+    client: &RpcClient,
     native_token: &Id,
 ) -> anyhow::Result<Amount> {
     let native_token = NamadaSdkAddress::from_str(&native_token.to_string())
@@ -66,7 +75,7 @@ pub async fn query_native_token_total_supply(
 }
 
 pub async fn query_native_token_effective_supply(
-    client: &HttpClient,
+    client: &RpcClient,
 ) -> anyhow::Result<Amount> {
     let operation = || async {
         rpc::get_effective_native_supply(client)
@@ -79,7 +88,7 @@ pub async fn query_native_token_effective_supply(
 }
 
 pub async fn get_first_block_in_epoch(
-    client: &HttpClient,
+    client: &RpcClient,
 ) -> anyhow::Result<BlockHeight> {
     let operation = || async {
         RPC.shell()
@@ -93,7 +102,7 @@ pub async fn get_first_block_in_epoch(
 }
 
 pub async fn get_epoch_at_block_height(
-    client: &HttpClient,
+    client: &RpcClient,
     block_height: BlockHeight,
 ) -> anyhow::Result<Epoch> {
     let block_height = to_block_height(block_height);
@@ -116,7 +125,7 @@ pub async fn get_epoch_at_block_height(
 }
 
 pub async fn query_balance(
-    client: &HttpClient,
+    client: &RpcClient,
     balance_changes: &HashSet<BalanceChange>,
     block_height: BlockHeight,
 ) -> anyhow::Result<Balances> {
@@ -163,7 +172,7 @@ pub async fn query_balance(
         .await)
 }
 
-pub async fn query_tokens(client: &HttpClient) -> anyhow::Result<Vec<Token>> {
+pub async fn query_tokens(client: &RpcClient) -> anyhow::Result<Vec<Token>> {
     let ibc_tokens = query_ibc_tokens(client).await?;
     let native_token = query_native_token(client).await?;
 
@@ -182,7 +191,7 @@ pub async fn query_tokens(client: &HttpClient) -> anyhow::Result<Vec<Token>> {
 }
 
 async fn query_ibc_tokens(
-    client: &HttpClient,
+    client: &RpcClient,
 ) -> anyhow::Result<HashSet<IbcToken>> {
     let prefix = ibc_trace_key_prefix(None);
 
@@ -212,8 +221,153 @@ async fn query_ibc_tokens(
     Ok(tokens)
 }
 
+/// The connection a channel is built on, as seen from namada
+#[derive(Clone)]
+struct IbcConnection {
+    counterparty_connection_id: Option<String>,
+    client_id: String,
+    counterparty_client_id: String,
+    counterparty_chain_id: Option<String>,
+}
+
+/// Channels whose channel or connection end can't be read are skipped, so
+/// that a single broken channel doesn't stop the crawler
+pub async fn query_ibc_channels(
+    client: &RpcClient,
+    block_height: BlockHeight,
+) -> anyhow::Result<Vec<IbcChannel>> {
+    let prefix = ibc_storage::ibc_key("channelEnds")
+        .context("Failed to build the ibc channels key")?;
+
+    let Some(channel_ends) =
+        query_storage_prefix_bytes(client, &prefix, Some(block_height)).await?
+    else {
+        return Ok(vec![]);
+    };
+
+    let mut connections: HashMap<ConnectionId, Option<IbcConnection>> =
+        HashMap::new();
+    let mut channels = vec![];
+
+    for (key, value) in channel_ends {
+        let Ok((port_id, channel_id)) = ibc_storage::port_channel_id(&key)
+        else {
+            continue;
+        };
+        let channel_end = match ChannelEnd::decode_vec(&value) {
+            Ok(channel_end) => channel_end,
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping channel {}/{}, failed to decode its end: {}",
+                    port_id,
+                    channel_id,
+                    e
+                );
+                continue;
+            }
+        };
+
+        // Channels are built on a single connection hop
+        let Some(connection_id) = channel_end.connection_hops.first() else {
+            continue;
+        };
+        let connection = match connections.get(connection_id) {
+            Some(connection) => connection.clone(),
+            None => {
+                let connection =
+                    query_ibc_connection(client, connection_id, block_height)
+                        .await?;
+                connections.insert(connection_id.clone(), connection.clone());
+                connection
+            }
+        };
+        let Some(connection) = connection else {
+            tracing::warn!(
+                "Skipping channel {}/{}, connection {} can't be read",
+                port_id,
+                channel_id,
+                connection_id
+            );
+            continue;
+        };
+
+        channels.push(IbcChannel {
+            port_id: port_id.to_string(),
+            channel_id: channel_id.to_string(),
+            state: IbcChannelState::from(&channel_end.state),
+            counterparty_port_id: channel_end.remote.port_id.to_string(),
+            counterparty_channel_id: channel_end
+                .remote
+                .channel_id
+                .map(|channel_id| channel_id.to_string()),
+            connection_id: connection_id.to_string(),
+            counterparty_connection_id: connection.counterparty_connection_id,
+            client_id: connection.client_id,
+            counterparty_client_id: connection.counterparty_client_id,
+            counterparty_chain_id: connection.counterparty_chain_id,
+        });
+    }
+
+    Ok(channels)
+}
+
+/// Returns `None` when the connection is missing or can't be decoded
+async fn query_ibc_connection(
+    client: &RpcClient,
+    connection_id: &ConnectionId,
+    block_height: BlockHeight,
+) -> anyhow::Result<Option<IbcConnection>> {
+    let Some(connection_end) = query_storage_bytes(
+        client,
+        &ibc_storage::connection_key(connection_id),
+        Some(block_height),
+    )
+    .await?
+    else {
+        tracing::warn!("Connection {} not found", connection_id);
+        return Ok(None);
+    };
+    let connection_end = match ConnectionEnd::decode_vec(&connection_end) {
+        Ok(connection_end) => connection_end,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to decode connection end {}: {}",
+                connection_id,
+                e
+            );
+            return Ok(None);
+        }
+    };
+
+    let client_state = query_storage_bytes(
+        client,
+        &ibc_storage::client_state_key(connection_end.client_id()),
+        Some(block_height),
+    )
+    .await?;
+
+    // Only tendermint light clients track the chain id of the counterparty
+    let counterparty_chain_id = client_state
+        .and_then(|client_state| {
+            <TmClientState as Protobuf<Any>>::decode_vec(&client_state).ok()
+        })
+        .map(|client_state| client_state.chain_id.to_string());
+
+    let counterparty = connection_end.counterparty();
+
+    Ok(Some(IbcConnection {
+        counterparty_connection_id: counterparty
+            .connection_id
+            .as_ref()
+            .map(|connection_id| connection_id.to_string()),
+        client_id: connection_end.client_id().to_string(),
+        counterparty_client_id: counterparty.client_id().to_string(),
+        counterparty_chain_id,
+    }))
+}
+
 pub async fn query_all_balances(
-    client: &HttpClient,
+    client: &RpcClient,
     height: BlockHeight,
 ) -> anyhow::Result<Balances> {
     let tokens = query_tokens(client).await?;
@@ -228,7 +382,7 @@ pub async fn query_all_balances(
 }
 
 async fn add_balance(
-    client: &HttpClient,
+    client: &RpcClient,
     token: Token,
     height: BlockHeight,
 ) -> anyhow::Result<Vec<Balance>> {
@@ -271,7 +425,7 @@ async fn add_balance(
 }
 
 pub async fn query_last_block_height(
-    client: &HttpClient,
+    client: &RpcClient,
 ) -> anyhow::Result<BlockHeight> {
     let operation = || async {
         let height = RPC
@@ -291,7 +445,7 @@ pub async fn query_last_block_height(
 // TODO: this can be improved / optimized(bonds and unbonds can be processed in
 // parallel)
 pub async fn query_all_bonds_and_unbonds(
-    client: &HttpClient,
+    client: &RpcClient,
     source: Option<Id>,
     target: Option<Id>,
 ) -> anyhow::Result<(Bonds, Unbonds)> {
@@ -371,7 +525,8 @@ pub async fn query_all_bonds_and_unbonds(
 }
 
 pub async fn query_all_proposals(
-    client: &HttpClient,
+    client: &RpcClient,
+    checksums: &Checksums,
 ) -> anyhow::Result<Vec<GovernanceProposal>> {
     let last_proposal_id_key =
         namada_governance::storage::keys::get_counter_key();
@@ -398,6 +553,8 @@ pub async fn query_all_proposals(
         let proposal_data = match proposal_type {
             namada_governance::ProposalType::DefaultWithWasm(_) => {
                 let wasm_code = query_proposal_code(client, id).await?;
+                governance_proposal.wasm_code =
+                    Some(ProposalWasmCode::new(&wasm_code, checksums));
                 let hex_encoded = String::from_utf8(hex::encode(wasm_code))
                     .unwrap_or_default();
                 Some(hex_encoded)
@@ -421,7 +578,7 @@ pub async fn query_all_proposals(
 }
 
 pub async fn query_proposal_code(
-    client: &HttpClient,
+    client: &RpcClient,
     proposal_id: u64,
 ) -> anyhow::Result<Vec<u8>> {
     let proposal_code_key =
@@ -436,7 +593,7 @@ pub async fn query_proposal_code(
 }
 
 pub async fn query_next_governance_id(
-    client: &HttpClient,
+    client: &RpcClient,
     block_height: BlockHeight,
 ) -> anyhow::Result<u64> {
     // For block_height 0 the next id is always 0
@@ -460,7 +617,7 @@ pub async fn query_next_governance_id(
 }
 
 pub async fn query_bonds(
-    client: &HttpClient,
+    client: &RpcClient,
     addresses: HashSet<BondAddresses>,
 ) -> anyhow::Result<Vec<(Id, Id, Option<Bond>)>> {
     let nested_bonds = futures::stream::iter(addresses)
@@ -497,7 +654,7 @@ pub async fn query_bonds(
 }
 
 pub async fn query_unbonds(
-    client: &HttpClient,
+    client: &RpcClient,
     addresses: HashSet<UnbondAddresses>,
 ) -> anyhow::Result<Unbonds> {
     let nested_unbonds = futures::stream::iter(addresses)
@@ -570,7 +727,7 @@ pub async fn query_unbonds(
     anyhow::Ok(unbonds)
 }
 
-pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
+pub async fn get_current_epoch(client: &RpcClient) -> anyhow::Result<Epoch> {
     let operation = || async {
         rpc::query_epoch(client)
             .await
@@ -582,7 +739,7 @@ pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
 }
 
 pub async fn get_all_consensus_validators_addresses_at(
-    client: &HttpClient,
+    client: &RpcClient,
     epoch: u32,
     native_token: Id,
 ) -> anyhow::Result<HashSet<BalanceChange>> {
@@ -607,7 +764,7 @@ pub async fn get_all_consensus_validators_addresses_at(
 }
 
 pub async fn query_tx_code_hash(
-    client: &HttpClient,
+    client: &RpcClient,
     tx_code_path: &str,
 ) -> Option<String> {
     let storage_key = Key::wasm_hash(tx_code_path);
@@ -623,7 +780,7 @@ pub async fn query_tx_code_hash(
 }
 
 pub async fn is_steward(
-    client: &HttpClient,
+    client: &RpcClient,
     address: &Id,
 ) -> anyhow::Result<bool> {
     let address = NamadaSdkAddress::from(address.clone());
@@ -633,7 +790,7 @@ pub async fn is_steward(
 }
 
 pub async fn query_tallies(
-    client: &HttpClient,
+    client: &RpcClient,
     proposals: Vec<GovernanceProposal>,
 ) -> anyhow::Result<Vec<(GovernanceProposal, TallyType)>> {
     let proposals = futures::stream::iter(proposals)
@@ -652,7 +809,7 @@ pub async fn query_tallies(
 }
 
 pub async fn query_all_votes(
-    client: &HttpClient,
+    client: &RpcClient,
     proposals_ids: Vec<u64>,
 ) -> anyhow::Result<HashSet<GovernanceVote>> {
     let votes = futures::stream::iter(proposals_ids)
@@ -713,7 +870,7 @@ pub async fn query_all_votes(
 }
 
 pub async fn get_validator_set_at_epoch(
-    client: &HttpClient,
+    client: &RpcClient,
     epoch: Epoch,
 ) -> anyhow::Result<ValidatorSet> {
     let namada_epoch = NamadaSdkEpoch::from(epoch as u64);
@@ -799,7 +956,7 @@ pub async fn get_validator_set_at_epoch(
 }
 
 pub async fn get_validator_namada_address(
-    client: &HttpClient,
+    client: &RpcClient,
     tm_addr: &Id,
 ) -> anyhow::Result<Option<Id>> {
     let operation = || async {
@@ -831,7 +988,7 @@ pub fn query_native_addresses_balance_change(
     .collect::<HashSet<_>>()
 }
 
-pub async fn query_pipeline_length(client: &HttpClient) -> anyhow::Result<u64> {
+pub async fn query_pipeline_length(client: &RpcClient) -> anyhow::Result<u64> {
     let operation = || async {
         rpc::get_pos_params(client)
             .await
@@ -849,7 +1006,7 @@ pub(super) fn to_block_height(
 }
 
 pub async fn get_pgf_receipients(
-    client: &HttpClient,
+    client: &RpcClient,
     native_token: Id,
 ) -> HashSet<BalanceChange> {
     let payments = || async {
@@ -870,7 +1027,7 @@ pub async fn get_pgf_receipients(
 }
 
 pub async fn get_native_token_supply(
-    client: &HttpClient,
+    client: &RpcClient,
     native_token: &Id,
     epoch: u32,
 ) -> anyhow::Result<TokenSupply> {
@@ -891,7 +1048,7 @@ pub async fn get_native_token_supply(
 }
 
 pub async fn get_token_supply(
-    client: &HttpClient,
+    client: &RpcClient,
     token: String,
     epoch: u32,
 ) -> anyhow::Result<TokenSupply> {
@@ -918,7 +1075,7 @@ pub async fn get_token_supply(
 }
 
 pub async fn get_throughput_rate_limit(
-    client: &HttpClient,
+    client: &RpcClient,
     token: String,
     epoch: u32,
 ) -> anyhow::Result<IbcRateLimit> {
@@ -946,7 +1103,7 @@ pub async fn get_throughput_rate_limit(
 }
 
 pub async fn get_rate_limits_for_tokens<I>(
-    client: &HttpClient,
+    client: &RpcClient,
     tokens: I,
     epoch: u32,
 ) -> anyhow::Result<Vec<IbcRateLimit>>
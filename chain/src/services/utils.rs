@@ -5,14 +5,14 @@ use namada_sdk::borsh::BorshDeserialize;
 use namada_sdk::queries::RPC;
 use namada_sdk::storage::{self, PrefixValue};
 use shared::block::BlockHeight;
-use tendermint_rpc::HttpClient;
+use shared::client::RpcClient;
 use tokio::time::sleep;
 
 /// Query a range of storage values with a matching prefix and decode them with
 /// [`BorshDeserialize`]. Returns an iterator of the storage keys paired with
 /// their associated values.
 pub async fn query_storage_prefix<T>(
-    client: &HttpClient,
+    client: &RpcClient,
     key: &storage::Key,
     height: Option<BlockHeight>,
 ) -> anyhow::Result<Option<impl Iterator<Item = (storage::Key, T)>>>
@@ -45,8 +45,42 @@ where
     })
 }
 
+/// Like [`query_storage_prefix`], for values that are not borsh encoded (e.g.
+/// the protobuf encoded IBC state)
+pub async fn query_storage_prefix_bytes(
+    client: &RpcClient,
+    key: &storage::Key,
+    height: Option<BlockHeight>,
+) -> anyhow::Result<Option<impl Iterator<Item = (storage::Key, Vec<u8>)>>> {
+    let operation = || async {
+        RPC.shell()
+            .storage_prefix(
+                client,
+                None,
+                height.map(super::namada::to_block_height),
+                false,
+                key,
+            )
+            .await
+            .context("failed to query storage prefix")
+    };
+
+    let values = default_retry(operation).await?;
+
+    Ok(if values.data.is_empty() {
+        None
+    } else {
+        Some(
+            values
+                .data
+                .into_iter()
+                .map(|PrefixValue { key, value }| (key, value)),
+        )
+    })
+}
+
 pub async fn query_storage_value<T>(
-    client: &HttpClient,
+    client: &RpcClient,
     key: &storage::Key,
     height: Option<BlockHeight>,
 ) -> anyhow::Result<Option<T>>
@@ -78,7 +112,7 @@ where
 }
 
 pub async fn query_storage_bytes(
-    client: &HttpClient,
+    client: &RpcClient,
     key: &storage::Key,
     height: Option<BlockHeight>,
 ) -> anyhow::Result<Option<Vec<u8>>> {
@@ -2,6 +2,7 @@ use core::fmt;
 use std::fmt::Display;
 
 use shared::log_config::LogConfig;
+use shared::metrics::MetricsConfig;
 
 #[derive(clap::ValueEnum, Clone, Debug, Copy)]
 pub enum CargoEnv {
@@ -17,8 +18,15 @@ impl Display for CargoEnv {
 
 #[derive(clap::Parser)]
 pub struct AppConfig {
-    #[clap(long, env)]
-    pub tendermint_url: String,
+    #[clap(
+        long,
+        env,
+        value_delimiter = ',',
+        required = true,
+        help = "Comma separated list of RPC urls, requests fail over between \
+                them"
+    )]
+    pub tendermint_url: Vec<String>,
 
     #[clap(long, env, default_value_t = 60)]
     pub sleep_for: u64,
@@ -28,4 +36,7 @@ pub struct AppConfig {
 
     #[clap(flatten)]
     pub log: LogConfig,
+
+    #[clap(flatten)]
+    pub metrics: MetricsConfig,
 }
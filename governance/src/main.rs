@@ -14,30 +14,31 @@ use namada_governance::storage::proposal::{AddRemove, PGFAction, PGFTarget};
 use namada_sdk::time::DateTimeUtc;
 use orm::migrations::run_migrations;
 use shared::balance::Amount as NamadaAmount;
+use shared::client::RpcClient;
 use shared::crawler;
 use shared::crawler_state::{CrawlerName, IntervalCrawlerState};
 use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
 use shared::id::Id;
+use shared::metrics::{self, Stage};
 use shared::pgf::{PaymentKind, PaymentRecurrence, PgfAction, PgfPayment};
-use tendermint_rpc::HttpClient;
-use tendermint_rpc::client::CompatMode;
+use shared::proposal::{GovernanceProposalEvent, GovernanceProposalResult};
 use tokio::sync::{Mutex, MutexGuard};
 use tokio::time::Instant;
 
+/// Failed voting power snapshots after which an ended proposal is no longer
+/// retried
+const MAX_VOTING_POWER_ATTEMPTS: i32 = 5;
+
 #[tokio::main]
 async fn main() -> Result<(), MainError> {
     let config = AppConfig::parse();
 
     config.log.init();
+    config.metrics.init(CrawlerName::Governance);
 
     tracing::info!("version: {}", env!("VERGEN_GIT_SHA").to_string());
 
-    let client = Arc::new(
-        HttpClient::builder(config.tendermint_url.as_str().parse().unwrap())
-            .compat_mode(CompatMode::V0_37)
-            .build()
-            .unwrap(),
-    );
+    let client = Arc::new(RpcClient::new(&config.tendermint_url).unwrap());
 
     let app_state = AppState::new(config.database_url).into_db_error()?;
 
@@ -74,7 +75,7 @@ async fn main() -> Result<(), MainError> {
 
 async fn crawling_fn(
     conn: Arc<Object>,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
     instant: Arc<Mutex<Instant>>,
     sleep_for: u64,
 ) -> Result<(), MainError> {
@@ -95,6 +96,8 @@ async fn crawling_fn(
 
     tracing::debug!("Querying governance proposals...");
 
+    let start = Instant::now();
+
     let epoch = namada_service::query_last_epoch(&client)
         .await
         .into_rpc_error()?;
@@ -117,7 +120,7 @@ async fn crawling_fn(
 
     let proposals_statuses = namada_service::get_governance_proposals_updates(
         &client,
-        running_governance_proposals,
+        running_governance_proposals.clone(),
         epoch as u32,
     )
     .await
@@ -127,6 +130,80 @@ async fn crawling_fn(
         proposals_statuses.len()
     );
 
+    let proposal_events = proposals_statuses
+        .iter()
+        .filter_map(|status| {
+            let proposal = running_governance_proposals
+                .iter()
+                .find(|proposal| proposal.id == status.id)?;
+
+            Some(GovernanceProposalEvent::from_status(
+                status,
+                proposal.voting_start_epoch as u32,
+                proposal.voting_end_epoch as u32,
+                epoch,
+            ))
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let tallied_proposals = running_governance_proposals
+        .into_iter()
+        .filter(|proposal| {
+            proposals_statuses.iter().any(|status| {
+                status.id == proposal.id
+                    && matches!(
+                        status.result,
+                        GovernanceProposalResult::Passed
+                            | GovernanceProposalResult::Rejected
+                    )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let proposals_without_voting_power = conn
+        .interact(move |conn| {
+            repository::governance::get_proposals_without_voting_power(
+                conn,
+                MAX_VOTING_POWER_ATTEMPTS,
+            )
+        })
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()?;
+
+    let snapshotted_proposals = tallied_proposals
+        .into_iter()
+        .chain(proposals_without_voting_power)
+        .collect::<Vec<_>>();
+    let snapshotted_proposal_ids = snapshotted_proposals
+        .iter()
+        .map(|proposal| proposal.id)
+        .collect::<Vec<_>>();
+
+    // Proposals whose snapshot fails are retried at the next rounds, up to
+    // MAX_VOTING_POWER_ATTEMPTS times
+    let proposals_voting_power =
+        namada_service::get_governance_proposals_voting_power(
+            &client,
+            snapshotted_proposals,
+        )
+        .await;
+    tracing::debug!(
+        "Got {} proposals voting power snapshots...",
+        proposals_voting_power.len()
+    );
+
+    let failed_voting_power = snapshotted_proposal_ids
+        .into_iter()
+        .filter(|id| {
+            !proposals_voting_power
+                .iter()
+                .any(|voting_power| voting_power.proposal_id == *id)
+        })
+        .collect::<Vec<_>>();
+
     let pgf_payments = conn
         .interact(move |conn| {
             repository::governance::get_all_pgf_executed_proposals_data(
@@ -224,6 +301,10 @@ async fn crawling_fn(
         pgf_payments = pgf_payments.len(),
         "Queried governance proposals successfully"
     );
+    metrics::record_stage_duration(Stage::Rpc, start.elapsed());
+
+    let pgf_payments_count = pgf_payments.len();
+    let db_start = Instant::now();
 
     conn.interact(move |conn| {
         conn.build_transaction().read_write().run(
@@ -236,6 +317,23 @@ async fn crawling_fn(
                     )?;
                 }
 
+                repository::governance::insert_proposal_events(
+                    transaction_conn,
+                    proposal_events,
+                )?;
+
+                for voting_power in proposals_voting_power {
+                    repository::governance::update_proposal_voting_power(
+                        transaction_conn,
+                        voting_power,
+                    )?;
+                }
+
+                repository::governance::record_failed_voting_power(
+                    transaction_conn,
+                    failed_voting_power,
+                )?;
+
                 repository::pgf::update_pgf(transaction_conn, pgf_payments)?;
 
                 repository::crawler_state::upsert_crawler_state(
@@ -253,6 +351,8 @@ async fn crawling_fn(
     .into_db_error()?;
 
     tracing::info!(sleep_for = sleep_for, "Inserted governance into database");
+    metrics::record_stage_duration(Stage::Db, db_start.elapsed());
+    metrics::record_inserted_rows("public_good_funding", pgf_payments_count);
 
     // Once we are done processing, we reset the instant
     *instant = Instant::now();
@@ -1,15 +1,21 @@
 use anyhow::Context;
+use bigdecimal::BigDecimal;
 use diesel::connection::DefaultLoadingMode;
+use diesel::sql_types::{Array, Bool, Integer, Numeric, Text};
+use diesel::upsert::excluded;
 use diesel::{
     BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl,
-    RunQueryDsl,
+    RunQueryDsl, sql_query,
 };
 use orm::governance_proposal::{
     GovernanceProposalKindDb, GovernanceProposalResultDb,
     GovernanceProposalUpdateStatusDb,
 };
-use orm::schema::governance_proposals;
+use orm::proposal_events::ProposalEventInsertDb;
+use orm::schema::{governance_proposals, proposal_events};
+use shared::proposal::GovernanceProposalEvent;
 use shared::utils::GovernanceProposalShort;
+use shared::vote::GovernanceProposalVotingPower;
 
 pub fn get_all_running_proposals(
     conn: &mut PgConnection,
@@ -42,6 +48,46 @@ pub fn get_all_running_proposals(
         .collect::<Result<Vec<GovernanceProposalShort>, _>>()
 }
 
+/// Ended proposals whose voting power was not snapshotted yet, either because
+/// they ended before voting power was indexed or because the query failed.
+/// Proposals whose snapshot failed `max_attempts` times are left out, as the
+/// node most likely can't serve their end epoch anymore.
+pub fn get_proposals_without_voting_power(
+    conn: &mut PgConnection,
+    max_attempts: i32,
+) -> anyhow::Result<Vec<GovernanceProposalShort>> {
+    governance_proposals::table
+        .filter(
+            governance_proposals::dsl::result
+                .eq(GovernanceProposalResultDb::Passed)
+                .or(governance_proposals::dsl::result
+                    .eq(GovernanceProposalResultDb::Rejected)),
+        )
+        .filter(governance_proposals::dsl::total_voting_power.is_null())
+        .filter(
+            governance_proposals::dsl::voting_power_attempts.lt(max_attempts),
+        )
+        .select((
+            governance_proposals::dsl::id,
+            governance_proposals::dsl::start_epoch,
+            governance_proposals::dsl::end_epoch,
+        ))
+        .load::<(i32, i32, i32)>(conn)
+        .context("Failed to get governance proposals from db")
+        .map(|proposals| {
+            proposals
+                .into_iter()
+                .map(|(id, voting_start_epoch, voting_end_epoch)| {
+                    GovernanceProposalShort {
+                        id: id as u64,
+                        voting_start_epoch: voting_start_epoch as u64,
+                        voting_end_epoch: voting_end_epoch as u64,
+                    }
+                })
+                .collect()
+        })
+}
+
 pub fn get_all_pgf_executed_proposals_data(
     conn: &mut PgConnection,
     current_epoch: u32,
@@ -84,3 +130,109 @@ pub fn update_proposal_status(
 
     Ok(())
 }
+
+pub fn update_proposal_voting_power(
+    transaction_conn: &mut PgConnection,
+    voting_power: GovernanceProposalVotingPower,
+) -> anyhow::Result<()> {
+    let proposal_id = voting_power.proposal_id as i32;
+
+    diesel::update(governance_proposals::table.find(proposal_id))
+        .set(
+            governance_proposals::dsl::total_voting_power
+                .eq(BigDecimal::from(voting_power.total_voting_power)),
+        )
+        .execute(transaction_conn)
+        .context("Failed to update proposal total voting power in db")?;
+
+    let votes = voting_power.votes;
+    let addresses = votes
+        .iter()
+        .map(|vote| vote.address.to_string())
+        .collect::<Vec<_>>();
+    let voting_powers = votes
+        .iter()
+        .map(|vote| BigDecimal::from(vote.voting_power.clone()))
+        .collect::<Vec<_>>();
+    let is_validator = votes
+        .iter()
+        .map(|vote| vote.is_validator)
+        .collect::<Vec<_>>();
+    let overrides_validator = votes
+        .iter()
+        .map(|vote| vote.overrides_validator)
+        .collect::<Vec<_>>();
+
+    // Update all the votes of the proposal at once
+    sql_query(
+        "UPDATE governance_votes SET voting_power = vote_power.voting_power, \
+         is_validator = vote_power.is_validator, overrides_validator = \
+         vote_power.overrides_validator FROM unnest($1, $2, $3, $4) AS \
+         vote_power(voter_address, voting_power, is_validator, \
+         overrides_validator) WHERE governance_votes.proposal_id = $5 AND \
+         governance_votes.voter_address = vote_power.voter_address",
+    )
+    .bind::<Array<Text>, _>(addresses)
+    .bind::<Array<Numeric>, _>(voting_powers)
+    .bind::<Array<Bool>, _>(is_validator)
+    .bind::<Array<Bool>, _>(overrides_validator)
+    .bind::<Integer, _>(proposal_id)
+    .execute(transaction_conn)
+    .context("Failed to update votes voting power in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn record_failed_voting_power(
+    transaction_conn: &mut PgConnection,
+    proposal_ids: Vec<u64>,
+) -> anyhow::Result<()> {
+    diesel::update(
+        governance_proposals::table.filter(
+            governance_proposals::dsl::id.eq_any(
+                proposal_ids
+                    .into_iter()
+                    .map(|id| id as i32)
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+    )
+    .set(
+        governance_proposals::dsl::voting_power_attempts
+            .eq(governance_proposals::dsl::voting_power_attempts + 1),
+    )
+    .execute(transaction_conn)
+    .context("Failed to record failed voting power snapshots in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn insert_proposal_events(
+    transaction_conn: &mut PgConnection,
+    events: Vec<GovernanceProposalEvent>,
+) -> anyhow::Result<()> {
+    diesel::insert_into(proposal_events::table)
+        .values::<Vec<ProposalEventInsertDb>>(
+            events
+                .into_iter()
+                .map(ProposalEventInsertDb::from)
+                .collect(),
+        )
+        .on_conflict((
+            proposal_events::proposal_id,
+            proposal_events::kind,
+            proposal_events::epoch,
+        ))
+        .do_update()
+        .set((
+            proposal_events::result.eq(excluded(proposal_events::result)),
+            proposal_events::yay_votes.eq(excluded(proposal_events::yay_votes)),
+            proposal_events::nay_votes.eq(excluded(proposal_events::nay_votes)),
+            proposal_events::abstain_votes
+                .eq(excluded(proposal_events::abstain_votes)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update proposal events in db")?;
+
+    anyhow::Ok(())
+}
@@ -1,15 +1,18 @@
 use anyhow::Context;
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
+use namada_governance::utils::ProposalVotes;
 use namada_sdk::queries::RPC;
 use namada_sdk::rpc;
+use namada_sdk::state::Epoch as NamadaEpoch;
 use shared::block::{BlockHeight, Epoch};
+use shared::client::RpcClient;
 use shared::id::Id;
 use shared::proposal::{GovernanceProposalResult, GovernanceProposalStatus};
 use shared::utils::GovernanceProposalShort;
-use tendermint_rpc::HttpClient;
+use shared::vote::{GovernanceProposalVotingPower, GovernanceVotePower};
 
 pub async fn query_latest_block_height(
-    client: &HttpClient,
+    client: &RpcClient,
 ) -> anyhow::Result<BlockHeight> {
     let block = rpc::query_block(client)
         .await
@@ -17,14 +20,14 @@ pub async fn query_latest_block_height(
     Ok(block.map(|block| block.height.0 as u32).unwrap_or(0_u32))
 }
 
-pub async fn query_last_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
+pub async fn query_last_epoch(client: &RpcClient) -> anyhow::Result<Epoch> {
     let epoch = rpc::query_epoch(client)
         .await
         .with_context(|| "Failed to query Namada's epoch epoch".to_string())?;
     Ok(epoch.0 as Epoch)
 }
 
-pub async fn get_native_token(client: &HttpClient) -> anyhow::Result<Id> {
+pub async fn get_native_token(client: &RpcClient) -> anyhow::Result<Id> {
     let native_token = RPC
         .shell()
         .native_token(client)
@@ -34,7 +37,7 @@ pub async fn get_native_token(client: &HttpClient) -> anyhow::Result<Id> {
 }
 
 pub async fn get_governance_proposals_updates(
-    client: &HttpClient,
+    client: &RpcClient,
     proposal_data: Vec<GovernanceProposalShort>,
     current_epoch: Epoch,
 ) -> anyhow::Result<Vec<GovernanceProposalStatus>> {
@@ -87,3 +90,103 @@ pub async fn get_governance_proposals_updates(
         .collect::<Vec<_>>()
         .await)
 }
+
+/// Snapshots the voting power of the given proposals, skipping the ones that
+/// can't be queried
+pub async fn get_governance_proposals_voting_power(
+    client: &RpcClient,
+    proposal_data: Vec<GovernanceProposalShort>,
+) -> Vec<GovernanceProposalVotingPower> {
+    futures::stream::iter(proposal_data)
+        .map(|proposal| async move {
+            let proposal_id = proposal.id;
+
+            query_proposal_voting_power(client, proposal)
+                .await
+                .inspect_err(|error| {
+                    tracing::warn!(
+                        proposal_id,
+                        "Failed to snapshot proposal voting power: {:#}",
+                        error
+                    );
+                })
+                .ok()
+        })
+        .buffer_unordered(4)
+        .filter_map(futures::future::ready)
+        .collect()
+        .await
+}
+
+async fn query_proposal_voting_power(
+    client: &RpcClient,
+    proposal: GovernanceProposalShort,
+) -> anyhow::Result<GovernanceProposalVotingPower> {
+    // Voting power is snapshotted at the epoch the proposal is tallied at
+    let epoch = NamadaEpoch(proposal.voting_end_epoch);
+
+    let votes = rpc::query_proposal_votes(client, proposal.id)
+        .await
+        .with_context(|| {
+            format!("Failed to query votes of proposal {}", proposal.id)
+        })?;
+
+    let total_voting_power = rpc::get_total_active_voting_power(client, epoch)
+        .await
+        .with_context(|| {
+            format!("Failed to query total voting power at epoch {}", epoch)
+        })?;
+
+    let votes_power = futures::stream::iter(votes)
+        .map(|vote| async move {
+            let voting_power = if vote.is_validator() {
+                rpc::get_validator_stake(client, epoch, &vote.validator).await
+            } else {
+                rpc::get_bond_amount_at(
+                    client,
+                    &vote.delegator,
+                    &vote.validator,
+                    epoch,
+                )
+                .await
+            }
+            .with_context(|| {
+                format!(
+                    "Failed to query voting power of {} at epoch {}",
+                    vote.delegator, epoch
+                )
+            })?;
+
+            anyhow::Ok((vote, voting_power))
+        })
+        .buffer_unordered(32)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let mut proposal_votes = ProposalVotes::default();
+    for (vote, voting_power) in votes_power {
+        if vote.is_validator() {
+            proposal_votes.add_validator(
+                &vote.validator,
+                voting_power,
+                vote.data,
+            );
+        } else {
+            proposal_votes.add_delegator(
+                &vote.delegator,
+                &vote.validator,
+                voting_power,
+                vote.data,
+            );
+        }
+    }
+
+    Ok(GovernanceProposalVotingPower {
+        proposal_id: proposal.id,
+        total_voting_power: total_voting_power.into(),
+        votes: GovernanceVotePower::from_proposal_votes(
+            proposal.id,
+            &proposal_votes,
+        ),
+    })
+}
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+CREATE INDEX index_blocks_timestamp ON blocks (timestamp);
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE token ADD COLUMN denomination SMALLINT;
+
+-- Same rule as `Token::denomination`: the native token uses its max decimal
+-- places, IBC tokens are stored in their base unit
+UPDATE token SET denomination = CASE token_type
+    WHEN 'native' THEN 6
+    ELSE 0
+END;
+
+ALTER TABLE token ALTER COLUMN denomination SET NOT NULL;
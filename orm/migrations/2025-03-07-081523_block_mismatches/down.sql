@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS block_mismatches;
+
+DROP TYPE IF EXISTS BLOCK_MISMATCH_KIND;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TYPE BLOCK_MISMATCH_KIND AS ENUM ('hash', 'parent_hash', 'app_hash');
+
+CREATE TABLE block_mismatches (
+    id SERIAL PRIMARY KEY,
+    height INT NOT NULL,
+    kind BLOCK_MISMATCH_KIND NOT NULL,
+    indexed_value VARCHAR(64) NOT NULL,
+    rpc_value VARCHAR(64) NOT NULL,
+    detected_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE block_mismatches ADD UNIQUE (height, kind, indexed_value, rpc_value);
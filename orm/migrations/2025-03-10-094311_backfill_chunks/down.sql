@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS backfill_chunks;
+
+DROP TYPE IF EXISTS BACKFILL_CHUNK_STATUS;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TYPE BACKFILL_CHUNK_STATUS AS ENUM ('pending', 'in_progress', 'done');
+
+CREATE TABLE backfill_chunks (
+    from_height INT PRIMARY KEY,
+    to_height INT NOT NULL,
+    status BACKFILL_CHUNK_STATUS NOT NULL DEFAULT 'pending',
+    last_processed_block INT,
+    worker VARCHAR,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX index_backfill_chunks_status ON backfill_chunks (status, from_height);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS index_blocks_epoch_proposer;
+
+DROP TABLE IF EXISTS validator_epoch_snapshots;
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE validator_epoch_snapshots (
+    id SERIAL PRIMARY KEY,
+    validator_id INT NOT NULL,
+    epoch INT NOT NULL,
+    voting_power INT NOT NULL,
+    max_commission VARCHAR NOT NULL,
+    commission VARCHAR NOT NULL,
+    state VALIDATOR_STATE NOT NULL,
+    CONSTRAINT fk_validator_id FOREIGN KEY(validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+ALTER TABLE validator_epoch_snapshots ADD UNIQUE (validator_id, epoch);
+
+CREATE INDEX index_validator_epoch_snapshots_epoch ON validator_epoch_snapshots (epoch);
+-- For counting the blocks proposed by a validator in an epoch
+CREATE INDEX index_blocks_epoch_proposer ON blocks (epoch, proposer);
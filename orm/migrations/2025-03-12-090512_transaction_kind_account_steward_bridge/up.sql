@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TYPE TRANSACTION_KIND ADD VALUE 'init_account';
+ALTER TYPE TRANSACTION_KIND ADD VALUE 'update_account';
+ALTER TYPE TRANSACTION_KIND ADD VALUE 'change_consensus_key';
+ALTER TYPE TRANSACTION_KIND ADD VALUE 'resign_steward';
+ALTER TYPE TRANSACTION_KIND ADD VALUE 'update_steward_commission';
+ALTER TYPE TRANSACTION_KIND ADD VALUE 'bridge_pool_transfer';
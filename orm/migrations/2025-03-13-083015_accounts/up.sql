@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE accounts (
+    address VARCHAR(45) PRIMARY KEY,
+    vp_code_hash VARCHAR,
+    public_keys VARCHAR[],
+    threshold INT,
+    created_at INT,
+    updated_at INT NOT NULL
+);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS index_governance_proposals_content_tsv;
+DROP INDEX IF EXISTS index_validators_email_trgm;
+DROP INDEX IF EXISTS index_validators_name_trgm;
+DROP INDEX IF EXISTS index_blocks_hash;
+
+-- pg_trgm is left installed, other objects of the database may use it
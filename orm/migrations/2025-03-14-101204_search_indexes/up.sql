@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX index_blocks_hash ON blocks USING HASH (hash);
+
+CREATE INDEX index_validators_name_trgm ON validators USING GIN (name gin_trgm_ops);
+CREATE INDEX index_validators_email_trgm ON validators USING GIN (email gin_trgm_ops);
+
+CREATE INDEX index_governance_proposals_content_tsv ON governance_proposals USING GIN (to_tsvector('simple', content));
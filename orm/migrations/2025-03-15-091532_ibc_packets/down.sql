@@ -0,0 +1,29 @@
+-- This file should undo anything in `up.sql`
+CREATE TYPE IBC_STATUS AS ENUM ('fail', 'success', 'timeout', 'unknown');
+
+CREATE TABLE ibc_ack (
+    id VARCHAR PRIMARY KEY,
+    tx_hash VARCHAR NOT NULL,
+    timeout BIGINT NOT NULL,
+    status IBC_STATUS NOT NULL
+);
+
+INSERT INTO ibc_ack (id, tx_hash, timeout, status)
+SELECT
+    id,
+    send_tx_id,
+    timeout_timestamp,
+    CASE status
+        WHEN 'acknowledged' THEN 'success'
+        WHEN 'failed' THEN 'fail'
+        WHEN 'timed_out' THEN 'timeout'
+        ELSE 'unknown'
+    END::IBC_STATUS
+FROM ibc_packets
+WHERE direction = 'outgoing' AND send_tx_id IS NOT NULL;
+
+DROP TABLE IF EXISTS ibc_packets;
+
+DROP TYPE IF EXISTS IBC_PACKET_STATUS;
+
+DROP TYPE IF EXISTS IBC_PACKET_DIRECTION;
@@ -0,0 +1,68 @@
+-- Your SQL goes here
+CREATE TYPE IBC_PACKET_DIRECTION AS ENUM ('outgoing', 'incoming');
+
+CREATE TYPE IBC_PACKET_STATUS AS ENUM ('pending', 'received', 'acknowledged', 'failed', 'timed_out');
+
+CREATE TABLE ibc_packets (
+    id VARCHAR PRIMARY KEY,
+    direction IBC_PACKET_DIRECTION NOT NULL,
+    source_port VARCHAR NOT NULL,
+    source_channel VARCHAR NOT NULL,
+    dest_port VARCHAR NOT NULL,
+    dest_channel VARCHAR NOT NULL,
+    sequence BIGINT NOT NULL,
+    denom VARCHAR,
+    amount NUMERIC(78, 0),
+    sender VARCHAR,
+    receiver VARCHAR,
+    timeout_timestamp BIGINT NOT NULL,
+    status IBC_PACKET_STATUS NOT NULL,
+    refunded BOOLEAN NOT NULL DEFAULT false,
+    send_tx_id VARCHAR,
+    recv_tx_id VARCHAR,
+    ack_tx_id VARCHAR,
+    timeout_tx_id VARCHAR
+);
+
+CREATE INDEX index_ibc_packets_source_channel_status ON ibc_packets (source_channel, status);
+CREATE INDEX index_ibc_packets_dest_channel_status ON ibc_packets (dest_channel, status);
+CREATE INDEX index_ibc_packets_send_tx_id ON ibc_packets (send_tx_id);
+CREATE INDEX index_ibc_packets_recv_tx_id ON ibc_packets (recv_tx_id);
+
+-- The ids of ibc_ack are `dest_port/dest_channel/source_port/source_channel/sequence`
+-- and only outgoing packets were indexed
+INSERT INTO ibc_packets (
+    id,
+    direction,
+    dest_port,
+    dest_channel,
+    source_port,
+    source_channel,
+    sequence,
+    timeout_timestamp,
+    status,
+    refunded,
+    send_tx_id
+)
+SELECT
+    id,
+    'outgoing',
+    split_part(id, '/', 1),
+    split_part(id, '/', 2),
+    split_part(id, '/', 3),
+    split_part(id, '/', 4),
+    CAST(split_part(id, '/', 5) AS BIGINT),
+    timeout,
+    CASE status
+        WHEN 'success' THEN 'acknowledged'
+        WHEN 'fail' THEN 'failed'
+        WHEN 'timeout' THEN 'timed_out'
+        ELSE 'pending'
+    END::IBC_PACKET_STATUS,
+    status IN ('fail', 'timeout'),
+    tx_hash
+FROM ibc_ack;
+
+DROP TABLE ibc_ack;
+
+DROP TYPE IBC_STATUS;
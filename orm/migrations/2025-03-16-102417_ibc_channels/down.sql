@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS ibc_channels;
+
+DROP TYPE IF EXISTS IBC_CHANNEL_STATE;
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TYPE IBC_CHANNEL_STATE AS ENUM ('uninitialized', 'init', 'try_open', 'open', 'closed');
+
+CREATE TABLE ibc_channels (
+    port_id VARCHAR NOT NULL,
+    channel_id VARCHAR NOT NULL,
+    state IBC_CHANNEL_STATE NOT NULL,
+    counterparty_port_id VARCHAR NOT NULL,
+    counterparty_channel_id VARCHAR,
+    connection_id VARCHAR NOT NULL,
+    counterparty_connection_id VARCHAR,
+    client_id VARCHAR NOT NULL,
+    counterparty_client_id VARCHAR NOT NULL,
+    counterparty_chain_id VARCHAR,
+    updated_at INT NOT NULL,
+    PRIMARY KEY (port_id, channel_id)
+);
+
+CREATE INDEX index_ibc_channels_counterparty_chain_id ON ibc_channels (counterparty_chain_id);
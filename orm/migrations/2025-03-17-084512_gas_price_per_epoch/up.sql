@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE gas_price_per_epoch (
+    id SERIAL PRIMARY KEY,
+    token VARCHAR NOT NULL,
+    epoch INT NOT NULL,
+    amount NUMERIC NOT NULL
+);
+
+ALTER TABLE gas_price_per_epoch ADD UNIQUE (token, epoch);
+
+CREATE INDEX index_gas_price_per_epoch_epoch ON gas_price_per_epoch (epoch);
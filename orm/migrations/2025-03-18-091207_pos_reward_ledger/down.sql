@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS pos_reward_ledger;
+
+DROP TYPE IF EXISTS POS_REWARD_LEDGER_KIND;
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+CREATE TYPE POS_REWARD_LEDGER_KIND AS ENUM ('accrued', 'claimed');
+
+CREATE TABLE pos_reward_ledger (
+    id SERIAL PRIMARY KEY,
+    owner VARCHAR NOT NULL,
+    validator_id INT NOT NULL,
+    epoch INT NOT NULL,
+    kind POS_REWARD_LEDGER_KIND NOT NULL,
+    raw_amount NUMERIC NOT NULL,
+    -- The amount bonded by the owner to the validator, for accrued rewards
+    bonded_amount NUMERIC,
+    -- The claim rewards inner transaction, for claimed rewards
+    inner_tx_id VARCHAR(64),
+    CONSTRAINT fk_validator_id FOREIGN KEY(validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+ALTER TABLE pos_reward_ledger ADD UNIQUE (owner, validator_id, epoch, kind);
+
+CREATE INDEX index_pos_reward_ledger_validator_id_epoch ON pos_reward_ledger (validator_id, epoch);
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE rewards_checkpoints (
+    epoch INT PRIMARY KEY,
+    last_validator_address VARCHAR NOT NULL,
+    last_delegator_address VARCHAR NOT NULL,
+    processed_pairs INT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
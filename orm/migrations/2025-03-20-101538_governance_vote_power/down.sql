@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE governance_proposals DROP COLUMN total_voting_power;
+
+ALTER TABLE governance_votes DROP COLUMN overrides_validator;
+ALTER TABLE governance_votes DROP COLUMN is_validator;
+ALTER TABLE governance_votes DROP COLUMN voting_power;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- Voting power counted towards the tally at the proposal end epoch
+ALTER TABLE governance_votes ADD COLUMN voting_power NUMERIC;
+ALTER TABLE governance_votes ADD COLUMN is_validator BOOLEAN;
+ALTER TABLE governance_votes ADD COLUMN overrides_validator BOOLEAN;
+
+ALTER TABLE governance_proposals ADD COLUMN total_voting_power NUMERIC;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE proposal_events;
+
+DROP TYPE PROPOSAL_EVENT_KIND;
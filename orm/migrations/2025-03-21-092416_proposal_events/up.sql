@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TYPE PROPOSAL_EVENT_KIND AS ENUM ('created', 'voting_started', 'tally', 'ended', 'activated');
+
+CREATE TABLE proposal_events (
+    id SERIAL PRIMARY KEY,
+    proposal_id INT NOT NULL,
+    kind PROPOSAL_EVENT_KIND NOT NULL,
+    epoch INT NOT NULL,
+    block_height INT,
+    -- The init proposal inner transaction, for created events
+    inner_tx_id VARCHAR(64),
+    result GOVERNANCE_RESULT,
+    yay_votes VARCHAR,
+    nay_votes VARCHAR,
+    abstain_votes VARCHAR,
+    -- Whether the proposal carried wasm code and whether it ran successfully,
+    -- for activated events
+    has_proposal_code BOOLEAN,
+    proposal_code_succeeded BOOLEAN,
+    CONSTRAINT fk_proposal_id FOREIGN KEY(proposal_id) REFERENCES governance_proposals(id) ON DELETE CASCADE
+);
+
+ALTER TABLE proposal_events ADD UNIQUE (proposal_id, kind, epoch);
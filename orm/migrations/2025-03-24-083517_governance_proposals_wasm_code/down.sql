@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE governance_proposals DROP COLUMN wasm_code_name;
+ALTER TABLE governance_proposals DROP COLUMN wasm_code_size;
+ALTER TABLE governance_proposals DROP COLUMN wasm_code_hash;
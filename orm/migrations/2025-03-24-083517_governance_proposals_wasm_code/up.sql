@@ -0,0 +1,18 @@
+-- Your SQL goes here
+ALTER TABLE governance_proposals ADD COLUMN wasm_code_hash VARCHAR(64);
+ALTER TABLE governance_proposals ADD COLUMN wasm_code_size INT;
+ALTER TABLE governance_proposals ADD COLUMN wasm_code_name VARCHAR;
+
+-- The data of default with wasm proposals is the hex encoded wasm code
+UPDATE governance_proposals
+SET
+    wasm_code_hash = encode(sha256(decode(data, 'hex')), 'hex'),
+    wasm_code_size = length(decode(data, 'hex'))
+WHERE kind = 'default_with_wasm' AND data IS NOT NULL;
+
+-- Checksums are stored by wasm file name, while the crawlers name the wasm
+-- code without the .wasm extension
+UPDATE governance_proposals
+SET wasm_code_name = regexp_replace(checksums.key, '\.wasm$', '')
+FROM chain_parameters, jsonb_each_text(chain_parameters.checksums) AS checksums
+WHERE governance_proposals.wasm_code_hash = lower(checksums.value);
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE governance_proposals DROP COLUMN voting_power_attempts;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Failed voting power snapshots of an ended proposal, the backfill gives up
+-- after a few of them
+ALTER TABLE governance_proposals ADD COLUMN voting_power_attempts INTEGER NOT NULL DEFAULT 0;
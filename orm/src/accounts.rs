@@ -0,0 +1,44 @@
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use shared::account::AccountUpdate;
+
+use crate::schema::accounts;
+
+/// Columns left as `None` are not touched when upserting an existing account
+#[derive(Insertable, AsChangeset, Clone, Debug)]
+#[diesel(table_name = accounts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccountInsertDb {
+    pub address: String,
+    pub vp_code_hash: Option<String>,
+    pub public_keys: Option<Vec<Option<String>>>,
+    pub threshold: Option<i32>,
+    pub created_at: Option<i32>,
+    pub updated_at: i32,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = accounts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccountDb {
+    pub address: String,
+    pub vp_code_hash: Option<String>,
+    pub public_keys: Option<Vec<Option<String>>>,
+    pub threshold: Option<i32>,
+    pub created_at: Option<i32>,
+    pub updated_at: i32,
+}
+
+impl From<AccountUpdate> for AccountInsertDb {
+    fn from(value: AccountUpdate) -> Self {
+        Self {
+            address: value.address.to_string(),
+            vp_code_hash: value.vp_code_hash.map(|hash| hash.to_string()),
+            public_keys: value
+                .public_keys
+                .map(|pks| pks.into_iter().map(Some).collect()),
+            threshold: value.threshold.map(i32::from),
+            created_at: value.created_at.map(|height| height as i32),
+            updated_at: value.block_height as i32,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::backfill_chunks;
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::BackfillChunkStatus"]
+pub enum BackfillChunkStatusDb {
+    Pending,
+    InProgress,
+    Done,
+}
+
+#[derive(Insertable, Clone, Debug)]
+#[diesel(table_name = backfill_chunks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BackfillChunkInsertDb {
+    pub from_height: i32,
+    pub to_height: i32,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = backfill_chunks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BackfillChunkDb {
+    pub from_height: i32,
+    pub to_height: i32,
+    pub status: BackfillChunkStatusDb,
+    pub last_processed_block: Option<i32>,
+    pub worker: Option<String>,
+    pub updated_at: chrono::NaiveDateTime,
+}
@@ -1,8 +1,9 @@
 use diesel::{Insertable, Queryable, Selectable};
-use shared::block::Block;
+use serde::{Deserialize, Serialize};
+use shared::block::{Block, BlockMismatch, BlockMismatchKind};
 use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 
-use crate::schema::blocks;
+use crate::schema::{block_mismatches, blocks};
 
 #[derive(Insertable, Clone, Queryable, Selectable, Debug)]
 #[diesel(table_name = blocks)]
@@ -57,3 +58,54 @@ impl BlockInsertDb {
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::BlockMismatchKind"]
+pub enum BlockMismatchKindDb {
+    Hash,
+    ParentHash,
+    AppHash,
+}
+
+impl From<BlockMismatchKind> for BlockMismatchKindDb {
+    fn from(value: BlockMismatchKind) -> Self {
+        match value {
+            BlockMismatchKind::Hash => Self::Hash,
+            BlockMismatchKind::ParentHash => Self::ParentHash,
+            BlockMismatchKind::AppHash => Self::AppHash,
+        }
+    }
+}
+
+#[derive(Insertable, Clone, Debug)]
+#[diesel(table_name = block_mismatches)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BlockMismatchInsertDb {
+    pub height: i32,
+    pub kind: BlockMismatchKindDb,
+    pub indexed_value: String,
+    pub rpc_value: String,
+}
+
+impl From<BlockMismatch> for BlockMismatchInsertDb {
+    fn from(value: BlockMismatch) -> Self {
+        Self {
+            height: value.height as i32,
+            kind: BlockMismatchKindDb::from(value.kind),
+            indexed_value: value.indexed_value,
+            rpc_value: value.rpc_value,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = block_mismatches)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BlockMismatchDb {
+    pub id: i32,
+    pub height: i32,
+    pub kind: BlockMismatchKindDb,
+    pub indexed_value: String,
+    pub rpc_value: String,
+    pub detected_at: chrono::NaiveDateTime,
+}
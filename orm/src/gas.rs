@@ -2,9 +2,10 @@ use std::str::FromStr;
 
 use bigdecimal::BigDecimal;
 use diesel::{Insertable, Queryable, Selectable};
+use shared::block::Epoch;
 use shared::gas::{GasEstimation, GasPrice};
 
-use crate::schema::{gas_estimations, gas_price};
+use crate::schema::{gas_estimations, gas_price, gas_price_per_epoch};
 
 #[derive(Clone, Queryable, Selectable, Insertable)]
 #[diesel(table_name = gas_price)]
@@ -24,6 +25,37 @@ impl From<GasPrice> for GasPriceDb {
     }
 }
 
+#[derive(Clone, Queryable, Selectable)]
+#[diesel(table_name = gas_price_per_epoch)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GasPriceEpochDb {
+    pub id: i32,
+    pub token: String,
+    pub epoch: i32,
+    pub amount: BigDecimal,
+}
+
+#[derive(Clone, Insertable)]
+#[diesel(table_name = gas_price_per_epoch)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GasPriceEpochInsertDb {
+    pub token: String,
+    pub epoch: i32,
+    pub amount: BigDecimal,
+}
+
+impl GasPriceEpochInsertDb {
+    pub fn from(gas_price: GasPrice, epoch: Epoch) -> Self {
+        let GasPriceDb { token, amount } = GasPriceDb::from(gas_price);
+
+        Self {
+            token,
+            epoch: epoch as i32,
+            amount,
+        }
+    }
+}
+
 #[derive(Clone, Queryable, Selectable, Insertable)]
 #[diesel(table_name = gas_estimations)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -72,3 +104,24 @@ impl From<GasEstimation> for GasEstimationInsertDb {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use shared::balance::Amount;
+
+    use super::*;
+
+    #[test]
+    fn test_gas_price_per_epoch_from_gas_price() {
+        let gas_price = GasPrice {
+            token: "tnam1native".to_string(),
+            amount: Amount::from(BigDecimal::from(25)),
+        };
+
+        let gas_price_per_epoch = GasPriceEpochInsertDb::from(gas_price, 42);
+
+        assert_eq!(gas_price_per_epoch.token, "tnam1native");
+        assert_eq!(gas_price_per_epoch.epoch, 42);
+        assert_eq!(gas_price_per_epoch.amount, BigDecimal::from(25));
+    }
+}
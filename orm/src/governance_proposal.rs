@@ -1,3 +1,4 @@
+use bigdecimal::BigDecimal;
 use diesel::query_builder::AsChangeset;
 use diesel::{Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
@@ -86,6 +87,10 @@ pub struct GovernanceProposalDb {
     pub nay_votes: String,
     pub abstain_votes: String,
     pub result: GovernanceProposalResultDb,
+    pub total_voting_power: Option<BigDecimal>,
+    pub wasm_code_hash: Option<String>,
+    pub wasm_code_size: Option<i32>,
+    pub wasm_code_name: Option<String>,
 }
 
 #[derive(Serialize, Insertable, Clone)]
@@ -101,6 +106,9 @@ pub struct GovernanceProposalInsertDb {
     pub start_epoch: i32,
     pub end_epoch: i32,
     pub activation_epoch: i32,
+    pub wasm_code_hash: Option<String>,
+    pub wasm_code_size: Option<i32>,
+    pub wasm_code_name: Option<String>,
 }
 
 impl GovernanceProposalInsertDb {
@@ -108,6 +116,8 @@ impl GovernanceProposalInsertDb {
         proposal: GovernanceProposal,
         tally_type: TallyType,
     ) -> Self {
+        let wasm_code = proposal.wasm_code;
+
         Self {
             id: proposal.id as i32,
             content: proposal.content,
@@ -118,6 +128,9 @@ impl GovernanceProposalInsertDb {
             start_epoch: proposal.voting_start_epoch as i32,
             end_epoch: proposal.voting_end_epoch as i32,
             activation_epoch: proposal.activation_epoch as i32,
+            wasm_code_hash: wasm_code.as_ref().map(|code| code.hash.clone()),
+            wasm_code_size: wasm_code.as_ref().map(|code| code.size as i32),
+            wasm_code_name: wasm_code.and_then(|code| code.name),
         }
     }
 }
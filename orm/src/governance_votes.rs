@@ -1,3 +1,4 @@
+use bigdecimal::BigDecimal;
 use diesel::{Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
 use shared::vote::{GovernanceVote, ProposalVoteKind};
@@ -32,6 +33,9 @@ pub struct GovernanceProposalVoteDb {
     pub voter_address: String,
     pub kind: GovernanceVoteKindDb,
     pub proposal_id: i32,
+    pub voting_power: Option<BigDecimal>,
+    pub is_validator: Option<bool>,
+    pub overrides_validator: Option<bool>,
 }
 
 #[derive(Serialize, Insertable, Clone)]
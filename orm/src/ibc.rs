@@ -2,59 +2,251 @@ use bigdecimal::BigDecimal;
 use diesel::prelude::Queryable;
 use diesel::{AsChangeset, Insertable, Selectable};
 use serde::{Deserialize, Serialize};
+use shared::block::BlockHeight;
+use shared::ibc::{IbcChannel, IbcChannelState};
+use shared::id::Id;
 use shared::token::IbcRateLimit;
-use shared::transaction::{IbcAckStatus, IbcSequence};
+use shared::transaction::{
+    IbcPacketDirection, IbcPacketKey, IbcPacketTransfer,
+};
 
-use crate::schema::{ibc_ack, ibc_rate_limits, ibc_token_flows};
+use crate::schema::{
+    ibc_channels, ibc_packets, ibc_rate_limits, ibc_token_flows,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
-#[ExistingTypePath = "crate::schema::sql_types::IbcStatus"]
-pub enum IbcAckStatusDb {
-    Unknown,
-    Timeout,
-    Fail,
-    Success,
+#[ExistingTypePath = "crate::schema::sql_types::IbcChannelState"]
+pub enum IbcChannelStateDb {
+    Uninitialized,
+    Init,
+    TryOpen,
+    Open,
+    Closed,
 }
 
-impl From<IbcAckStatus> for IbcAckStatusDb {
-    fn from(value: IbcAckStatus) -> Self {
+impl From<IbcChannelState> for IbcChannelStateDb {
+    fn from(value: IbcChannelState) -> Self {
         match value {
-            IbcAckStatus::Success => Self::Success,
-            IbcAckStatus::Fail => Self::Fail,
-            IbcAckStatus::Timeout => Self::Timeout,
-            IbcAckStatus::Unknown => Self::Unknown,
+            IbcChannelState::Uninitialized => Self::Uninitialized,
+            IbcChannelState::Init => Self::Init,
+            IbcChannelState::TryOpen => Self::TryOpen,
+            IbcChannelState::Open => Self::Open,
+            IbcChannelState::Closed => Self::Closed,
         }
     }
 }
 
-#[derive(Serialize, Queryable, Insertable, Selectable, Clone, Debug)]
-#[diesel(table_name = ibc_ack)]
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = ibc_channels)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
-pub struct IbcAckDb {
+#[diesel(treat_none_as_null = true)]
+pub struct IbcChannelDb {
+    pub port_id: String,
+    pub channel_id: String,
+    pub state: IbcChannelStateDb,
+    pub counterparty_port_id: String,
+    pub counterparty_channel_id: Option<String>,
+    pub connection_id: String,
+    pub counterparty_connection_id: Option<String>,
+    pub client_id: String,
+    pub counterparty_client_id: String,
+    pub counterparty_chain_id: Option<String>,
+    pub updated_at: i32,
+}
+
+pub type IbcChannelInsertDb = IbcChannelDb;
+
+impl IbcChannelInsertDb {
+    pub fn from(channel: IbcChannel, block_height: BlockHeight) -> Self {
+        Self {
+            port_id: channel.port_id,
+            channel_id: channel.channel_id,
+            state: IbcChannelStateDb::from(channel.state),
+            counterparty_port_id: channel.counterparty_port_id,
+            counterparty_channel_id: channel.counterparty_channel_id,
+            connection_id: channel.connection_id,
+            counterparty_connection_id: channel.counterparty_connection_id,
+            client_id: channel.client_id,
+            counterparty_client_id: channel.counterparty_client_id,
+            counterparty_chain_id: channel.counterparty_chain_id,
+            updated_at: block_height as i32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::IbcPacketDirection"]
+pub enum IbcPacketDirectionDb {
+    Outgoing,
+    Incoming,
+}
+
+impl From<IbcPacketDirection> for IbcPacketDirectionDb {
+    fn from(value: IbcPacketDirection) -> Self {
+        match value {
+            IbcPacketDirection::Outgoing => Self::Outgoing,
+            IbcPacketDirection::Incoming => Self::Incoming,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::IbcPacketStatus"]
+pub enum IbcPacketStatusDb {
+    Pending,
+    Received,
+    Acknowledged,
+    Failed,
+    TimedOut,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = ibc_packets)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IbcPacketDb {
+    pub id: String,
+    pub direction: IbcPacketDirectionDb,
+    pub source_port: String,
+    pub source_channel: String,
+    pub dest_port: String,
+    pub dest_channel: String,
+    pub sequence: i64,
+    pub denom: Option<String>,
+    pub amount: Option<BigDecimal>,
+    pub sender: Option<String>,
+    pub receiver: Option<String>,
+    pub timeout_timestamp: i64,
+    pub status: IbcPacketStatusDb,
+    pub refunded: bool,
+    pub send_tx_id: Option<String>,
+    pub recv_tx_id: Option<String>,
+    pub ack_tx_id: Option<String>,
+    pub timeout_tx_id: Option<String>,
+}
+
+#[derive(Insertable, Clone, Debug)]
+#[diesel(table_name = ibc_packets)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IbcPacketInsertDb {
     pub id: String,
-    pub tx_hash: String,
-    pub timeout: i64,
-    pub status: IbcAckStatusDb,
+    pub direction: IbcPacketDirectionDb,
+    pub source_port: String,
+    pub source_channel: String,
+    pub dest_port: String,
+    pub dest_channel: String,
+    pub sequence: i64,
+    pub denom: Option<String>,
+    pub amount: Option<BigDecimal>,
+    pub sender: Option<String>,
+    pub receiver: Option<String>,
+    pub timeout_timestamp: i64,
+    pub status: IbcPacketStatusDb,
+    pub send_tx_id: Option<String>,
+    pub recv_tx_id: Option<String>,
 }
 
-pub type IbcAckInsertDb = IbcAckDb;
+impl IbcPacketInsertDb {
+    /// Outgoing packets start as pending while incoming ones are already
+    /// received
+    pub fn new(
+        direction: IbcPacketDirection,
+        key: IbcPacketKey,
+        transfer: Option<IbcPacketTransfer>,
+        timeout_timestamp: u64,
+        tx_id: Id,
+    ) -> Self {
+        let (status, send_tx_id, recv_tx_id) = match direction {
+            IbcPacketDirection::Outgoing => {
+                (IbcPacketStatusDb::Pending, Some(tx_id.to_string()), None)
+            }
+            IbcPacketDirection::Incoming => {
+                (IbcPacketStatusDb::Received, None, Some(tx_id.to_string()))
+            }
+        };
 
-impl From<IbcSequence> for IbcAckInsertDb {
-    fn from(value: IbcSequence) -> Self {
         Self {
-            id: value.id(),
-            tx_hash: value.tx_id.to_string(),
-            timeout: value.timeout as i64,
-            status: IbcAckStatusDb::Unknown,
+            id: key.id(),
+            direction: IbcPacketDirectionDb::from(direction),
+            source_port: key.source_port,
+            source_channel: key.source_channel,
+            dest_port: key.dest_port,
+            dest_channel: key.dest_channel,
+            sequence: key.sequence as i64,
+            denom: transfer.as_ref().map(|t| t.denom.clone()),
+            amount: transfer.as_ref().map(|t| t.amount.clone()),
+            sender: transfer.as_ref().map(|t| t.sender.clone()),
+            receiver: transfer.map(|t| t.receiver),
+            timeout_timestamp: timeout_timestamp as i64,
+            status,
+            send_tx_id,
+            recv_tx_id,
         }
     }
 }
 
-#[derive(Serialize, AsChangeset, Clone)]
-#[diesel(table_name = ibc_ack)]
+/// The outcome of a packet sent by namada. With parallel crawling it can be
+/// committed before the packet itself, in which case the row is created from
+/// the packet key and the send fields are filled in once the packet is
+/// indexed.
+#[derive(Insertable, Clone, Debug)]
+#[diesel(table_name = ibc_packets)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
-pub struct IbcSequencekStatusUpdateDb {
-    pub status: IbcAckStatusDb,
+pub struct IbcPacketStatusInsertDb {
+    pub id: String,
+    pub direction: IbcPacketDirectionDb,
+    pub source_port: String,
+    pub source_channel: String,
+    pub dest_port: String,
+    pub dest_channel: String,
+    pub sequence: i64,
+    pub timeout_timestamp: i64,
+    pub status: IbcPacketStatusDb,
+    pub refunded: bool,
+    pub ack_tx_id: Option<String>,
+    pub timeout_tx_id: Option<String>,
+}
+
+impl IbcPacketStatusInsertDb {
+    pub fn acknowledged(key: IbcPacketKey, success: bool, tx_id: Id) -> Self {
+        Self {
+            status: if success {
+                IbcPacketStatusDb::Acknowledged
+            } else {
+                IbcPacketStatusDb::Failed
+            },
+            refunded: !success,
+            ack_tx_id: Some(tx_id.to_string()),
+            ..Self::new(key)
+        }
+    }
+
+    pub fn timed_out(key: IbcPacketKey, tx_id: Id) -> Self {
+        Self {
+            status: IbcPacketStatusDb::TimedOut,
+            refunded: true,
+            timeout_tx_id: Some(tx_id.to_string()),
+            ..Self::new(key)
+        }
+    }
+
+    /// Only outgoing packets are acknowledged or timed out on namada. The
+    /// timeout is unknown until the packet itself is indexed.
+    fn new(key: IbcPacketKey) -> Self {
+        Self {
+            id: key.id(),
+            direction: IbcPacketDirectionDb::Outgoing,
+            source_port: key.source_port,
+            source_channel: key.source_channel,
+            dest_port: key.dest_port,
+            dest_channel: key.dest_channel,
+            sequence: key.sequence as i64,
+            timeout_timestamp: 0,
+            status: IbcPacketStatusDb::Pending,
+            refunded: false,
+            ack_tx_id: None,
+            timeout_tx_id: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Queryable, Selectable)]
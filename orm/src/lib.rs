@@ -1,3 +1,5 @@
+pub mod accounts;
+pub mod backfill;
 pub mod balances;
 pub mod blocks;
 pub mod bond;
@@ -13,6 +15,7 @@ pub mod migrations;
 pub mod parameters;
 pub mod pgf;
 pub mod pos_rewards;
+pub mod proposal_events;
 pub mod revealed_pk;
 pub mod schema;
 pub mod token;
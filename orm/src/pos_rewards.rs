@@ -1,10 +1,14 @@
 use std::str::FromStr;
 
-use bigdecimal::BigDecimal;
-use diesel::{Insertable, Queryable, Selectable};
-use shared::rewards::Reward;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDateTime;
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use shared::block::Epoch;
+use shared::rewards::{Reward, RewardClaim};
+use shared::utils::DelegationPair;
 
-use crate::schema::pos_rewards;
+use crate::schema::{pos_reward_ledger, pos_rewards, rewards_checkpoints};
 
 #[derive(Insertable, Clone, Queryable, Selectable)]
 #[diesel(table_name = pos_rewards)]
@@ -27,3 +31,179 @@ impl PosRewardInsertDb {
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::PosRewardLedgerKind"]
+pub enum PosRewardLedgerKindDb {
+    Accrued,
+    Claimed,
+}
+
+#[derive(Clone, Queryable, Selectable)]
+#[diesel(table_name = pos_reward_ledger)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PosRewardLedgerDb {
+    pub id: i32,
+    pub owner: String,
+    pub validator_id: i32,
+    pub epoch: i32,
+    pub kind: PosRewardLedgerKindDb,
+    pub raw_amount: BigDecimal,
+    pub bonded_amount: Option<BigDecimal>,
+    pub inner_tx_id: Option<String>,
+}
+
+#[derive(Clone, Insertable)]
+#[diesel(table_name = pos_reward_ledger)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PosRewardLedgerInsertDb {
+    pub owner: String,
+    pub validator_id: i32,
+    pub epoch: i32,
+    pub kind: PosRewardLedgerKindDb,
+    pub raw_amount: BigDecimal,
+    pub bonded_amount: Option<BigDecimal>,
+    pub inner_tx_id: Option<String>,
+}
+
+impl PosRewardLedgerInsertDb {
+    pub fn accrued(
+        reward: &PosRewardInsertDb,
+        previous_amount: Option<&BigDecimal>,
+        bonded_amount: Option<BigDecimal>,
+        epoch: Epoch,
+    ) -> Self {
+        // Unclaimed rewards only go down when claimed, which removes the
+        // previous amount, so a lower amount can't be a negative accrual
+        let raw_amount = match previous_amount {
+            Some(previous_amount) => {
+                (&reward.raw_amount - previous_amount).max(BigDecimal::zero())
+            }
+            None => reward.raw_amount.clone(),
+        };
+
+        Self {
+            owner: reward.owner.clone(),
+            validator_id: reward.validator_id,
+            epoch: epoch as i32,
+            kind: PosRewardLedgerKindDb::Accrued,
+            raw_amount,
+            bonded_amount,
+            inner_tx_id: None,
+        }
+    }
+
+    pub fn claimed(
+        claim: RewardClaim,
+        validator_id: i32,
+        epoch: Epoch,
+    ) -> Self {
+        Self {
+            owner: claim.delegation_pair.delegator_address.to_string(),
+            validator_id,
+            epoch: epoch as i32,
+            kind: PosRewardLedgerKindDb::Claimed,
+            raw_amount: BigDecimal::from_str(&claim.amount.to_string())
+                .expect("Invalid amount"),
+            bonded_amount: None,
+            inner_tx_id: Some(claim.inner_tx_id.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Queryable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = rewards_checkpoints)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RewardsCheckpointDb {
+    pub epoch: i32,
+    pub last_validator_address: String,
+    pub last_delegator_address: String,
+    pub processed_pairs: i32,
+    pub updated_at: NaiveDateTime,
+}
+
+pub type RewardsCheckpointInsertDb = RewardsCheckpointDb;
+
+impl RewardsCheckpointInsertDb {
+    pub fn from(
+        epoch: Epoch,
+        last_pair: &DelegationPair,
+        processed_pairs: usize,
+        updated_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            epoch: epoch as i32,
+            last_validator_address: last_pair.validator_address.to_string(),
+            last_delegator_address: last_pair.delegator_address.to_string(),
+            processed_pairs: processed_pairs as i32,
+            updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use shared::id::Id;
+
+    use super::*;
+
+    fn reward(raw_amount: i64) -> PosRewardInsertDb {
+        PosRewardInsertDb {
+            owner: "tnam1owner".to_string(),
+            validator_id: 1,
+            raw_amount: BigDecimal::from(raw_amount),
+        }
+    }
+
+    #[test]
+    fn test_accrued_since_previous_amount() {
+        let accrued = PosRewardLedgerInsertDb::accrued(
+            &reward(150),
+            Some(&BigDecimal::from(100)),
+            Some(BigDecimal::from(1000)),
+            4,
+        );
+
+        assert_eq!(accrued.raw_amount, BigDecimal::from(50));
+        assert_eq!(accrued.bonded_amount, Some(BigDecimal::from(1000)));
+        assert_eq!(accrued.epoch, 4);
+        assert!(matches!(accrued.kind, PosRewardLedgerKindDb::Accrued));
+
+        let first =
+            PosRewardLedgerInsertDb::accrued(&reward(150), None, None, 4);
+        assert_eq!(first.raw_amount, BigDecimal::from(150));
+    }
+
+    #[test]
+    fn test_accrued_is_never_negative() {
+        let accrued = PosRewardLedgerInsertDb::accrued(
+            &reward(80),
+            Some(&BigDecimal::from(100)),
+            None,
+            4,
+        );
+
+        assert_eq!(accrued.raw_amount, BigDecimal::zero());
+    }
+
+    #[test]
+    fn test_checkpoint_from_last_pair() {
+        let last_pair = DelegationPair {
+            validator_address: Id::Account("tnam1validator".to_string()),
+            delegator_address: Id::Account("tnam1delegator".to_string()),
+        };
+        let updated_at = DateTime::from_timestamp(1_700_000_000, 0)
+            .unwrap()
+            .naive_utc();
+
+        let checkpoint =
+            RewardsCheckpointInsertDb::from(12, &last_pair, 3000, updated_at);
+
+        assert_eq!(checkpoint.epoch, 12);
+        assert_eq!(checkpoint.last_validator_address, "tnam1validator");
+        assert_eq!(checkpoint.last_delegator_address, "tnam1delegator");
+        assert_eq!(checkpoint.processed_pairs, 3000);
+        assert_eq!(checkpoint.updated_at, updated_at);
+    }
+}
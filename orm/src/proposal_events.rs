@@ -0,0 +1,81 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use shared::proposal::{GovernanceProposalEvent, GovernanceProposalEventKind};
+
+use crate::governance_proposal::GovernanceProposalResultDb;
+use crate::schema::proposal_events;
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::ProposalEventKind"]
+pub enum ProposalEventKindDb {
+    Created,
+    VotingStarted,
+    Tally,
+    Ended,
+    Activated,
+}
+
+impl From<GovernanceProposalEventKind> for ProposalEventKindDb {
+    fn from(value: GovernanceProposalEventKind) -> Self {
+        match value {
+            GovernanceProposalEventKind::Created => Self::Created,
+            GovernanceProposalEventKind::VotingStarted => Self::VotingStarted,
+            GovernanceProposalEventKind::Tally => Self::Tally,
+            GovernanceProposalEventKind::Ended => Self::Ended,
+            GovernanceProposalEventKind::Activated => Self::Activated,
+        }
+    }
+}
+
+#[derive(Clone, Queryable, Selectable)]
+#[diesel(table_name = proposal_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ProposalEventDb {
+    pub id: i32,
+    pub proposal_id: i32,
+    pub kind: ProposalEventKindDb,
+    pub epoch: i32,
+    pub block_height: Option<i32>,
+    pub inner_tx_id: Option<String>,
+    pub result: Option<GovernanceProposalResultDb>,
+    pub yay_votes: Option<String>,
+    pub nay_votes: Option<String>,
+    pub abstain_votes: Option<String>,
+    pub has_proposal_code: Option<bool>,
+    pub proposal_code_succeeded: Option<bool>,
+}
+
+#[derive(Clone, Insertable)]
+#[diesel(table_name = proposal_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ProposalEventInsertDb {
+    pub proposal_id: i32,
+    pub kind: ProposalEventKindDb,
+    pub epoch: i32,
+    pub block_height: Option<i32>,
+    pub inner_tx_id: Option<String>,
+    pub result: Option<GovernanceProposalResultDb>,
+    pub yay_votes: Option<String>,
+    pub nay_votes: Option<String>,
+    pub abstain_votes: Option<String>,
+    pub has_proposal_code: Option<bool>,
+    pub proposal_code_succeeded: Option<bool>,
+}
+
+impl From<GovernanceProposalEvent> for ProposalEventInsertDb {
+    fn from(value: GovernanceProposalEvent) -> Self {
+        Self {
+            proposal_id: value.proposal_id as i32,
+            kind: value.kind.into(),
+            epoch: value.epoch as i32,
+            block_height: value.block_height.map(|height| height as i32),
+            inner_tx_id: value.inner_tx_id.map(|id| id.to_string()),
+            result: value.result.map(GovernanceProposalResultDb::from),
+            yay_votes: value.yay_votes,
+            nay_votes: value.nay_votes,
+            abstain_votes: value.abstain_votes,
+            has_proposal_code: value.has_proposal_code,
+            proposal_code_succeeded: value.proposal_code_succeeded,
+        }
+    }
+}
@@ -1,6 +1,22 @@
 // @generated automatically by Diesel CLI.
 
 pub mod sql_types {
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "backfill_chunk_status"))]
+    pub struct BackfillChunkStatus;
+
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "block_mismatch_kind"))]
+    pub struct BlockMismatchKind;
+
     #[derive(
         diesel::query_builder::QueryId,
         std::fmt::Debug,
@@ -46,8 +62,24 @@ pub mod sql_types {
         std::fmt::Debug,
         diesel::sql_types::SqlType,
     )]
-    #[diesel(postgres_type(name = "ibc_status"))]
-    pub struct IbcStatus;
+    #[diesel(postgres_type(name = "ibc_channel_state"))]
+    pub struct IbcChannelState;
+
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "ibc_packet_direction"))]
+    pub struct IbcPacketDirection;
+
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "ibc_packet_status"))]
+    pub struct IbcPacketStatus;
 
     #[derive(
         diesel::query_builder::QueryId,
@@ -89,6 +121,22 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "payment_recurrence"))]
     pub struct PaymentRecurrence;
 
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "pos_reward_ledger_kind"))]
+    pub struct PosRewardLedgerKind;
+
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "proposal_event_kind"))]
+    pub struct ProposalEventKind;
+
     #[derive(
         diesel::query_builder::QueryId,
         std::fmt::Debug,
@@ -130,6 +178,32 @@ pub mod sql_types {
     pub struct VoteKind;
 }
 
+diesel::table! {
+    accounts (address) {
+        #[max_length = 45]
+        address -> Varchar,
+        vp_code_hash -> Nullable<Varchar>,
+        public_keys -> Nullable<Array<Nullable<Varchar>>>,
+        threshold -> Nullable<Int4>,
+        created_at -> Nullable<Int4>,
+        updated_at -> Int4,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BackfillChunkStatus;
+
+    backfill_chunks (from_height) {
+        from_height -> Int4,
+        to_height -> Int4,
+        status -> BackfillChunkStatus,
+        last_processed_block -> Nullable<Int4>,
+        worker -> Nullable<Varchar>,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     balance_changes (id) {
         id -> Int4,
@@ -141,6 +215,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BlockMismatchKind;
+
+    block_mismatches (id) {
+        id -> Int4,
+        height -> Int4,
+        kind -> BlockMismatchKind,
+        #[max_length = 64]
+        indexed_value -> Varchar,
+        #[max_length = 64]
+        rpc_value -> Varchar,
+        detected_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     blocks (height) {
         height -> Int4,
@@ -229,6 +319,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    gas_price_per_epoch (id) {
+        id -> Int4,
+        token -> Varchar,
+        epoch -> Int4,
+        amount -> Numeric,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::GovernanceKind;
@@ -249,6 +348,12 @@ diesel::table! {
         yay_votes -> Varchar,
         nay_votes -> Varchar,
         abstain_votes -> Varchar,
+        total_voting_power -> Nullable<Numeric>,
+        #[max_length = 64]
+        wasm_code_hash -> Nullable<Varchar>,
+        wasm_code_size -> Nullable<Int4>,
+        wasm_code_name -> Nullable<Varchar>,
+        voting_power_attempts -> Int4,
     }
 }
 
@@ -261,18 +366,55 @@ diesel::table! {
         kind -> VoteKind,
         voter_address -> Varchar,
         proposal_id -> Int4,
+        voting_power -> Nullable<Numeric>,
+        is_validator -> Nullable<Bool>,
+        overrides_validator -> Nullable<Bool>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::IbcChannelState;
+
+    ibc_channels (port_id, channel_id) {
+        port_id -> Varchar,
+        channel_id -> Varchar,
+        state -> IbcChannelState,
+        counterparty_port_id -> Varchar,
+        counterparty_channel_id -> Nullable<Varchar>,
+        connection_id -> Varchar,
+        counterparty_connection_id -> Nullable<Varchar>,
+        client_id -> Varchar,
+        counterparty_client_id -> Varchar,
+        counterparty_chain_id -> Nullable<Varchar>,
+        updated_at -> Int4,
     }
 }
 
 diesel::table! {
     use diesel::sql_types::*;
-    use super::sql_types::IbcStatus;
+    use super::sql_types::IbcPacketDirection;
+    use super::sql_types::IbcPacketStatus;
 
-    ibc_ack (id) {
+    ibc_packets (id) {
         id -> Varchar,
-        tx_hash -> Varchar,
-        timeout -> Int8,
-        status -> IbcStatus,
+        direction -> IbcPacketDirection,
+        source_port -> Varchar,
+        source_channel -> Varchar,
+        dest_port -> Varchar,
+        dest_channel -> Varchar,
+        sequence -> Int8,
+        denom -> Nullable<Varchar>,
+        amount -> Nullable<Numeric>,
+        sender -> Nullable<Varchar>,
+        receiver -> Nullable<Varchar>,
+        timeout_timestamp -> Int8,
+        status -> IbcPacketStatus,
+        refunded -> Bool,
+        send_tx_id -> Nullable<Varchar>,
+        recv_tx_id -> Nullable<Varchar>,
+        ack_tx_id -> Nullable<Varchar>,
+        timeout_tx_id -> Nullable<Varchar>,
     }
 }
 
@@ -353,6 +495,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::PosRewardLedgerKind;
+
+    pos_reward_ledger (id) {
+        id -> Int4,
+        owner -> Varchar,
+        validator_id -> Int4,
+        epoch -> Int4,
+        kind -> PosRewardLedgerKind,
+        raw_amount -> Numeric,
+        bonded_amount -> Nullable<Numeric>,
+        #[max_length = 64]
+        inner_tx_id -> Nullable<Varchar>,
+    }
+}
+
 diesel::table! {
     pos_rewards (id) {
         id -> Int4,
@@ -362,6 +521,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ProposalEventKind;
+    use super::sql_types::GovernanceResult;
+
+    proposal_events (id) {
+        id -> Int4,
+        proposal_id -> Int4,
+        kind -> ProposalEventKind,
+        epoch -> Int4,
+        block_height -> Nullable<Int4>,
+        #[max_length = 64]
+        inner_tx_id -> Nullable<Varchar>,
+        result -> Nullable<GovernanceResult>,
+        yay_votes -> Nullable<Varchar>,
+        nay_votes -> Nullable<Varchar>,
+        abstain_votes -> Nullable<Varchar>,
+        has_proposal_code -> Nullable<Bool>,
+        proposal_code_succeeded -> Nullable<Bool>,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::PaymentRecurrence;
@@ -385,6 +566,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    rewards_checkpoints (epoch) {
+        epoch -> Int4,
+        last_validator_address -> Varchar,
+        last_delegator_address -> Varchar,
+        processed_pairs -> Int4,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::TokenType;
@@ -393,6 +584,7 @@ diesel::table! {
         #[max_length = 45]
         address -> Varchar,
         token_type -> TokenType,
+        denomination -> Int2,
     }
 }
 
@@ -430,6 +622,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ValidatorState;
+
+    validator_epoch_snapshots (id) {
+        id -> Int4,
+        validator_id -> Int4,
+        epoch -> Int4,
+        voting_power -> Int4,
+        max_commission -> Varchar,
+        commission -> Varchar,
+        state -> ValidatorState,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::ValidatorState;
@@ -478,37 +685,49 @@ diesel::joinable!(ibc_token -> token (address));
 diesel::joinable!(ibc_token_flows -> token (address));
 diesel::joinable!(inner_transactions -> wrapper_transactions (wrapper_id));
 diesel::joinable!(masp_pool -> inner_transactions (inner_tx_id));
+diesel::joinable!(pos_reward_ledger -> validators (validator_id));
 diesel::joinable!(pos_rewards -> validators (validator_id));
+diesel::joinable!(proposal_events -> governance_proposals (proposal_id));
 diesel::joinable!(public_good_funding -> governance_proposals (proposal_id));
 diesel::joinable!(token_supplies_per_epoch -> token (address));
 diesel::joinable!(transaction_history -> inner_transactions (inner_tx_id));
 diesel::joinable!(unbonds -> validators (validator_id));
+diesel::joinable!(validator_epoch_snapshots -> validators (validator_id));
 diesel::joinable!(wrapper_transactions -> blocks (block_height));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    accounts,
+    backfill_chunks,
     balance_changes,
+    block_mismatches,
     blocks,
     bonds,
     chain_parameters,
     crawler_state,
     gas_estimations,
     gas_price,
+    gas_price_per_epoch,
     governance_proposals,
     governance_votes,
-    ibc_ack,
+    ibc_channels,
+    ibc_packets,
     ibc_rate_limits,
     ibc_token,
     ibc_token_flows,
     inner_transactions,
     masp_pool,
     masp_pool_aggregate,
+    pos_reward_ledger,
     pos_rewards,
+    proposal_events,
     public_good_funding,
     revealed_pk,
+    rewards_checkpoints,
     token,
     token_supplies_per_epoch,
     transaction_history,
     unbonds,
+    validator_epoch_snapshots,
     validators,
     wrapper_transactions,
 );
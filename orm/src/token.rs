@@ -17,20 +17,25 @@ pub enum TokenTypeDb {
 pub struct TokenDb {
     pub address: String,
     pub token_type: TokenTypeDb,
+    pub denomination: i16,
 }
 
 pub type TokenInsertDb = TokenDb;
 
 impl From<&Token> for TokenDb {
     fn from(token: &Token) -> Self {
+        let denomination = token.denomination() as i16;
+
         match token {
             Token::Native(token) => TokenDb {
                 address: token.to_string(),
                 token_type: TokenTypeDb::Native,
+                denomination,
             },
             Token::Ibc(token) => TokenDb {
                 address: token.address.to_string(),
                 token_type: TokenTypeDb::Ibc,
+                denomination,
             },
         }
     }
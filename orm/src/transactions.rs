@@ -35,6 +35,12 @@ pub enum TransactionKindDb {
     ReactivateValidator,
     DeactivateValidator,
     UnjailValidator,
+    InitAccount,
+    UpdateAccount,
+    ChangeConsensusKey,
+    ResignSteward,
+    UpdateStewardCommission,
+    BridgePoolTransfer,
     Unknown,
 }
 
@@ -78,6 +84,14 @@ impl From<TransactionKind> for TransactionKindDb {
             TransactionKind::RevealPk(_) => Self::RevealPk,
             TransactionKind::BecomeValidator(_) => Self::BecomeValidator,
             TransactionKind::UnjailValidator(_) => Self::UnjailValidator,
+            TransactionKind::InitAccount(_) => Self::InitAccount,
+            TransactionKind::UpdateAccount(_) => Self::UpdateAccount,
+            TransactionKind::ChangeConsensusKey(_) => Self::ChangeConsensusKey,
+            TransactionKind::ResignSteward(_) => Self::ResignSteward,
+            TransactionKind::UpdateStewardCommission(_) => {
+                Self::UpdateStewardCommission
+            }
+            TransactionKind::BridgePoolTransfer(_) => Self::BridgePoolTransfer,
             TransactionKind::Unknown(_) => TransactionKindDb::Unknown,
         }
     }
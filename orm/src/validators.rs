@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use shared::validator::{Validator, ValidatorState};
 
 use crate::helpers::OrderByDb;
-use crate::schema::validators;
+use crate::schema::{validator_epoch_snapshots, validators};
 use crate::{asc_desc, rev_asc_desc};
 
 #[derive(Debug)]
@@ -117,6 +117,48 @@ pub struct ValidatorUpdateMetadataDb {
     pub avatar: Option<String>,
 }
 
+#[derive(Serialize, Insertable, Clone)]
+#[diesel(table_name = validator_epoch_snapshots)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ValidatorEpochSnapshotInsertDb {
+    pub validator_id: i32,
+    pub epoch: i32,
+    pub voting_power: i32,
+    pub max_commission: String,
+    pub commission: String,
+    pub state: ValidatorStateDb,
+}
+
+#[derive(Serialize, Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = validator_epoch_snapshots)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ValidatorEpochSnapshotDb {
+    pub id: i32,
+    pub validator_id: i32,
+    pub epoch: i32,
+    pub voting_power: i32,
+    pub max_commission: String,
+    pub commission: String,
+    pub state: ValidatorStateDb,
+}
+
+impl ValidatorEpochSnapshotInsertDb {
+    pub fn from_validator(
+        validator_id: i32,
+        epoch: i32,
+        validator: &ValidatorInsertDb,
+    ) -> Self {
+        Self {
+            validator_id,
+            epoch,
+            voting_power: validator.voting_power,
+            max_commission: validator.max_commission.clone(),
+            commission: validator.commission.clone(),
+            state: validator.state.clone(),
+        }
+    }
+}
+
 impl ValidatorInsertDb {
     pub fn from_validator(validator: Validator) -> Self {
         Self {
@@ -171,3 +213,29 @@ pub fn validator_sort_by(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_from_validator() {
+        let validator = ValidatorInsertDb {
+            namada_address: "tnam1validator".to_string(),
+            voting_power: 42,
+            max_commission: "0.1".to_string(),
+            commission: "0.05".to_string(),
+            state: ValidatorStateDb::BelowCapacity,
+        };
+
+        let snapshot =
+            ValidatorEpochSnapshotInsertDb::from_validator(7, 12, &validator);
+
+        assert_eq!(snapshot.validator_id, 7);
+        assert_eq!(snapshot.epoch, 12);
+        assert_eq!(snapshot.voting_power, 42);
+        assert_eq!(snapshot.max_commission, "0.1");
+        assert_eq!(snapshot.commission, "0.05");
+        assert!(matches!(snapshot.state, ValidatorStateDb::BelowCapacity));
+    }
+}
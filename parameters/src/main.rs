@@ -7,7 +7,7 @@ use clap::Parser;
 use deadpool_diesel::postgres::Object;
 use namada_sdk::state::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada_sdk::time::{DateTimeUtc, Utc};
-use orm::gas::GasPriceDb;
+use orm::gas::{GasPriceDb, GasPriceEpochInsertDb};
 use orm::migrations::run_migrations;
 use orm::parameters::ParametersInsertDb;
 use parameters::app_state::AppState;
@@ -16,11 +16,11 @@ use parameters::repository;
 use parameters::services::{
     namada as namada_service, tendermint as tendermint_service,
 };
+use shared::client::RpcClient;
 use shared::crawler;
 use shared::crawler_state::{CrawlerName, IntervalCrawlerState};
 use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
-use tendermint_rpc::HttpClient;
-use tendermint_rpc::client::CompatMode;
+use shared::metrics::{self, Stage};
 use tokio::sync::{Mutex, MutexGuard};
 use tokio::time::Instant;
 
@@ -29,13 +29,9 @@ async fn main() -> Result<(), MainError> {
     let config = AppConfig::parse();
 
     config.log.init();
+    config.metrics.init(CrawlerName::Parameters);
 
-    let client = Arc::new(
-        HttpClient::builder(config.tendermint_url.as_str().parse().unwrap())
-            .compat_mode(CompatMode::V0_37)
-            .build()
-            .unwrap(),
-    );
+    let client = Arc::new(RpcClient::new(&config.tendermint_url).unwrap());
 
     let app_state = AppState::new(config.database_url).into_db_error()?;
     let conn = Arc::new(app_state.get_db_connection().await.into_db_error()?);
@@ -71,7 +67,7 @@ async fn main() -> Result<(), MainError> {
 
 async fn crawling_fn(
     conn: Arc<Object>,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
     instant: Arc<Mutex<Instant>>,
     sleep_for: u64,
 ) -> Result<(), MainError> {
@@ -92,6 +88,8 @@ async fn crawling_fn(
 
     tracing::debug!("Querying parameters...");
 
+    let start = Instant::now();
+
     let parameters = namada_service::get_parameters(&client)
         .await
         .into_rpc_error()?;
@@ -104,10 +102,18 @@ async fn crawling_fn(
 
     let gas_price = namada_service::get_gas_price(&client).await;
 
+    let epoch = namada_service::get_current_epoch(&client)
+        .await
+        .into_rpc_error()?;
+
     let timestamp = DateTimeUtc::now().0.timestamp();
     let crawler_state = IntervalCrawlerState { timestamp };
 
     tracing::info!("Queried parameters successfully",);
+    metrics::record_stage_duration(Stage::Rpc, start.elapsed());
+
+    let gas_price_count = gas_price.len();
+    let db_start = Instant::now();
 
     conn.interact(move |conn| {
         conn.build_transaction()
@@ -132,6 +138,19 @@ async fn crawling_fn(
                         .collect::<Vec<GasPriceDb>>(),
                 )?;
 
+                // Keep the last gas price seen in each epoch for the fee
+                // market history
+                repository::parameters::upsert_gas_price_per_epoch(
+                    transaction_conn,
+                    gas_price
+                        .iter()
+                        .cloned()
+                        .map(|gas_price| {
+                            GasPriceEpochInsertDb::from(gas_price, epoch)
+                        })
+                        .collect::<Vec<GasPriceEpochInsertDb>>(),
+                )?;
+
                 repository::crawler_state::upsert_crawler_state(
                     transaction_conn,
                     (CrawlerName::Parameters, crawler_state).into(),
@@ -146,6 +165,10 @@ async fn crawling_fn(
     .into_db_error()?;
 
     tracing::info!(sleep_for = sleep_for, "Inserted parameters into database");
+    metrics::record_stage_duration(Stage::Db, db_start.elapsed());
+    metrics::record_inserted_rows("chain_parameters", 1);
+    metrics::record_inserted_rows("gas_price", gas_price_count);
+    metrics::record_inserted_rows("gas_price_per_epoch", gas_price_count);
 
     // Once we are done processing, we reset the instant
     *instant = Instant::now();
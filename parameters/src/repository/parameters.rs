@@ -1,9 +1,9 @@
 use anyhow::Context;
 use diesel::upsert::excluded;
 use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
-use orm::gas::GasPriceDb;
+use orm::gas::{GasPriceDb, GasPriceEpochInsertDb};
 use orm::parameters::ParametersInsertDb;
-use orm::schema::{chain_parameters, gas_price};
+use orm::schema::{chain_parameters, gas_price, gas_price_per_epoch};
 
 pub fn upsert_chain_parameters(
     transaction_conn: &mut PgConnection,
@@ -47,3 +47,21 @@ pub fn upsert_gas_price(
 
     Ok(())
 }
+
+pub fn upsert_gas_price_per_epoch(
+    transaction_conn: &mut PgConnection,
+    gas_price: Vec<GasPriceEpochInsertDb>,
+) -> anyhow::Result<()> {
+    diesel::insert_into(gas_price_per_epoch::table)
+        .values(gas_price)
+        .on_conflict((gas_price_per_epoch::token, gas_price_per_epoch::epoch))
+        .do_update()
+        .set(
+            gas_price_per_epoch::amount
+                .eq(excluded(gas_price_per_epoch::amount)),
+        )
+        .execute(transaction_conn)
+        .context("Failed to update gas price per epoch in db")?;
+
+    Ok(())
+}
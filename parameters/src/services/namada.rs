@@ -17,12 +17,12 @@ use namada_sdk::token::Amount as NamadaSdkAmount;
 use shared::balance::Amount;
 use shared::block::Epoch;
 use shared::checksums::Checksums;
+use shared::client::RpcClient;
 use shared::gas::GasPrice;
 use shared::parameters::Parameters;
-use tendermint_rpc::HttpClient;
 
 async fn query_tx_code_hash(
-    client: &HttpClient,
+    client: &RpcClient,
     tx_code_path: &str,
 ) -> Option<String> {
     let hash_key = Key::wasm_hash(tx_code_path);
@@ -39,7 +39,7 @@ async fn query_tx_code_hash(
     }
 }
 
-pub async fn query_checksums(client: &HttpClient) -> Checksums {
+pub async fn query_checksums(client: &RpcClient) -> Checksums {
     let mut checksums = Checksums::default();
     for code_path in Checksums::code_paths() {
         let code =
@@ -55,7 +55,7 @@ pub async fn query_checksums(client: &HttpClient) -> Checksums {
     checksums
 }
 
-pub async fn get_parameters(client: &HttpClient) -> anyhow::Result<Parameters> {
+pub async fn get_parameters(client: &RpcClient) -> anyhow::Result<Parameters> {
     let pos_parameters = rpc::get_pos_params(client)
         .await
         .with_context(|| "Failed to query pos parameters".to_string())?;
@@ -122,10 +122,10 @@ pub async fn get_parameters(client: &HttpClient) -> anyhow::Result<Parameters> {
     })
 }
 
-pub async fn get_gas_price(client: &HttpClient) -> Vec<GasPrice> {
+pub async fn get_gas_price(client: &RpcClient) -> Vec<GasPrice> {
     let min_gas_price_key = namada_parameters::storage::get_gas_cost_key();
     let gas_cost_table = query_storage_value::<
-        HttpClient,
+        RpcClient,
         BTreeMap<NamadaAddress, NamadaSdkAmount>,
     >(client, &min_gas_price_key)
     .await
@@ -143,7 +143,7 @@ pub async fn get_gas_price(client: &HttpClient) -> Vec<GasPrice> {
     gas_table
 }
 
-pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
+pub async fn get_current_epoch(client: &RpcClient) -> anyhow::Result<Epoch> {
     let epoch = rpc::query_epoch(client)
         .await
         .context("Failed to query Namada's current epoch")?;
@@ -152,7 +152,7 @@ pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
 }
 
 async fn _calc_apr(
-    client: &HttpClient,
+    client: &RpcClient,
     epoch: NamadaEpoch,
     native_token_address: &NamadaAddress,
     epochs_per_year: u64,
@@ -1,7 +1,8 @@
-use namada_sdk::tendermint_rpc::{Client, HttpClient};
+use namada_sdk::tendermint_rpc::Client;
+use shared::client::RpcClient;
 use shared::genesis::{Genesis, GenesisParams, GenesisRequest};
 
-pub async fn query_genesis(client: &HttpClient) -> anyhow::Result<Genesis> {
+pub async fn query_genesis(client: &RpcClient) -> anyhow::Result<Genesis> {
     let genesis_params: GenesisParams =
         client.perform(GenesisRequest).await?.genesis;
 
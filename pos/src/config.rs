@@ -2,6 +2,7 @@ use core::fmt;
 use std::fmt::Display;
 
 use shared::log_config::LogConfig;
+use shared::metrics::MetricsConfig;
 
 #[derive(clap::ValueEnum, Clone, Debug, Copy)]
 pub enum CargoEnv {
@@ -17,12 +18,22 @@ impl Display for CargoEnv {
 
 #[derive(clap::Parser)]
 pub struct AppConfig {
-    #[clap(long, env)]
-    pub tendermint_url: String,
+    #[clap(
+        long,
+        env,
+        value_delimiter = ',',
+        required = true,
+        help = "Comma separated list of RPC urls, requests fail over between \
+                them"
+    )]
+    pub tendermint_url: Vec<String>,
 
     #[clap(long, env)]
     pub database_url: String,
 
     #[clap(flatten)]
     pub log: LogConfig,
+
+    #[clap(flatten)]
+    pub metrics: MetricsConfig,
 }
@@ -1,5 +1,6 @@
 use std::convert::identity;
 use std::sync::Arc;
+use std::time::Instant;
 
 use chrono::{NaiveDateTime, Utc};
 use clap::Parser;
@@ -12,24 +13,20 @@ use pos::app_state::AppState;
 use pos::config::AppConfig;
 use pos::repository::{self};
 use pos::services::namada as namada_service;
+use shared::client::RpcClient;
 use shared::crawler;
 use shared::crawler_state::{CrawlerName, EpochCrawlerState};
 use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
-use tendermint_rpc::HttpClient;
-use tendermint_rpc::client::CompatMode;
+use shared::metrics::{self, Stage};
 
 #[tokio::main]
 async fn main() -> Result<(), MainError> {
     let config = AppConfig::parse();
 
     config.log.init();
+    config.metrics.init(CrawlerName::Pos);
 
-    let client = Arc::new(
-        HttpClient::builder(config.tendermint_url.as_str().parse().unwrap())
-            .compat_mode(CompatMode::V0_37)
-            .build()
-            .unwrap(),
-    );
+    let client = Arc::new(RpcClient::new(&config.tendermint_url).unwrap());
 
     let app_state = AppState::new(config.database_url).into_db_error()?;
     let conn = Arc::new(app_state.get_db_connection().await.into_db_error()?);
@@ -56,7 +53,7 @@ async fn main() -> Result<(), MainError> {
 async fn crawling_fn(
     epoch_to_process: u32,
     conn: Arc<Object>,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
 ) -> Result<(), MainError> {
     let should_process = can_process(epoch_to_process, client.clone()).await?;
 
@@ -72,6 +69,8 @@ async fn crawling_fn(
         return Err(MainError::NoAction);
     }
 
+    let start = Instant::now();
+
     let validators_set =
         namada_service::get_validator_set_at_epoch(&client, epoch_to_process)
             .await
@@ -82,6 +81,10 @@ async fn crawling_fn(
         validators = validators_set.validators.len(),
         "Queried validators successfully...",
     );
+    metrics::record_stage_duration(Stage::Rpc, start.elapsed());
+
+    let validators_count = validators_set.validators.len();
+    let db_start = Instant::now();
 
     let timestamp = DateTimeUtc::now().0.timestamp();
     let crawler_state = EpochCrawlerState {
@@ -106,6 +109,12 @@ async fn crawling_fn(
                     validators_dbo,
                 )?;
 
+                repository::pos::upsert_validator_snapshots(
+                    transaction_conn,
+                    epoch_to_process,
+                    validators_dbo,
+                )?;
+
                 repository::crawler_state::upsert_crawler_state(
                     transaction_conn,
                     crawler_state,
@@ -120,13 +129,20 @@ async fn crawling_fn(
     .into_db_error()?;
 
     tracing::info!(epoch = epoch_to_process, "Updated validators in database");
+    metrics::record_stage_duration(Stage::Db, db_start.elapsed());
+    metrics::record_inserted_rows("validators", validators_count);
+    metrics::record_inserted_rows(
+        "validator_epoch_snapshots",
+        validators_count,
+    );
+    metrics::record_last_processed(epoch_to_process as u64);
 
     Ok(())
 }
 
 async fn can_process(
     epoch: u32,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
 ) -> Result<bool, MainError> {
     let current_epoch = namada_service::get_current_epoch(&client.clone())
         .await
@@ -137,6 +153,7 @@ async fn can_process(
             );
             MainError::RpcError
         })?;
+    metrics::record_chain_tip(current_epoch as u64);
 
     Ok(current_epoch >= epoch)
 }
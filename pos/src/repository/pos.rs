@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use anyhow::Context;
 use diesel::upsert::excluded;
-use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
-use orm::schema::validators;
-use orm::validators::ValidatorInsertDb;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use orm::schema::{validator_epoch_snapshots, validators};
+use orm::validators::{ValidatorEpochSnapshotInsertDb, ValidatorInsertDb};
+use shared::block::Epoch;
 
 pub fn upsert_validators(
     transaction_conn: &mut PgConnection,
@@ -26,3 +29,60 @@ pub fn upsert_validators(
 
     Ok(())
 }
+
+/// Records the validators stake, commission and state at the given epoch.
+/// The validators must already be stored in the validators table.
+pub fn upsert_validator_snapshots(
+    transaction_conn: &mut PgConnection,
+    epoch: Epoch,
+    validators_db: &[ValidatorInsertDb],
+) -> anyhow::Result<()> {
+    let addresses = validators_db
+        .iter()
+        .map(|validator| validator.namada_address.clone())
+        .collect::<Vec<_>>();
+
+    let validator_ids: HashMap<String, i32> = validators::table
+        .filter(validators::columns::namada_address.eq_any(addresses))
+        .select((validators::columns::namada_address, validators::columns::id))
+        .load::<(String, i32)>(transaction_conn)
+        .context("Failed to query validator ids from db")?
+        .into_iter()
+        .collect();
+
+    let snapshots = validators_db
+        .iter()
+        .filter_map(|validator| {
+            validator_ids.get(&validator.namada_address).map(|id| {
+                ValidatorEpochSnapshotInsertDb::from_validator(
+                    *id,
+                    epoch as i32,
+                    validator,
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(validator_epoch_snapshots::table)
+        .values::<&Vec<ValidatorEpochSnapshotInsertDb>>(&snapshots)
+        .on_conflict((
+            validator_epoch_snapshots::columns::validator_id,
+            validator_epoch_snapshots::columns::epoch,
+        ))
+        .do_update()
+        .set((
+            validator_epoch_snapshots::columns::voting_power
+                .eq(excluded(validator_epoch_snapshots::columns::voting_power)),
+            validator_epoch_snapshots::columns::max_commission.eq(excluded(
+                validator_epoch_snapshots::columns::max_commission,
+            )),
+            validator_epoch_snapshots::columns::commission
+                .eq(excluded(validator_epoch_snapshots::columns::commission)),
+            validator_epoch_snapshots::columns::state
+                .eq(excluded(validator_epoch_snapshots::columns::state)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update validator snapshots in db")?;
+
+    Ok(())
+}
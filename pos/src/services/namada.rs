@@ -6,12 +6,12 @@ use namada_core::chain::Epoch as NamadaSdkEpoch;
 use namada_sdk::address::Address;
 use namada_sdk::rpc;
 use shared::block::Epoch;
+use shared::client::RpcClient;
 use shared::id::Id;
 use shared::validator::{Validator, ValidatorSet, ValidatorState};
-use tendermint_rpc::HttpClient;
 
 pub async fn get_validator_set_at_epoch(
-    client: &HttpClient,
+    client: &RpcClient,
     epoch: Epoch,
 ) -> anyhow::Result<ValidatorSet> {
     let namada_epoch = to_epoch(epoch);
@@ -93,7 +93,7 @@ pub async fn get_validator_set_at_epoch(
 }
 
 pub async fn get_validators_state(
-    client: &HttpClient,
+    client: &RpcClient,
     validators: Vec<Validator>,
     epoch: Epoch,
 ) -> anyhow::Result<ValidatorSet> {
@@ -150,7 +150,7 @@ pub async fn get_validators_state(
     Ok(ValidatorSet { validators, epoch })
 }
 
-pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
+pub async fn get_current_epoch(client: &RpcClient) -> anyhow::Result<Epoch> {
     let epoch = rpc::query_epoch(client)
         .await
         .context("Failed to query Namada's current epoch")?;
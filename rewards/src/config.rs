@@ -2,6 +2,7 @@ use core::fmt;
 use std::fmt::Display;
 
 use shared::log_config::LogConfig;
+use shared::metrics::MetricsConfig;
 
 #[derive(clap::ValueEnum, Clone, Debug, Copy)]
 pub enum CargoEnv {
@@ -17,8 +18,15 @@ impl Display for CargoEnv {
 
 #[derive(clap::Parser)]
 pub struct AppConfig {
-    #[clap(long, env)]
-    pub tendermint_url: String,
+    #[clap(
+        long,
+        env,
+        value_delimiter = ',',
+        required = true,
+        help = "Comma separated list of RPC urls, requests fail over between \
+                them"
+    )]
+    pub tendermint_url: Vec<String>,
 
     #[clap(long, env, default_value_t = 60)]
     pub sleep_for: u64,
@@ -26,6 +34,27 @@ pub struct AppConfig {
     #[clap(long, env)]
     pub database_url: String,
 
+    #[clap(
+        long,
+        env,
+        default_value_t = 1000,
+        help = "Number of delegation pairs whose rewards are stored at once, \
+                a restart resumes after the last stored pairs"
+    )]
+    pub checkpoint_size: usize,
+
+    #[clap(
+        long,
+        env,
+        default_value_t = 3,
+        help = "Maximum number of batches of 32 delegation pairs whose \
+                rewards are queried concurrently"
+    )]
+    pub max_concurrent_batches: usize,
+
     #[clap(flatten)]
     pub log: LogConfig,
+
+    #[clap(flatten)]
+    pub metrics: MetricsConfig,
 }
@@ -1,21 +1,24 @@
+use std::collections::HashSet;
 use std::convert::identity;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::NaiveDateTime;
 use clap::Parser;
 use deadpool_diesel::postgres::Object;
 use namada_sdk::time::{DateTimeUtc, Utc};
 use orm::migrations::run_migrations;
+use orm::pos_rewards::{RewardsCheckpointDb, RewardsCheckpointInsertDb};
 use rewards::config::AppConfig;
 use rewards::repository;
-use rewards::services::namada as namada_service;
+use rewards::services::{namada as namada_service, utils};
 use rewards::state::AppState;
+use shared::client::RpcClient;
 use shared::crawler;
 use shared::crawler_state::{CrawlerName, IntervalCrawlerState};
 use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
-use tendermint_rpc::HttpClient;
-use tendermint_rpc::client::CompatMode;
+use shared::metrics::{self, Stage};
+use shared::utils::DelegationPair;
 use tokio::time::sleep;
 
 #[tokio::main]
@@ -23,15 +26,11 @@ async fn main() -> Result<(), MainError> {
     let config = AppConfig::parse();
 
     config.log.init();
+    config.metrics.init(CrawlerName::Rewards);
 
     tracing::info!("version: {}", env!("VERGEN_GIT_SHA").to_string());
 
-    let client = Arc::new(
-        HttpClient::builder(config.tendermint_url.as_str().parse().unwrap())
-            .compat_mode(CompatMode::V0_37)
-            .build()
-            .unwrap(),
-    );
+    let client = Arc::new(RpcClient::new(&config.tendermint_url).unwrap());
 
     let app_state = AppState::new(config.database_url).into_db_error()?;
 
@@ -59,8 +58,19 @@ async fn main() -> Result<(), MainError> {
         }
     }
 
+    let checkpoint_size = config.checkpoint_size;
+    let max_concurrent_batches = config.max_concurrent_batches;
+
     crawler::crawl(
-        move |epoch| crawling_fn(conn.clone(), client.clone(), epoch),
+        move |epoch| {
+            crawling_fn(
+                conn.clone(),
+                client.clone(),
+                epoch,
+                checkpoint_size,
+                max_concurrent_batches,
+            )
+        },
         epoch,
         None,
     )
@@ -69,8 +79,10 @@ async fn main() -> Result<(), MainError> {
 
 async fn crawling_fn(
     conn: Arc<Object>,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
     epoch_to_process: u32,
+    checkpoint_size: usize,
+    max_concurrent_batches: usize,
 ) -> Result<(), MainError> {
     let should_process = can_process(epoch_to_process, client.clone()).await?;
 
@@ -86,43 +98,110 @@ async fn crawling_fn(
         return Err(MainError::NoAction);
     }
 
-    // TODO: change this by querying all the pairs in the database
-    let delegations_pairs = namada_service::query_delegation_pairs(&client)
-        .await
-        .into_rpc_error()?;
+    let checkpoint = get_checkpoint(&conn, epoch_to_process).await?;
+
+    let delegations_pairs = get_delegation_pairs(&conn, &client).await?;
+    let total_pairs = delegations_pairs.len();
+
+    let mut processed_pairs = 0;
+    let mut last_pair = None;
+    if let Some(checkpoint) = checkpoint {
+        last_pair = Some((
+            checkpoint.last_validator_address,
+            checkpoint.last_delegator_address,
+        ));
+        processed_pairs = checkpoint.processed_pairs as usize;
+
+        tracing::info!(
+            epoch = epoch_to_process,
+            processed_pairs,
+            "Resuming rewards from checkpoint..."
+        );
+    }
+
+    let delegations_pairs =
+        utils::pairs_after_checkpoint(delegations_pairs, last_pair);
 
     tracing::info!(
         epoch = epoch_to_process,
-        delegations = delegations_pairs.len(),
+        delegations = total_pairs,
+        remaining = delegations_pairs.len(),
         "Querying rewards..."
     );
 
-    let rewards = namada_service::query_rewards(&client, &delegations_pairs)
+    for batch in delegations_pairs.chunks(checkpoint_size.max(1)) {
+        let start = Instant::now();
+
+        let rewards = namada_service::query_rewards(
+            &client,
+            batch,
+            max_concurrent_batches.max(1),
+        )
         .await
         .into_rpc_error()?;
-    let non_zero_rewards = rewards
-        .iter()
-        .filter(|reward| !reward.amount.is_zero())
-        .cloned()
-        .collect::<Vec<_>>();
+        let non_zero_rewards = rewards
+            .iter()
+            .filter(|reward| !reward.amount.is_zero())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        metrics::record_stage_duration(Stage::Rpc, start.elapsed());
+
+        processed_pairs += batch.len();
+        let rewards_count = non_zero_rewards.len();
+        let checkpoint = RewardsCheckpointInsertDb::from(
+            epoch_to_process,
+            batch.last().expect("Batches can't be empty"),
+            processed_pairs,
+            Utc::now().naive_utc(),
+        );
+        let db_start = Instant::now();
+
+        conn.interact(move |conn| {
+            conn.build_transaction().read_write().run(
+                |transaction_conn: &mut diesel::prelude::PgConnection| {
+                    repository::pos_rewards::upsert_rewards(
+                        transaction_conn,
+                        non_zero_rewards,
+                        epoch_to_process,
+                    )?;
+
+                    repository::checkpoint::upsert_checkpoint(
+                        transaction_conn,
+                        checkpoint,
+                    )?;
+
+                    anyhow::Ok(())
+                },
+            )
+        })
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()?;
+
+        tracing::info!(
+            epoch = epoch_to_process,
+            processed_pairs,
+            total_pairs,
+            rewards = rewards.len(),
+            non_zero_rewards = rewards_count,
+            "Inserted rewards batch into database",
+        );
+        metrics::record_stage_duration(Stage::Db, db_start.elapsed());
+        metrics::record_inserted_rows("pos_rewards", rewards_count);
+        metrics::record_inserted_rows("pos_reward_ledger", rewards_count);
+    }
 
     let timestamp = DateTimeUtc::now().0.timestamp();
     let crawler_state = IntervalCrawlerState { timestamp };
 
-    tracing::info!(
-        epoch = epoch_to_process,
-        delegations = delegations_pairs.len(),
-        rewards = rewards.len(),
-        non_zero_rewards = non_zero_rewards.len(),
-        "Queried rewards successfully",
-    );
-
     conn.interact(move |conn| {
         conn.build_transaction().read_write().run(
             |transaction_conn: &mut diesel::prelude::PgConnection| {
-                repository::pos_rewards::upsert_rewards(
+                repository::checkpoint::delete_checkpoints_before(
                     transaction_conn,
-                    non_zero_rewards,
+                    epoch_to_process,
                 )?;
 
                 repository::crawler_state::upsert_crawler_state(
@@ -143,13 +222,51 @@ async fn crawling_fn(
         epoch = epoch_to_process,
         "Inserted rewards into database; waiting for next epoch"
     );
+    metrics::record_last_processed(epoch_to_process as u64);
 
     Ok(())
 }
 
+async fn get_checkpoint(
+    conn: &Object,
+    epoch: u32,
+) -> Result<Option<RewardsCheckpointDb>, MainError> {
+    conn.interact(move |conn| {
+        repository::checkpoint::get_checkpoint(conn, epoch)
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+/// Delegation pairs come from the bonds indexed by the chain crawler, the node
+/// is only queried before the chain crawler indexed any of them
+async fn get_delegation_pairs(
+    conn: &Object,
+    client: &RpcClient,
+) -> Result<HashSet<DelegationPair>, MainError> {
+    let delegations_pairs = conn
+        .interact(repository::pos_rewards::query_delegation_pairs)
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()?;
+
+    if !delegations_pairs.is_empty() {
+        return Ok(delegations_pairs);
+    }
+
+    tracing::info!("No bonds indexed yet, querying delegation pairs...");
+
+    namada_service::query_delegation_pairs(client)
+        .await
+        .into_rpc_error()
+}
+
 async fn can_process(
     epoch: u32,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
 ) -> Result<bool, MainError> {
     let current_epoch = namada_service::get_current_epoch(&client.clone())
         .await
@@ -160,6 +277,7 @@ async fn can_process(
             );
             MainError::RpcError
         })?;
+    metrics::record_chain_tip(current_epoch as u64);
 
     Ok(current_epoch >= epoch)
 }
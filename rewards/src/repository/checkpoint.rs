@@ -0,0 +1,60 @@
+use anyhow::Context;
+use diesel::upsert::excluded;
+use diesel::{
+    ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
+use orm::pos_rewards::{RewardsCheckpointDb, RewardsCheckpointInsertDb};
+use orm::schema::rewards_checkpoints;
+use shared::block::Epoch;
+
+pub fn get_checkpoint(
+    conn: &mut PgConnection,
+    epoch: Epoch,
+) -> anyhow::Result<Option<RewardsCheckpointDb>> {
+    rewards_checkpoints::table
+        .filter(rewards_checkpoints::epoch.eq(epoch as i32))
+        .select(RewardsCheckpointDb::as_select())
+        .first(conn)
+        .optional()
+        .context("Failed to query rewards checkpoint from db")
+}
+
+pub fn upsert_checkpoint(
+    transaction_conn: &mut PgConnection,
+    checkpoint: RewardsCheckpointInsertDb,
+) -> anyhow::Result<()> {
+    diesel::insert_into(rewards_checkpoints::table)
+        .values(&checkpoint)
+        .on_conflict(rewards_checkpoints::epoch)
+        .do_update()
+        .set((
+            rewards_checkpoints::last_validator_address
+                .eq(excluded(rewards_checkpoints::last_validator_address)),
+            rewards_checkpoints::last_delegator_address
+                .eq(excluded(rewards_checkpoints::last_delegator_address)),
+            rewards_checkpoints::processed_pairs
+                .eq(excluded(rewards_checkpoints::processed_pairs)),
+            rewards_checkpoints::updated_at
+                .eq(excluded(rewards_checkpoints::updated_at)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update rewards checkpoint in db")?;
+
+    anyhow::Ok(())
+}
+
+/// Checkpoints of past epochs are never resumed
+pub fn delete_checkpoints_before(
+    transaction_conn: &mut PgConnection,
+    epoch: Epoch,
+) -> anyhow::Result<()> {
+    diesel::delete(
+        rewards_checkpoints::table
+            .filter(rewards_checkpoints::epoch.lt(epoch as i32)),
+    )
+    .execute(transaction_conn)
+    .context("Failed to delete old rewards checkpoints from db")?;
+
+    anyhow::Ok(())
+}
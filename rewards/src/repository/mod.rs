@@ -1,3 +1,4 @@
+pub mod checkpoint;
 pub mod crawler_state;
 pub mod pos_rewards;
 mod utils;
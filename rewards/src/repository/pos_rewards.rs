@@ -1,25 +1,79 @@
+use std::collections::{HashMap, HashSet};
+
 use anyhow::Context;
+use bigdecimal::BigDecimal;
+use diesel::dsl::sum;
 use diesel::upsert::excluded;
 use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
-use orm::pos_rewards::PosRewardInsertDb;
-use orm::schema::{pos_rewards, validators};
+use orm::pos_rewards::{PosRewardInsertDb, PosRewardLedgerInsertDb};
+use orm::schema::{bonds, pos_reward_ledger, pos_rewards, unbonds, validators};
+use shared::block::Epoch;
+use shared::id::Id;
 use shared::rewards::Reward;
 use shared::tuple_len::TupleLen;
+use shared::utils::DelegationPair;
 
 use super::utils::MAX_PARAM_SIZE;
 
+/// Returns the delegation pairs indexed by the chain crawler. Unbonded
+/// delegations and delegations with unclaimed rewards can still have rewards
+/// to claim. Validators also get rewards on their own address.
+pub fn query_delegation_pairs(
+    conn: &mut PgConnection,
+) -> anyhow::Result<HashSet<DelegationPair>> {
+    let bond_pairs = bonds::table
+        .inner_join(validators::table)
+        .select((validators::namada_address, bonds::address))
+        .distinct()
+        .load::<(String, String)>(conn)
+        .context("Failed to query bonds delegation pairs from db")?;
+
+    let unbond_pairs = unbonds::table
+        .inner_join(validators::table)
+        .select((validators::namada_address, unbonds::address))
+        .distinct()
+        .load::<(String, String)>(conn)
+        .context("Failed to query unbonds delegation pairs from db")?;
+
+    let reward_pairs = pos_rewards::table
+        .inner_join(validators::table)
+        .select((validators::namada_address, pos_rewards::owner))
+        .distinct()
+        .load::<(String, String)>(conn)
+        .context("Failed to query rewards delegation pairs from db")?;
+
+    let pairs = bond_pairs
+        .into_iter()
+        .chain(unbond_pairs)
+        .chain(reward_pairs)
+        .fold(HashSet::new(), |mut acc, (validator, delegator)| {
+            acc.insert(DelegationPair {
+                validator_address: Id::Account(validator.clone()),
+                delegator_address: Id::Account(delegator),
+            });
+            acc.insert(DelegationPair {
+                validator_address: Id::Account(validator.clone()),
+                delegator_address: Id::Account(validator),
+            });
+            acc
+        });
+
+    anyhow::Ok(pairs)
+}
+
 pub fn upsert_rewards(
     transaction_conn: &mut PgConnection,
     rewards: Vec<Reward>,
+    epoch: Epoch,
 ) -> anyhow::Result<()> {
-    let rewards_col_count = pos_rewards::all_columns.len() as i64;
+    let rewards_col_count = pos_reward_ledger::all_columns.len() as i64;
 
     for chunk in rewards
         .into_iter()
         .collect::<Vec<_>>()
         .chunks((MAX_PARAM_SIZE as i64 / rewards_col_count) as usize)
     {
-        upsert_rewards_chunk(transaction_conn, chunk.to_vec())?;
+        upsert_rewards_chunk(transaction_conn, chunk.to_vec(), epoch)?;
     }
 
     anyhow::Ok(())
@@ -28,27 +82,28 @@ pub fn upsert_rewards(
 fn upsert_rewards_chunk(
     transaction_conn: &mut PgConnection,
     rewards: Vec<Reward>,
+    epoch: Epoch,
 ) -> anyhow::Result<()> {
+    let rewards = rewards
+        .into_iter()
+        .map(|reward| {
+            let validator_id: i32 =
+                validators::table
+                    .filter(validators::namada_address.eq(
+                        &reward.delegation_pair.validator_address.to_string(),
+                    ))
+                    .select(validators::id)
+                    .first(transaction_conn)
+                    .expect("Failed to get validator");
+
+            PosRewardInsertDb::from_reward(reward, validator_id)
+        })
+        .collect::<Vec<_>>();
+
+    insert_accrued_rewards(transaction_conn, &rewards, epoch)?;
+
     diesel::insert_into(pos_rewards::table)
-        .values::<Vec<PosRewardInsertDb>>(
-            rewards
-                .into_iter()
-                .map(|reward| {
-                    let validator_id: i32 = validators::table
-                        .filter(
-                            validators::namada_address.eq(&reward
-                                .delegation_pair
-                                .validator_address
-                                .to_string()),
-                        )
-                        .select(validators::id)
-                        .first(transaction_conn)
-                        .expect("Failed to get validator");
-
-                    PosRewardInsertDb::from_reward(reward, validator_id)
-                })
-                .collect::<Vec<_>>(),
-        )
+        .values::<Vec<PosRewardInsertDb>>(rewards)
         .on_conflict((
             pos_rewards::columns::owner,
             pos_rewards::columns::validator_id,
@@ -63,3 +118,79 @@ fn upsert_rewards_chunk(
 
     Ok(())
 }
+
+/// Records the rewards accrued since the unclaimed amounts were last indexed.
+/// Claiming removes the unclaimed amount, so a delegation accrues again from
+/// zero after a claim.
+fn insert_accrued_rewards(
+    transaction_conn: &mut PgConnection,
+    rewards: &[PosRewardInsertDb],
+    epoch: Epoch,
+) -> anyhow::Result<()> {
+    let owners = rewards
+        .iter()
+        .map(|reward| reward.owner.as_str())
+        .collect::<Vec<_>>();
+
+    let previous_rewards: HashMap<(String, i32), BigDecimal> =
+        pos_rewards::table
+            .filter(pos_rewards::columns::owner.eq_any(&owners))
+            .select((
+                pos_rewards::columns::owner,
+                pos_rewards::columns::validator_id,
+                pos_rewards::columns::raw_amount,
+            ))
+            .load::<(String, i32, BigDecimal)>(transaction_conn)
+            .context("Failed to query previous rewards from db")?
+            .into_iter()
+            .map(|(owner, validator_id, amount)| {
+                ((owner, validator_id), amount)
+            })
+            .collect();
+
+    let bonded_amounts: HashMap<(String, i32), Option<BigDecimal>> =
+        bonds::table
+            .filter(bonds::columns::address.eq_any(&owners))
+            .filter(bonds::columns::start.le(epoch as i32))
+            .group_by((bonds::columns::address, bonds::columns::validator_id))
+            .select((
+                bonds::columns::address,
+                bonds::columns::validator_id,
+                sum(bonds::columns::raw_amount),
+            ))
+            .load::<(String, i32, Option<BigDecimal>)>(transaction_conn)
+            .context("Failed to query bonded amounts from db")?
+            .into_iter()
+            .map(|(owner, validator_id, amount)| {
+                ((owner, validator_id), amount)
+            })
+            .collect();
+
+    let accrued_rewards = rewards
+        .iter()
+        .map(|reward| {
+            let key = (reward.owner.clone(), reward.validator_id);
+
+            PosRewardLedgerInsertDb::accrued(
+                reward,
+                previous_rewards.get(&key),
+                bonded_amounts.get(&key).cloned().flatten(),
+                epoch,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(pos_reward_ledger::table)
+        .values::<Vec<PosRewardLedgerInsertDb>>(accrued_rewards)
+        .on_conflict((
+            pos_reward_ledger::columns::owner,
+            pos_reward_ledger::columns::validator_id,
+            pos_reward_ledger::columns::epoch,
+            pos_reward_ledger::columns::kind,
+        ))
+        .do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert accrued rewards in db")?;
+
+    anyhow::Ok(())
+}
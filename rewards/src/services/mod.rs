@@ -1 +1,2 @@
 pub mod namada;
+pub mod utils;
@@ -7,13 +7,13 @@ use namada_sdk::queries::RPC;
 use namada_sdk::rpc;
 use shared::balance::Amount;
 use shared::block::Epoch;
+use shared::client::RpcClient;
 use shared::id::Id;
 use shared::rewards::Reward;
 use shared::utils::DelegationPair;
-use tendermint_rpc::HttpClient;
 
 pub async fn query_delegation_pairs(
-    client: &HttpClient,
+    client: &RpcClient,
 ) -> anyhow::Result<HashSet<DelegationPair>> {
     let data = rpc::bonds_and_unbonds(client, &None, &None)
         .await
@@ -39,15 +39,13 @@ pub async fn query_delegation_pairs(
 }
 
 pub async fn query_rewards(
-    client: &HttpClient,
-    delegation_pairs: &HashSet<DelegationPair>,
+    client: &RpcClient,
+    delegation_pairs: &[DelegationPair],
+    max_concurrent_batches: usize,
 ) -> anyhow::Result<Vec<Reward>> {
     let mut all_rewards: Vec<Reward> = Vec::new();
 
     let batches: Vec<(usize, Vec<DelegationPair>)> = delegation_pairs
-        .clone()
-        .into_iter()
-        .collect::<Vec<_>>()
         .chunks(32)
         .enumerate()
         .map(|(i, chunk)| (i, chunk.to_vec()))
@@ -61,7 +59,7 @@ pub async fn query_rewards(
 
     let results = futures::stream::iter(batches)
         .map(|batch| process_batch_with_retries(client, batch))
-        .buffer_unordered(3)
+        .buffer_unordered(max_concurrent_batches)
         .collect::<Vec<_>>()
         .await;
 
@@ -77,7 +75,7 @@ pub async fn query_rewards(
     Ok(all_rewards)
 }
 
-pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
+pub async fn get_current_epoch(client: &RpcClient) -> anyhow::Result<Epoch> {
     let epoch = rpc::query_epoch(client)
         .await
         .context("Failed to query Namada's current epoch")?;
@@ -86,7 +84,7 @@ pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
 }
 
 async fn process_batch_with_retries(
-    client: &HttpClient,
+    client: &RpcClient,
     batch: (usize, Vec<DelegationPair>),
 ) -> anyhow::Result<Vec<Reward>> {
     let mut retries = 0;
@@ -122,7 +120,7 @@ async fn process_batch_with_retries(
 }
 
 async fn process_batch(
-    client: &HttpClient,
+    client: &RpcClient,
     batch: Vec<DelegationPair>,
 ) -> anyhow::Result<Vec<Reward>> {
     Ok(futures::stream::iter(batch)
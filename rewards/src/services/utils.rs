@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+
+use shared::utils::DelegationPair;
+
+/// Sort the delegation pairs by validator and delegator address and keep the
+/// ones after the last pair stored by a checkpoint, so that a restart resumes
+/// where it stopped. Pairs indexed in the meantime before that one are picked
+/// up at the next epoch.
+pub fn pairs_after_checkpoint(
+    delegations_pairs: HashSet<DelegationPair>,
+    last_pair: Option<(String, String)>,
+) -> Vec<DelegationPair> {
+    let mut delegations_pairs = delegations_pairs
+        .into_iter()
+        .map(|pair| {
+            (
+                (
+                    pair.validator_address.to_string(),
+                    pair.delegator_address.to_string(),
+                ),
+                pair,
+            )
+        })
+        .collect::<Vec<_>>();
+    delegations_pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    delegations_pairs
+        .into_iter()
+        .filter(|(key, _)| last_pair.as_ref().is_none_or(|last| key > last))
+        .map(|(_, pair)| pair)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::id::Id;
+
+    use super::*;
+
+    fn pair(validator: &str, delegator: &str) -> DelegationPair {
+        DelegationPair {
+            validator_address: Id::Account(validator.to_string()),
+            delegator_address: Id::Account(delegator.to_string()),
+        }
+    }
+
+    fn pairs() -> HashSet<DelegationPair> {
+        HashSet::from([
+            pair("tnam1validator2", "tnam1delegator1"),
+            pair("tnam1validator1", "tnam1delegator2"),
+            pair("tnam1validator1", "tnam1delegator1"),
+        ])
+    }
+
+    #[test]
+    fn pairs_are_sorted_without_checkpoint() {
+        let pairs = pairs_after_checkpoint(pairs(), None);
+
+        assert_eq!(
+            pairs,
+            vec![
+                pair("tnam1validator1", "tnam1delegator1"),
+                pair("tnam1validator1", "tnam1delegator2"),
+                pair("tnam1validator2", "tnam1delegator1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_resume_after_checkpoint() {
+        let pairs = pairs_after_checkpoint(
+            pairs(),
+            Some((
+                "tnam1validator1".to_string(),
+                "tnam1delegator2".to_string(),
+            )),
+        );
+
+        assert_eq!(pairs, vec![pair("tnam1validator2", "tnam1delegator1")]);
+    }
+
+    #[test]
+    fn pairs_resume_after_removed_checkpoint_pair() {
+        // The last stored pair has since been unbonded
+        let pairs = pairs_after_checkpoint(
+            pairs(),
+            Some((
+                "tnam1validator1".to_string(),
+                "tnam1delegator15".to_string(),
+            )),
+        );
+
+        assert_eq!(
+            pairs,
+            vec![
+                pair("tnam1validator1", "tnam1delegator2"),
+                pair("tnam1validator2", "tnam1delegator1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_pairs_left_after_last_checkpoint() {
+        let pairs = pairs_after_checkpoint(
+            pairs(),
+            Some((
+                "tnam1validator2".to_string(),
+                "tnam1delegator1".to_string(),
+            )),
+        );
+
+        assert!(pairs.is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+use namada_sdk::account::{InitAccount, UpdateAccount};
+
+use crate::block::BlockHeight;
+use crate::id::Id;
+use crate::public_key::PublicKey;
+
+/// Change to the vp, public keys or threshold of an established account. Only
+/// the fields set by the transaction are `Some`.
+#[derive(Debug, Clone)]
+pub struct AccountUpdate {
+    pub address: Id,
+    pub vp_code_hash: Option<Id>,
+    pub public_keys: Option<Vec<String>>,
+    pub threshold: Option<u8>,
+    pub created_at: Option<BlockHeight>,
+    pub block_height: BlockHeight,
+}
+
+impl AccountUpdate {
+    pub fn from_init_account(
+        address: Id,
+        data: InitAccount,
+        block_height: BlockHeight,
+    ) -> Self {
+        Self {
+            address,
+            vp_code_hash: Some(Id::from(data.vp_code_hash)),
+            public_keys: Some(public_keys(data.public_keys)),
+            threshold: Some(data.threshold),
+            created_at: Some(block_height),
+            block_height,
+        }
+    }
+
+    pub fn from_update_account(
+        data: UpdateAccount,
+        block_height: BlockHeight,
+    ) -> Self {
+        // An empty list of keys leaves the stored ones untouched
+        let public_keys = (!data.public_keys.is_empty())
+            .then(|| public_keys(data.public_keys));
+
+        Self {
+            address: Id::from(data.addr),
+            vp_code_hash: data.vp_code_hash.map(Id::from),
+            public_keys,
+            threshold: data.threshold,
+            created_at: None,
+            block_height,
+        }
+    }
+}
+
+fn public_keys(
+    public_keys: Vec<namada_sdk::key::common::PublicKey>,
+) -> Vec<String> {
+    public_keys
+        .into_iter()
+        .map(|pk| PublicKey::from(pk).0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::gen_established_address;
+
+    use super::*;
+
+    #[test]
+    fn test_update_account_without_keys_keeps_them() {
+        let address = gen_established_address("namada-indexer");
+        let update = AccountUpdate::from_update_account(
+            UpdateAccount {
+                addr: address.clone(),
+                vp_code_hash: None,
+                public_keys: vec![],
+                threshold: Some(2),
+            },
+            10,
+        );
+
+        assert_eq!(update.address, Id::from(address));
+        assert_eq!(update.public_keys, None);
+        assert_eq!(update.vp_code_hash, None);
+        assert_eq!(update.threshold, Some(2));
+        assert_eq!(update.created_at, None);
+        assert_eq!(update.block_height, 10);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
 use namada_ibc::IbcMessage;
@@ -10,21 +10,26 @@ use namada_sdk::token::Transfer;
 use subtle_encoding::hex;
 use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 
+use crate::account::AccountUpdate;
+use crate::balance::Amount;
 use crate::block_result::BlockResult;
 use crate::bond::BondAddresses;
 use crate::checksums::Checksums;
 use crate::header::BlockHeader;
 use crate::id::Id;
 use crate::masp::{MaspEntry, MaspEntryDirection};
-use crate::proposal::{GovernanceProposal, GovernanceProposalKind};
+use crate::proposal::{
+    GovernanceProposal, GovernanceProposalKind, ProposalWasmCode,
+};
 use crate::public_key::PublicKey;
+use crate::rewards::RewardClaim;
 use crate::token::{IbcToken, Token};
 use crate::transaction::{
     InnerTransaction, Transaction, TransactionKind, TransactionTarget,
     WrapperTransaction,
 };
 use crate::unbond::UnbondAddresses;
-use crate::utils::{BalanceChange, MASP_ADDRESS};
+use crate::utils::{BalanceChange, DelegationPair, MASP_ADDRESS};
 use crate::validator::{
     Validator, ValidatorMetadataChange, ValidatorState, ValidatorStateChange,
 };
@@ -100,6 +105,23 @@ impl TxAttributes {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockMismatchKind {
+    Hash,
+    ParentHash,
+    AppHash,
+}
+
+/// Disagreement between an already indexed block and the one returned by
+/// the RPC node
+#[derive(Debug, Clone)]
+pub struct BlockMismatch {
+    pub height: BlockHeight,
+    pub kind: BlockMismatchKind,
+    pub indexed_value: String,
+    pub rpc_value: String,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Block {
     pub hash: Id,
@@ -155,12 +177,59 @@ impl Block {
                     .map(Id::to_string),
                 timestamp: block_response.block.header.time.unix_timestamp(),
                 app_hash: Id::from(&block_response.block.header.app_hash),
+                parent_hash: block_response
+                    .block
+                    .header
+                    .last_block_id
+                    .map(Id::from),
             },
             transactions,
             epoch,
         }
     }
 
+    /// Compares the block header against the block indexed at the same
+    /// height and the hash of the indexed parent block, if any.
+    pub fn mismatches(
+        &self,
+        indexed_hash: Option<&str>,
+        indexed_app_hash: Option<&str>,
+        indexed_parent_hash: Option<&str>,
+    ) -> Vec<BlockMismatch> {
+        let parent_hash = self.header.parent_hash.as_ref().map(Id::to_string);
+
+        [
+            (
+                BlockMismatchKind::Hash,
+                indexed_hash,
+                Some(self.hash.to_string()),
+            ),
+            (
+                BlockMismatchKind::AppHash,
+                indexed_app_hash,
+                Some(self.header.app_hash.to_string()),
+            ),
+            (
+                BlockMismatchKind::ParentHash,
+                indexed_parent_hash,
+                parent_hash,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(kind, indexed_value, rpc_value)| {
+            let indexed_value = indexed_value?.to_lowercase();
+            let rpc_value = rpc_value?;
+
+            (indexed_value != rpc_value).then_some(BlockMismatch {
+                height: self.header.height,
+                kind,
+                indexed_value,
+                rpc_value,
+            })
+        })
+        .collect()
+    }
+
     pub fn inner_txs(&self) -> Vec<InnerTransaction> {
         self.transactions
             .iter()
@@ -390,6 +459,97 @@ impl Block {
                         vec![]
                     }
                 }
+                TransactionKind::InitAccount(init_account) => {
+                    // The address of the new account is not part of the tx
+                    // data, attach the tx to the keys controlling it and to
+                    // the accounts reported in the tx result
+                    if let Some(data) = init_account {
+                        data.public_keys
+                            .iter()
+                            .map(|public_key| {
+                                TransactionTarget::sent(
+                                    tx.tx_id.clone(),
+                                    Address::from(public_key).to_string(),
+                                )
+                            })
+                            .chain(tx.initialized_accounts.iter().map(
+                                |address| {
+                                    TransactionTarget::received(
+                                        tx.tx_id.clone(),
+                                        address.to_string(),
+                                    )
+                                },
+                            ))
+                            .collect()
+                    } else {
+                        vec![]
+                    }
+                }
+                TransactionKind::UpdateAccount(update_account) => {
+                    if let Some(data) = update_account {
+                        vec![TransactionTarget::sent(
+                            tx.tx_id,
+                            data.addr.to_string(),
+                        )]
+                    } else {
+                        vec![]
+                    }
+                }
+                TransactionKind::ChangeConsensusKey(consensus_key_change) => {
+                    if let Some(data) = consensus_key_change {
+                        vec![TransactionTarget::sent(
+                            tx.tx_id,
+                            data.validator.to_string(),
+                        )]
+                    } else {
+                        vec![]
+                    }
+                }
+                TransactionKind::ResignSteward(address) => {
+                    if let Some(data) = address {
+                        vec![TransactionTarget::sent(
+                            tx.tx_id,
+                            data.to_string(),
+                        )]
+                    } else {
+                        vec![]
+                    }
+                }
+                TransactionKind::UpdateStewardCommission(commission) => {
+                    if let Some(data) = commission {
+                        let sent = TransactionTarget::sent(
+                            tx.tx_id.clone(),
+                            data.steward.to_string(),
+                        );
+                        let received = data.commission.keys().map(|target| {
+                            TransactionTarget::received(
+                                tx.tx_id.clone(),
+                                target.to_string(),
+                            )
+                        });
+
+                        std::iter::once(sent).chain(received).collect()
+                    } else {
+                        vec![]
+                    }
+                }
+                TransactionKind::BridgePoolTransfer(pending_transfer) => {
+                    if let Some(data) = pending_transfer {
+                        let mut targets = vec![TransactionTarget::sent(
+                            tx.tx_id.clone(),
+                            data.transfer.sender.to_string(),
+                        )];
+                        if data.gas_fee.payer != data.transfer.sender {
+                            targets.push(TransactionTarget::sent(
+                                tx.tx_id,
+                                data.gas_fee.payer.to_string(),
+                            ));
+                        }
+                        targets
+                    } else {
+                        vec![]
+                    }
+                }
                 TransactionKind::Unknown(_) => vec![],
             })
             .collect::<HashSet<_>>()
@@ -512,6 +672,7 @@ impl Block {
     pub fn governance_proposal(
         &self,
         mut next_proposal_id: u64,
+        checksums: &Checksums,
     ) -> Vec<GovernanceProposal> {
         self.transactions
             .iter()
@@ -544,6 +705,8 @@ impl Block {
                         serde_json::to_string_pretty(&proposal_content)
                             .unwrap_or_default();
 
+                    let mut proposal_wasm_code = None;
+
                     let proposal_data = match init_proposal_data.r#type.clone()
                     {
                         namada_governance::ProposalType::DefaultWithWasm(
@@ -552,6 +715,10 @@ impl Block {
                             let wasm_code =
                                 tx.get_section_data_by_id(Id::from(hash));
                             if let Some(wasm_code) = wasm_code {
+                                proposal_wasm_code =
+                                    Some(ProposalWasmCode::new(
+                                        &wasm_code, checksums,
+                                    ));
                                 let hex_encoded =
                                     String::from_utf8(hex::encode(wasm_code))
                                         .unwrap_or_default();
@@ -589,6 +756,8 @@ impl Block {
                             init_proposal_data.activation_epoch.0 as u32,
                         ),
                         content: proposal_content_serialized,
+                        inner_tx_id: Some(tx.tx_id.clone()),
+                        wasm_code: proposal_wasm_code,
                     })
                 }
                 _ => None,
@@ -596,7 +765,24 @@ impl Block {
             .collect()
     }
 
-    pub fn pos_rewards(&self) -> HashSet<(Id, Id)> {
+    /// Successful reward claims of the block. Claims are matched to the
+    /// rewards transferred to their source in execution order, so multiple
+    /// claims of the same source get their own amount.
+    pub fn pos_rewards(
+        &self,
+        block_results: &BlockResult,
+    ) -> HashSet<RewardClaim> {
+        let mut claimed_rewards =
+            block_results.claimed_rewards().into_iter().fold(
+                HashMap::<Id, VecDeque<Amount>>::new(),
+                |mut acc, claimed| {
+                    acc.entry(claimed.owner)
+                        .or_default()
+                        .push_back(claimed.amount);
+                    acc
+                },
+            );
+
         self.transactions
             .iter()
             .fold(vec![], |mut acc, (wrapper_tx, inner_txs)| {
@@ -613,12 +799,25 @@ impl Block {
             .filter_map(|tx| match &tx.kind {
                 TransactionKind::ClaimRewards(Some(data)) => {
                     let validator = data.validator.to_owned();
-                    let source = data
-                        .source
-                        .to_owned()
-                        .unwrap_or_else(|| validator.clone());
-
-                    Some((Id::from(source), Id::from(validator)))
+                    let source = Id::from(
+                        data.source
+                            .to_owned()
+                            .unwrap_or_else(|| validator.clone()),
+                    );
+
+                    let amount = claimed_rewards
+                        .get_mut(&source)
+                        .and_then(VecDeque::pop_front)
+                        .unwrap_or_else(Amount::zero);
+
+                    Some(RewardClaim {
+                        delegation_pair: DelegationPair {
+                            validator_address: Id::from(validator),
+                            delegator_address: source,
+                        },
+                        inner_tx_id: tx.tx_id.clone(),
+                        amount,
+                    })
                 }
                 _ => None,
             })
@@ -652,6 +851,28 @@ impl Block {
             .collect()
     }
 
+    /// Whether the block contains successful IBC connection or channel
+    /// handshake messages, which change the channels of the chain
+    pub fn has_ibc_channel_updates(&self) -> bool {
+        self.transactions.iter().any(|(wrapper_tx, inner_txs)| {
+            inner_txs.iter().any(|inner_tx| match &inner_tx.kind {
+                TransactionKind::IbcMsg(Some(ibc_message)) => {
+                    inner_tx.was_successful(wrapper_tx)
+                        && matches!(
+                            &ibc_message.0,
+                            IbcMessage::Envelope(msg_envelope)
+                                if matches!(
+                                    msg_envelope.as_ref(),
+                                    MsgEnvelope::Connection(_)
+                                        | MsgEnvelope::Channel(_)
+                                )
+                        )
+                }
+                _ => false,
+            })
+        })
+    }
+
     pub fn ibc_tokens(&self) -> HashSet<IbcToken> {
         self.transactions
             .iter()
@@ -802,6 +1023,20 @@ impl Block {
                     Token::Native(native_token.clone()),
                 )]
             }
+            TransactionKind::BridgePoolTransfer(data) => {
+                let data = data.as_ref()?;
+
+                vec![
+                    BalanceChange::new(
+                        Id::from(data.transfer.sender.clone()),
+                        Token::Native(Id::from(data.token_address())),
+                    ),
+                    BalanceChange::new(
+                        Id::from(data.gas_fee.payer.clone()),
+                        Token::Native(Id::from(data.gas_fee.token.clone())),
+                    ),
+                ]
+            }
             TransactionKind::Redelegation(_)
             | TransactionKind::CommissionChange(_)
             | TransactionKind::RevealPk(_)
@@ -812,7 +1047,12 @@ impl Block {
             | TransactionKind::ReactivateValidator(_)
             | TransactionKind::Unbond(_)
             | TransactionKind::BecomeValidator(_)
-            | TransactionKind::ProposalVote(_) => Default::default(),
+            | TransactionKind::ProposalVote(_)
+            | TransactionKind::InitAccount(_)
+            | TransactionKind::UpdateAccount(_)
+            | TransactionKind::ChangeConsensusKey(_)
+            | TransactionKind::ResignSteward(_)
+            | TransactionKind::UpdateStewardCommission(_) => Default::default(),
         };
 
         Some(change)
@@ -1101,6 +1341,45 @@ impl Block {
             .collect()
     }
 
+    /// Established accounts created or updated in this block, in execution
+    /// order
+    pub fn account_updates(&self) -> Vec<AccountUpdate> {
+        self.transactions
+            .iter()
+            .fold(vec![], |mut acc, (wrapper_tx, inner_txs)| {
+                // Extract successful inner txs
+                for inner_tx in inner_txs {
+                    if inner_tx.was_successful(wrapper_tx) {
+                        acc.push(inner_tx)
+                    }
+                }
+
+                acc
+            })
+            .iter()
+            .flat_map(|tx| match &tx.kind {
+                TransactionKind::InitAccount(Some(data)) => tx
+                    .initialized_accounts
+                    .iter()
+                    .map(|address| {
+                        AccountUpdate::from_init_account(
+                            address.clone(),
+                            data.clone(),
+                            self.header.height,
+                        )
+                    })
+                    .collect(),
+                TransactionKind::UpdateAccount(Some(data)) => {
+                    vec![AccountUpdate::from_update_account(
+                        data.clone(),
+                        self.header.height,
+                    )]
+                }
+                _ => vec![],
+            })
+            .collect()
+    }
+
     pub fn revealed_pks(&self) -> Vec<(PublicKey, Id)> {
         self.transactions
             .iter()
@@ -1125,3 +1404,62 @@ impl Block {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block() -> Block {
+        Block {
+            hash: Id::Hash("ABCD".to_string()),
+            header: BlockHeader {
+                height: 10,
+                app_hash: Id::Hash("EF01".to_string()),
+                parent_hash: Some(Id::Hash("2345".to_string())),
+                ..BlockHeader::default()
+            },
+            ..Block::default()
+        }
+    }
+
+    #[test]
+    fn test_mismatches_with_matching_block() {
+        let mismatches =
+            block().mismatches(Some("abcd"), Some("ef01"), Some("2345"));
+
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_mismatches_with_nothing_indexed() {
+        let mismatches = block().mismatches(None, None, None);
+
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_mismatches_with_different_parent() {
+        let mismatches = block().mismatches(None, None, Some("6789"));
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, BlockMismatchKind::ParentHash);
+        assert_eq!(mismatches[0].height, 10);
+        assert_eq!(mismatches[0].indexed_value, "6789");
+        assert_eq!(mismatches[0].rpc_value, "2345");
+    }
+
+    #[test]
+    fn test_mismatches_with_different_block() {
+        let mismatches =
+            block().mismatches(Some("1111"), Some("2222"), Some("2345"));
+
+        let kinds = mismatches
+            .into_iter()
+            .map(|mismatch| mismatch.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![BlockMismatchKind::Hash, BlockMismatchKind::AppHash]
+        );
+    }
+}
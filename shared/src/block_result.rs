@@ -4,9 +4,14 @@ use std::fmt;
 use std::str::FromStr;
 
 use bigdecimal::BigDecimal;
+use namada_core::address::Address;
 use namada_core::token::Amount as NamadaAmount;
+use namada_core::uint::Uint;
 use namada_ibc::apps::transfer::types::packet::PacketData as Ics20PacketData;
-use namada_sdk::events::extend::{IndexedMaspData, MaspTxRefs};
+use namada_proof_of_stake::CLAIM_REWARDS_EVENT_DESC;
+use namada_sdk::events::extend::{
+    EventValue, IndexedMaspData, MaspTxRefs, UserAccount,
+};
 use namada_tx::data::TxResult;
 use tendermint_rpc::endpoint::block_results::Response as TendermintBlockResultResponse;
 
@@ -27,6 +32,9 @@ pub enum EventKind {
     Applied,
     IbcCore(IbcCorePacketKind),
     FungibleTokenPacket,
+    ProposalPassed,
+    ProposalRejected,
+    TokenTransfer,
     Unknown,
 }
 
@@ -37,6 +45,9 @@ impl From<&String> for EventKind {
             "send_packet" => Self::IbcCore(IbcCorePacketKind::Send),
             "recv_packet" => Self::IbcCore(IbcCorePacketKind::Recv),
             "fungible_token_packet" => Self::FungibleTokenPacket,
+            "governance/proposal/passed" => Self::ProposalPassed,
+            "governance/proposal/rejected" => Self::ProposalRejected,
+            "token/transfer" => Self::TokenTransfer,
             _ => Self::Unknown,
         }
     }
@@ -75,6 +86,7 @@ impl From<&str> for TxEventStatusCode {
 pub struct BatchResults {
     pub batch_errors: BTreeMap<Id, BTreeMap<Id, String>>,
     pub batch_results: BTreeMap<Id, bool>,
+    pub batch_initialized_accounts: BTreeMap<Id, Vec<Id>>,
 }
 
 impl From<TxResult<String>> for BatchResults {
@@ -113,6 +125,24 @@ impl From<TxResult<String>> for BatchResults {
                     acc
                 },
             ),
+            batch_initialized_accounts: value.0.iter().fold(
+                BTreeMap::default(),
+                |mut acc, (tx_hash, result)| {
+                    let tx_id = Id::from(*tx_hash);
+                    let result = if let Ok(result) = result {
+                        result
+                            .initialized_accounts
+                            .iter()
+                            .cloned()
+                            .map(Id::from)
+                            .collect()
+                    } else {
+                        vec![]
+                    };
+                    acc.insert(tx_id, result);
+                    acc
+                },
+            ),
         }
     }
 }
@@ -186,6 +216,24 @@ pub struct FungibleTokenPacket {
     pub amount: BigDecimal,
 }
 
+/// A governance proposal tallied and, if it passed, executed by the ledger at
+/// its activation epoch
+#[derive(Debug, Clone)]
+pub struct ProposalEnded {
+    pub proposal_id: u64,
+    pub passed: bool,
+    pub has_proposal_code: bool,
+    pub proposal_code_exit_status: bool,
+}
+
+/// Rewards transferred by the PoS account to the owner of a bond that claimed
+/// them
+#[derive(Debug, Clone)]
+pub struct RewardsClaimed {
+    pub owner: Id,
+    pub amount: Amount,
+}
+
 #[derive(Debug, Clone)]
 pub enum TxAttributesType {
     TxApplied(TxApplied),
@@ -196,6 +244,8 @@ pub enum TxAttributesType {
         success: bool,
         packet: FungibleTokenPacket,
     },
+    ProposalEnded(ProposalEnded),
+    RewardsClaimed(RewardsClaimed),
 }
 
 impl TxAttributesType {
@@ -284,6 +334,44 @@ impl TxAttributesType {
                     data,
                 }))
             }
+            EventKind::ProposalPassed | EventKind::ProposalRejected => {
+                Some(Self::ProposalEnded(ProposalEnded {
+                    proposal_id: attributes
+                        .get("proposal_id")?
+                        .parse::<u64>()
+                        .ok()?,
+                    passed: matches!(event_kind, EventKind::ProposalPassed),
+                    has_proposal_code: attributes
+                        .get("has_proposal_code")
+                        .is_some_and(|value| value == "true"),
+                    proposal_code_exit_status: attributes
+                        .get("proposal_code_exit_status")
+                        .is_some_and(|value| value == "true"),
+                }))
+            }
+            EventKind::TokenTransfer => {
+                // Only the transfers of claimed rewards are indexed
+                if attributes.get("token-event-descriptor")?
+                    != CLAIM_REWARDS_EVENT_DESC
+                {
+                    return None;
+                }
+
+                let targets = attributes
+                    .get("target-accounts")?
+                    .parse::<EventValue<Vec<((UserAccount, Address), Uint)>>>()
+                    .ok()?;
+                let ((target, _token), amount) =
+                    targets.0.into_iter().next()?;
+                let UserAccount::Internal(owner) = target else {
+                    return None;
+                };
+
+                Some(Self::RewardsClaimed(RewardsClaimed {
+                    owner: Id::from(owner),
+                    amount: Amount::from(NamadaAmount::from(amount)),
+                }))
+            }
             EventKind::Applied => Some(Self::TxApplied(TxApplied {
                 code: attributes
                     .get("code")
@@ -507,6 +595,34 @@ impl BlockResult {
         exit_status.unwrap_or(TransactionExitStatus::Rejected)
     }
 
+    /// Established addresses created by the inner tx
+    pub fn initialized_accounts(
+        &self,
+        wrapper_hash: &Id,
+        inner_hash: &Id,
+    ) -> Vec<Id> {
+        self.end_events
+            .iter()
+            .filter_map(|event| {
+                if let Some(TxAttributesType::TxApplied(data)) =
+                    &event.attributes
+                {
+                    Some(data.clone())
+                } else {
+                    None
+                }
+            })
+            .find(|attributes| attributes.hash.eq(wrapper_hash))
+            .and_then(|attributes| {
+                attributes
+                    .batch
+                    .batch_initialized_accounts
+                    .get(inner_hash)
+                    .cloned()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn masp_refs(&self, wrapper_hash: &Id, index: u64) -> MaspTxRefs {
         self.end_events
             .iter()
@@ -525,6 +641,39 @@ impl BlockResult {
             })
             .unwrap_or_default()
     }
+
+    /// Governance proposals tallied and executed by the ledger in this block
+    /// Rewards claimed in the block, in the order the claims were executed
+    pub fn claimed_rewards(&self) -> Vec<RewardsClaimed> {
+        self.end_events
+            .iter()
+            .filter_map(|event| {
+                if let Some(TxAttributesType::RewardsClaimed(data)) =
+                    &event.attributes
+                {
+                    Some(data.clone())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn ended_proposals(&self) -> Vec<ProposalEnded> {
+        self.begin_events
+            .iter()
+            .chain(self.end_events.iter())
+            .filter_map(|event| {
+                if let Some(TxAttributesType::ProposalEnded(data)) =
+                    &event.attributes
+                {
+                    Some(data.clone())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -541,6 +690,98 @@ mod tests {
         attributes: Vec<TestAttribute>,
     }
 
+    #[test]
+    fn governance_proposal_events() {
+        let attributes = |has_code: &str, exit_status: &str| {
+            BTreeMap::from_iter([
+                ("proposal_id".to_owned(), "12".to_owned()),
+                ("tally_result".to_owned(), "passed".to_owned()),
+                ("has_proposal_code".to_owned(), has_code.to_owned()),
+                (
+                    "proposal_code_exit_status".to_owned(),
+                    exit_status.to_owned(),
+                ),
+            ])
+        };
+
+        let passed = EventKind::from(&"governance/proposal/passed".to_owned());
+        let rejected =
+            EventKind::from(&"governance/proposal/rejected".to_owned());
+
+        let block_result = BlockResult {
+            height: 1,
+            begin_events: vec![],
+            end_events: vec![
+                Event {
+                    attributes: TxAttributesType::deserialize(
+                        &passed,
+                        &attributes("true", "false"),
+                    ),
+                    kind: passed,
+                },
+                Event {
+                    attributes: TxAttributesType::deserialize(
+                        &rejected,
+                        &attributes("false", "false"),
+                    ),
+                    kind: rejected,
+                },
+            ],
+        };
+
+        let ended = block_result.ended_proposals();
+
+        assert_eq!(ended.len(), 2);
+        assert!(ended[0].passed);
+        assert_eq!(ended[0].proposal_id, 12);
+        assert!(ended[0].has_proposal_code);
+        assert!(!ended[0].proposal_code_exit_status);
+        assert!(!ended[1].passed);
+        assert!(!ended[1].has_proposal_code);
+    }
+
+    #[test]
+    fn claimed_rewards_events() {
+        let owner = namada_core::address::gen_established_address("owner");
+        let token = namada_core::address::gen_established_address("token");
+        let attributes = |descriptor: &str, amount: u64| {
+            let targets = EventValue::from(vec![(
+                (UserAccount::Internal(owner.clone()), token.clone()),
+                Uint::from(amount),
+            )]);
+
+            BTreeMap::from_iter([
+                ("token-event-descriptor".to_owned(), descriptor.to_owned()),
+                ("target-accounts".to_owned(), targets.to_string()),
+            ])
+        };
+
+        let kind = EventKind::from(&"token/transfer".to_owned());
+        let block_result = BlockResult {
+            height: 1,
+            begin_events: vec![],
+            end_events: [
+                attributes(CLAIM_REWARDS_EVENT_DESC, 10),
+                attributes("transfer-from-wasm", 20),
+                attributes(CLAIM_REWARDS_EVENT_DESC, 30),
+            ]
+            .iter()
+            .map(|attributes| Event {
+                attributes: TxAttributesType::deserialize(&kind, attributes),
+                kind: kind.clone(),
+            })
+            .collect(),
+        };
+
+        let claimed = block_result.claimed_rewards();
+
+        assert_eq!(claimed.len(), 2);
+        assert_eq!(claimed[0].owner, Id::from(owner.clone()));
+        assert_eq!(claimed[0].amount, Amount::from(NamadaAmount::from(10)));
+        assert_eq!(claimed[1].owner, Id::from(owner));
+        assert_eq!(claimed[1].amount, Amount::from(NamadaAmount::from(30)));
+    }
+
     #[test]
     fn ibc_fungible_token_events() {
         let mut events: Vec<_> = example_events()
@@ -0,0 +1,586 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tendermint::Hash;
+use tendermint::block::Height;
+use tendermint::evidence::Evidence;
+use tendermint_rpc::client::CompatMode;
+use tendermint_rpc::endpoint::{
+    abci_query, block, block_by_hash, block_results, block_search, broadcast,
+    evidence, header, header_by_hash, tx, tx_search,
+};
+use tendermint_rpc::error::ErrorDetail;
+use tendermint_rpc::query::Query;
+use tendermint_rpc::{Client, Error, HttpClient, Order, SimpleRequest};
+
+/// How long a node is skipped after failing repeatedly, doubled on each
+/// extra failure
+const BASE_COOLDOWN: Duration = Duration::from_secs(5);
+const MAX_COOLDOWN: Duration = Duration::from_secs(120);
+/// Consecutive failures after which a node is considered unhealthy
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+/// How often the block range served by each node is refreshed
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+/// Weight of the latest request in the moving averages
+const SMOOTHING: f64 = 0.2;
+
+#[derive(Debug, Default, Clone)]
+struct NodeHealth {
+    /// Moving average of the latency of successful requests, in ms
+    latency: f64,
+    /// Moving average of the ratio of failed requests
+    error_rate: f64,
+    consecutive_failures: u32,
+    last_failure: Option<Instant>,
+    /// First block still stored by the node, pruned nodes don't serve the
+    /// ones before it
+    earliest_block_height: Option<u64>,
+}
+
+impl NodeHealth {
+    fn is_cooling_down(&self) -> bool {
+        if self.consecutive_failures < MAX_CONSECUTIVE_FAILURES {
+            return false;
+        }
+        let cooldown = BASE_COOLDOWN
+            .saturating_mul(2u32.saturating_pow(
+                self.consecutive_failures - MAX_CONSECUTIVE_FAILURES,
+            ))
+            .min(MAX_COOLDOWN);
+
+        self.last_failure
+            .is_some_and(|last_failure| last_failure.elapsed() < cooldown)
+    }
+
+    fn covers(&self, height: Option<u64>) -> bool {
+        match (height, self.earliest_block_height) {
+            (Some(height), Some(earliest)) => earliest <= height,
+            _ => true,
+        }
+    }
+
+    /// Lower is better, a failed request weighs as much as one second of
+    /// latency
+    fn score(&self) -> f64 {
+        self.latency + 1000.0 * self.error_rate
+    }
+
+    fn record_success(&mut self, latency: Duration) {
+        let latency = latency.as_secs_f64() * 1000.0;
+        self.latency = if self.latency == 0.0 {
+            latency
+        } else {
+            SMOOTHING * latency + (1.0 - SMOOTHING) * self.latency
+        };
+        self.error_rate *= 1.0 - SMOOTHING;
+        self.consecutive_failures = 0;
+    }
+
+    fn record_failure(&mut self) {
+        self.error_rate = SMOOTHING + (1.0 - SMOOTHING) * self.error_rate;
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_failure = Some(Instant::now());
+    }
+}
+
+#[derive(Debug)]
+struct RpcNode {
+    url: String,
+    client: HttpClient,
+    health: Mutex<NodeHealth>,
+}
+
+impl RpcNode {
+    fn health(&self) -> NodeHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    fn update_health(&self, f: impl FnOnce(&mut NodeHealth)) {
+        f(&mut self.health.lock().unwrap())
+    }
+}
+
+/// Whether the node could not answer at all, as opposed to answering with an
+/// error that any other node would return as well
+fn is_node_failure(error: &Error) -> bool {
+    match error.detail() {
+        ErrorDetail::Io(_)
+        | ErrorDetail::Http(_)
+        | ErrorDetail::Timeout(_)
+        | ErrorDetail::ChannelSend(_)
+        | ErrorDetail::Join(_) => true,
+        ErrorDetail::HttpRequestFailed(e) => e.status.is_server_error(),
+        _ => false,
+    }
+}
+
+/// CometBFT RPC client backed by several nodes. Requests go to the
+/// healthiest node able to serve them and fail over to the next ones on
+/// transport errors and timeouts.
+#[derive(Debug)]
+pub struct RpcClient {
+    nodes: Vec<RpcNode>,
+    status_refreshed_at: Mutex<Option<Instant>>,
+}
+
+impl RpcClient {
+    pub fn new(urls: &[String]) -> anyhow::Result<Self> {
+        anyhow::ensure!(!urls.is_empty(), "At least one RPC url is required");
+
+        let nodes = urls
+            .iter()
+            .map(|url| {
+                let client = HttpClient::builder(url.as_str().parse()?)
+                    .compat_mode(CompatMode::V0_37)
+                    .build()?;
+
+                anyhow::Ok(RpcNode {
+                    url: url.clone(),
+                    client,
+                    health: Mutex::new(NodeHealth::default()),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            nodes,
+            status_refreshed_at: Mutex::new(None),
+        })
+    }
+
+    /// Queries the block range served by each node
+    pub async fn refresh_status(&self) {
+        *self.status_refreshed_at.lock().unwrap() = Some(Instant::now());
+
+        futures::future::join_all(self.nodes.iter().map(|node| async move {
+            match node.client.status().await {
+                Ok(status) => node.update_health(|health| {
+                    health.earliest_block_height =
+                        Some(status.sync_info.earliest_block_height.value())
+                }),
+                Err(e) => {
+                    tracing::warn!(
+                        url = node.url,
+                        "Failed to query status: {}",
+                        e
+                    );
+                    node.update_health(NodeHealth::record_failure)
+                }
+            }
+        }))
+        .await;
+    }
+
+    async fn refresh_status_if_stale(&self) {
+        let is_stale = self.status_refreshed_at.lock().unwrap().is_none_or(
+            |refreshed_at| refreshed_at.elapsed() > STATUS_REFRESH_INTERVAL,
+        );
+
+        if is_stale {
+            self.refresh_status().await;
+        }
+    }
+
+    /// Url of the node currently preferred for requests
+    pub fn preferred_url(&self) -> String {
+        self.ordered_nodes(None)[0].url.clone()
+    }
+
+    /// Nodes sorted by preference: healthy nodes storing the requested
+    /// height first, then by latency and error rate
+    fn ordered_nodes(&self, height: Option<u64>) -> Vec<&RpcNode> {
+        let mut nodes = self
+            .nodes
+            .iter()
+            .map(|node| (node, node.health()))
+            .collect::<Vec<_>>();
+
+        nodes.sort_by(|(_, a), (_, b)| {
+            a.is_cooling_down()
+                .cmp(&b.is_cooling_down())
+                .then(b.covers(height).cmp(&a.covers(height)))
+                .then(a.score().total_cmp(&b.score()))
+        });
+
+        nodes.into_iter().map(|(node, _)| node).collect()
+    }
+
+    async fn call<T, F, Fut>(
+        &self,
+        height: Option<u64>,
+        f: F,
+    ) -> Result<T, Error>
+    where
+        F: Fn(HttpClient) -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T, Error>> + Send,
+    {
+        if height.is_some() {
+            self.refresh_status_if_stale().await;
+        }
+
+        let mut last_error = None;
+        for node in self.ordered_nodes(height) {
+            let start = Instant::now();
+
+            match f(node.client.clone()).await {
+                Ok(response) => {
+                    node.update_health(|health| {
+                        health.record_success(start.elapsed())
+                    });
+                    return Ok(response);
+                }
+                Err(e) if is_node_failure(&e) => {
+                    tracing::warn!(url = node.url, "RPC request failed: {}", e);
+                    node.update_health(NodeHealth::record_failure);
+                    last_error = Some(e);
+                }
+                // The node is fine, the request itself is rejected
+                Err(e) => {
+                    node.update_health(|health| {
+                        health.record_success(start.elapsed())
+                    });
+                    return Err(e);
+                }
+            }
+        }
+
+        Err(last_error.expect("There is at least one node"))
+    }
+}
+
+#[async_trait]
+impl Client for RpcClient {
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        // Requests are consumed when sent, keep a copy for each node we try
+        let request = &serde_json::to_value(request).map_err(Error::serde)?;
+        self.call(None, |client| async move {
+            let request: R = serde_json::from_value(request.clone())
+                .map_err(Error::serde)?;
+            client.perform(request).await
+        })
+        .await
+    }
+
+    async fn abci_query<V>(
+        &self,
+        path: Option<String>,
+        data: V,
+        height: Option<Height>,
+        prove: bool,
+    ) -> Result<abci_query::AbciQuery, Error>
+    where
+        V: Into<Vec<u8>> + Send,
+    {
+        let data: Vec<u8> = data.into();
+        let (path, data) = (&path, &data);
+        self.call(height.map(|height| height.value()), |client| async move {
+            client
+                .abci_query(path.clone(), data.clone(), height, prove)
+                .await
+        })
+        .await
+    }
+
+    async fn block<H>(&self, height: H) -> Result<block::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.call(Some(height.value()), |client| async move {
+            client.block(height).await
+        })
+        .await
+    }
+
+    async fn block_by_hash(
+        &self,
+        hash: Hash,
+    ) -> Result<block_by_hash::Response, Error> {
+        self.call(
+            None,
+            |client| async move { client.block_by_hash(hash).await },
+        )
+        .await
+    }
+
+    async fn latest_block(&self) -> Result<block::Response, Error> {
+        self.call(None, |client| async move { client.latest_block().await })
+            .await
+    }
+
+    async fn block_results<H>(
+        &self,
+        height: H,
+    ) -> Result<block_results::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.call(Some(height.value()), |client| async move {
+            client.block_results(height).await
+        })
+        .await
+    }
+
+    async fn latest_block_results(
+        &self,
+    ) -> Result<block_results::Response, Error> {
+        self.call(
+            None,
+            |client| async move { client.latest_block_results().await },
+        )
+        .await
+    }
+
+    async fn block_search(
+        &self,
+        query: Query,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<block_search::Response, Error> {
+        let (query, order) = (&query, &order);
+        self.call(None, |client| async move {
+            client
+                .block_search(query.clone(), page, per_page, order.clone())
+                .await
+        })
+        .await
+    }
+
+    async fn header<H>(&self, height: H) -> Result<header::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.call(Some(height.value()), |client| async move {
+            client.header(height).await
+        })
+        .await
+    }
+
+    async fn header_by_hash(
+        &self,
+        hash: Hash,
+    ) -> Result<header_by_hash::Response, Error> {
+        self.call(
+            None,
+            |client| async move { client.header_by_hash(hash).await },
+        )
+        .await
+    }
+
+    async fn broadcast_evidence(
+        &self,
+        evidence: Evidence,
+    ) -> Result<evidence::Response, Error> {
+        let evidence = &evidence;
+        self.call(None, |client| async move {
+            client.broadcast_evidence(evidence.clone()).await
+        })
+        .await
+    }
+
+    async fn tx(&self, hash: Hash, prove: bool) -> Result<tx::Response, Error> {
+        self.call(None, |client| async move { client.tx(hash, prove).await })
+            .await
+    }
+
+    async fn tx_search(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<tx_search::Response, Error> {
+        let (query, order) = (&query, &order);
+        self.call(None, |client| async move {
+            client
+                .tx_search(query.clone(), prove, page, per_page, order.clone())
+                .await
+        })
+        .await
+    }
+
+    async fn broadcast_tx_commit<T>(
+        &self,
+        tx: T,
+    ) -> Result<broadcast::tx_commit::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        let tx: Vec<u8> = tx.into();
+        let tx = &tx;
+        self.call(None, |client| async move {
+            client.broadcast_tx_commit(tx.clone()).await
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Starts a JSON-RPC server answering every request with the given
+    /// status and body
+    async fn mock_rpc_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    break;
+                };
+                let mut buffer = vec![0; 4096];
+                let _ = socket.read(&mut buffer).await;
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: \
+                     application/json\r\nContent-Length: {}\r\nConnection: \
+                     close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        format!("http://{address}")
+    }
+
+    async fn healthy_node() -> String {
+        mock_rpc_server("200 OK", r#"{"jsonrpc":"2.0","id":"","result":{}}"#)
+            .await
+    }
+
+    async fn failing_node() -> String {
+        mock_rpc_server("500 Internal Server Error", "").await
+    }
+
+    async fn rejecting_node() -> String {
+        mock_rpc_server("400 Bad Request", "").await
+    }
+
+    #[tokio::test]
+    async fn test_failover_to_healthy_node() {
+        let urls = vec![failing_node().await, healthy_node().await];
+        let client = RpcClient::new(&urls).unwrap();
+
+        let result = client.health().await;
+
+        assert!(result.is_ok());
+        assert_eq!(client.nodes[0].health().consecutive_failures, 1);
+        assert_eq!(client.nodes[1].health().consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_prefers_node_without_failures() {
+        let urls = vec![failing_node().await, healthy_node().await];
+        let client = RpcClient::new(&urls).unwrap();
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            client.health().await.unwrap();
+        }
+
+        let ordered_nodes = client.ordered_nodes(None);
+        assert_eq!(ordered_nodes[0].url, urls[1]);
+        assert_eq!(client.nodes[0].health().consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn test_all_nodes_failing() {
+        let urls = vec![failing_node().await, failing_node().await];
+        let client = RpcClient::new(&urls).unwrap();
+
+        let result = client.health().await;
+
+        assert!(result.is_err());
+        assert!(
+            client
+                .nodes
+                .iter()
+                .all(|node| node.health().consecutive_failures == 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_failover_on_rejected_request() {
+        let urls = vec![rejecting_node().await, healthy_node().await];
+        let client = RpcClient::new(&urls).unwrap();
+
+        let result = client.health().await;
+
+        assert!(result.is_err());
+        assert_eq!(client.nodes[0].health().consecutive_failures, 0);
+        assert_eq!(client.nodes[1].health().latency, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_no_failover_on_response_error() {
+        let urls = vec![
+            mock_rpc_server(
+                "200 OK",
+                r#"{"jsonrpc":"2.0","id":"","error":{"code":-32603,"message":"Internal error","data":"height is not available"}}"#,
+            )
+            .await,
+            healthy_node().await,
+        ];
+        let client = RpcClient::new(&urls).unwrap();
+
+        let result = client.health().await;
+
+        assert!(result.is_err());
+        assert_eq!(client.nodes[0].health().consecutive_failures, 0);
+        assert_eq!(client.nodes[1].health().latency, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_preferred_url_after_failover() {
+        let urls = vec![failing_node().await, healthy_node().await];
+        let client = RpcClient::new(&urls).unwrap();
+
+        assert_eq!(client.preferred_url(), urls[0]);
+
+        client.health().await.unwrap();
+
+        assert_eq!(client.preferred_url(), urls[1]);
+    }
+
+    #[test]
+    fn test_prefers_node_storing_height() {
+        let urls = vec![
+            "http://127.0.0.1:26657".to_string(),
+            "http://127.0.0.1:26658".to_string(),
+        ];
+        let client = RpcClient::new(&urls).unwrap();
+        client.nodes[0].update_health(|health| {
+            health.earliest_block_height = Some(1000);
+            health.latency = 10.0;
+        });
+        client.nodes[1].update_health(|health| {
+            health.earliest_block_height = Some(1);
+            health.latency = 100.0;
+        });
+
+        assert_eq!(client.ordered_nodes(Some(10))[0].url, urls[1]);
+        assert_eq!(client.ordered_nodes(Some(2000))[0].url, urls[0]);
+        assert_eq!(client.ordered_nodes(None)[0].url, urls[0]);
+    }
+
+    #[test]
+    fn test_new_without_urls() {
+        assert!(RpcClient::new(&[]).is_err());
+    }
+}
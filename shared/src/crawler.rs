@@ -11,6 +11,7 @@ use tokio_retry::RetryIf;
 use tokio_retry::strategy::{FixedInterval, jitter};
 
 use crate::error::MainError;
+use crate::metrics;
 
 fn indexes(from: u32, to: Option<u32>) -> impl Stream<Item = u32> {
     stream! {
@@ -39,10 +40,10 @@ where
         if must_exit.load(atomic::Ordering::Relaxed) {
             break;
         }
-        _ = RetryIf::spawn(
+        let result = RetryIf::spawn(
             retry_strategy.clone(),
             || async {
-                f(index).await?;
+                f(index).await.inspect_err(metrics::record_error)?;
                 Ok(())
             },
             |e: &MainError| {
@@ -53,6 +54,11 @@ where
             },
         )
         .await;
+
+        // Data could be corrupted, stop until an operator looks into it
+        if let Err(MainError::BlockMismatch) = result {
+            return result;
+        }
     }
 
     Ok(())
@@ -1,3 +1,5 @@
+use std::fmt::{self, Display, Formatter};
+
 use crate::block::{BlockHeight, Epoch};
 
 pub enum CrawlerName {
@@ -9,6 +11,19 @@ pub enum CrawlerName {
     Transactions,
 }
 
+impl Display for CrawlerName {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Chain => f.write_str("chain"),
+            Self::Governance => f.write_str("governance"),
+            Self::Parameters => f.write_str("parameters"),
+            Self::Pos => f.write_str("pos"),
+            Self::Rewards => f.write_str("rewards"),
+            Self::Transactions => f.write_str("transactions"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ChainCrawlerState {
     pub last_processed_block: BlockHeight,
@@ -17,7 +32,7 @@ pub struct ChainCrawlerState {
     pub timestamp: i64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BlockCrawlerState {
     pub last_processed_block: BlockHeight,
     pub timestamp: i64,
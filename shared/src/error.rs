@@ -10,6 +10,21 @@ pub enum MainError {
     Database,
     #[error("Failed to join async task")]
     TaskJoinError,
+    #[error("Indexed block doesn't match the block returned by the RPC")]
+    BlockMismatch,
+}
+
+impl MainError {
+    /// Short name used to label metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MainError::NoAction => "no_action",
+            MainError::RpcError => "rpc",
+            MainError::Database => "database",
+            MainError::TaskJoinError => "task_join",
+            MainError::BlockMismatch => "block_mismatch",
+        }
+    }
 }
 
 pub trait AsRpcError<T> {
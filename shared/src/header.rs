@@ -8,4 +8,5 @@ pub struct BlockHeader {
     pub proposer_address_namada: Option<String>,
     pub timestamp: i64,
     pub app_hash: Id,
+    pub parent_hash: Option<Id>,
 }
@@ -0,0 +1,40 @@
+use namada_ibc::core::channel::types::channel::State as NamadaChannelState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IbcChannelState {
+    Uninitialized,
+    Init,
+    TryOpen,
+    Open,
+    Closed,
+}
+
+impl From<&NamadaChannelState> for IbcChannelState {
+    fn from(state: &NamadaChannelState) -> Self {
+        match state {
+            NamadaChannelState::Uninitialized => Self::Uninitialized,
+            NamadaChannelState::Init => Self::Init,
+            NamadaChannelState::TryOpen => Self::TryOpen,
+            NamadaChannelState::Open => Self::Open,
+            NamadaChannelState::Closed => Self::Closed,
+        }
+    }
+}
+
+/// A channel of namada, with the connection and client it is built on and the
+/// chain on the other end
+#[derive(Debug, Clone)]
+pub struct IbcChannel {
+    pub port_id: String,
+    pub channel_id: String,
+    pub state: IbcChannelState,
+    pub counterparty_port_id: String,
+    /// Not known until the counterparty chain answers the channel handshake
+    pub counterparty_channel_id: Option<String>,
+    pub connection_id: String,
+    pub counterparty_connection_id: Option<String>,
+    pub client_id: String,
+    pub counterparty_client_id: String,
+    /// Only known for tendermint light clients
+    pub counterparty_chain_id: Option<String>,
+}
@@ -1,8 +1,10 @@
+pub mod account;
 pub mod balance;
 pub mod block;
 pub mod block_result;
 pub mod bond;
 pub mod checksums;
+pub mod client;
 pub mod crawler;
 pub mod crawler_state;
 pub mod error;
@@ -10,9 +12,11 @@ pub mod futures;
 pub mod gas;
 pub mod genesis;
 pub mod header;
+pub mod ibc;
 pub mod id;
 pub mod log_config;
 pub mod masp;
+pub mod metrics;
 pub mod parameters;
 pub mod pgf;
 pub mod proposal;
@@ -0,0 +1,102 @@
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+use crate::crawler_state::CrawlerName;
+use crate::error::MainError;
+
+const LAST_PROCESSED_INDEX: &str = "crawler_last_processed_index";
+const CHAIN_TIP: &str = "crawler_chain_tip";
+const LAG: &str = "crawler_lag";
+const STAGE_DURATION: &str = "crawler_stage_duration_seconds";
+const ERRORS: &str = "crawler_errors_total";
+const INSERTED_ROWS: &str = "crawler_inserted_rows_total";
+
+/// Kept around to compute the lag whenever one of them changes
+static LAST_PROCESSED: AtomicU64 = AtomicU64::new(0);
+static TIP: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Copy, Debug)]
+pub enum Stage {
+    /// Querying the data from the RPC node
+    Rpc,
+    /// Committing the data to the database
+    Db,
+}
+
+impl Stage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Stage::Rpc => "rpc",
+            Stage::Db => "db",
+        }
+    }
+}
+
+#[derive(clap::Parser, Clone)]
+pub struct MetricsConfig {
+    #[clap(
+        long,
+        env,
+        default_value = "0.0.0.0:9100",
+        help = "Address serving the prometheus metrics of the crawler"
+    )]
+    pub metrics_address: SocketAddr,
+}
+
+impl MetricsConfig {
+    /// Serves the metrics on `/metrics`, every metric is labelled with the
+    /// crawler name
+    pub fn init(&self, crawler: CrawlerName) {
+        let result = PrometheusBuilder::new()
+            .with_http_listener(self.metrics_address)
+            .add_global_label("crawler", crawler.to_string())
+            .install();
+
+        // Metrics are not worth stopping the crawler for
+        if let Err(e) = result {
+            tracing::warn!("Failed to start the metrics endpoint: {}", e);
+        }
+    }
+}
+
+/// Last block height or epoch successfully processed. Not recorded by the
+/// interval crawlers, which have no height or epoch to catch up with.
+pub fn record_last_processed(index: u64) {
+    LAST_PROCESSED.store(index, Ordering::Relaxed);
+    metrics::gauge!(LAST_PROCESSED_INDEX).set(index as f64);
+    update_lag();
+}
+
+/// Latest block height or epoch of the node
+pub fn record_chain_tip(index: u64) {
+    TIP.store(index, Ordering::Relaxed);
+    metrics::gauge!(CHAIN_TIP).set(index as f64);
+    update_lag();
+}
+
+pub fn record_stage_duration(stage: Stage, duration: Duration) {
+    metrics::histogram!(STAGE_DURATION, "stage" => stage.as_str())
+        .record(duration.as_secs_f64());
+}
+
+pub fn record_error(error: &MainError) {
+    // Nothing to process yet, not an error
+    if let MainError::NoAction = error {
+        return;
+    }
+    metrics::counter!(ERRORS, "error" => error.kind()).increment(1);
+}
+
+pub fn record_inserted_rows(table: &'static str, rows: usize) {
+    metrics::counter!(INSERTED_ROWS, "table" => table).increment(rows as u64);
+}
+
+fn update_lag() {
+    let tip = TIP.load(Ordering::Relaxed);
+    let last_processed = LAST_PROCESSED.load(Ordering::Relaxed);
+
+    metrics::gauge!(LAG).set(tip.saturating_sub(last_processed) as f64);
+}
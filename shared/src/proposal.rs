@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 
 use fake::Fake;
+use namada_core::hash::Hash;
 use namada_governance::ProposalType;
 use namada_governance::storage::proposal::{
     AddRemove, PGFAction, PGFIbcTarget, PGFInternalTarget, PGFTarget,
@@ -12,7 +13,9 @@ use namada_sdk::token::Amount;
 use rand::distributions::{Distribution, Standard};
 use subtle_encoding::hex;
 
-use crate::block::Epoch;
+use crate::block::{BlockHeight, Epoch};
+use crate::block_result::ProposalEnded;
+use crate::checksums::Checksums;
 use crate::id::Id;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -52,6 +55,38 @@ pub struct GovernanceProposal {
     pub voting_start_epoch: Epoch,
     pub voting_end_epoch: Epoch,
     pub activation_epoch: Epoch,
+    /// The init proposal inner transaction, when the proposal was indexed
+    /// from a block
+    pub inner_tx_id: Option<Id>,
+    pub wasm_code: Option<ProposalWasmCode>,
+}
+
+/// The wasm code of a default with wasm proposal, identified by its sha256
+/// hash and, when it is a known transaction code, by its name
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProposalWasmCode {
+    pub hash: String,
+    pub size: u64,
+    pub name: Option<String>,
+}
+
+impl ProposalWasmCode {
+    pub fn new(code: &[u8], checksums: &Checksums) -> Self {
+        let hash = Hash::sha256(code).to_string().to_lowercase();
+        // Names are stored without extension, whichever way the checksums
+        // were loaded
+        let name = checksums.get_name_by_id(&hash).map(|name| {
+            name.strip_suffix(".wasm")
+                .map(str::to_owned)
+                .unwrap_or(name)
+        });
+
+        Self {
+            hash,
+            size: code.len() as u64,
+            name,
+        }
+    }
 }
 
 impl From<StorageProposal> for GovernanceProposal {
@@ -68,6 +103,8 @@ impl From<StorageProposal> for GovernanceProposal {
             voting_start_epoch: proposal.voting_start_epoch.0 as Epoch,
             voting_end_epoch: proposal.voting_end_epoch.0 as Epoch,
             activation_epoch: proposal.activation_epoch.0 as Epoch,
+            inner_tx_id: None,
+            wasm_code: None,
         }
     }
 }
@@ -144,6 +181,8 @@ impl GovernanceProposal {
             voting_start_epoch,
             voting_end_epoch,
             activation_epoch,
+            inner_tx_id: None,
+            wasm_code: None,
             content: "Lorem ipsum dolor sit amet, consectetur adipiscing \
                       elit. Nullam purus tellus, mollis in nisi sed, laoreet \
                       scelerisque ante. Mauris at odio in magna ullamcorper \
@@ -192,6 +231,141 @@ impl GovernanceProposalStatus {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GovernanceProposalEventKind {
+    Created,
+    VotingStarted,
+    Tally,
+    Ended,
+    Activated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GovernanceProposalEvent {
+    pub proposal_id: u64,
+    pub kind: GovernanceProposalEventKind,
+    pub epoch: Epoch,
+    pub block_height: Option<BlockHeight>,
+    pub inner_tx_id: Option<Id>,
+    pub result: Option<GovernanceProposalResult>,
+    pub yay_votes: Option<String>,
+    pub nay_votes: Option<String>,
+    pub abstain_votes: Option<String>,
+    pub has_proposal_code: Option<bool>,
+    pub proposal_code_succeeded: Option<bool>,
+}
+
+impl GovernanceProposalEvent {
+    fn new(
+        proposal_id: u64,
+        kind: GovernanceProposalEventKind,
+        epoch: Epoch,
+    ) -> Self {
+        Self {
+            proposal_id,
+            kind,
+            epoch,
+            block_height: None,
+            inner_tx_id: None,
+            result: None,
+            yay_votes: None,
+            nay_votes: None,
+            abstain_votes: None,
+            has_proposal_code: None,
+            proposal_code_succeeded: None,
+        }
+    }
+
+    pub fn created(
+        proposal: &GovernanceProposal,
+        epoch: Epoch,
+        block_height: BlockHeight,
+    ) -> Self {
+        Self {
+            block_height: Some(block_height),
+            inner_tx_id: proposal.inner_tx_id.clone(),
+            ..Self::new(
+                proposal.id,
+                GovernanceProposalEventKind::Created,
+                epoch,
+            )
+        }
+    }
+
+    /// The creation of a proposal indexed from the chain state instead of its
+    /// init transaction. The creation block is unknown, so the event is
+    /// recorded at the latest epoch the proposal could have been created at.
+    pub fn indexed(proposal: &GovernanceProposal, epoch: Epoch) -> Self {
+        Self::new(
+            proposal.id,
+            GovernanceProposalEventKind::Created,
+            epoch.min(proposal.voting_start_epoch.saturating_sub(1)),
+        )
+    }
+
+    /// The events a status update of a proposal contributes to its timeline:
+    /// the start of the voting period and a tally snapshot while voting, or
+    /// the final tally once voting ended.
+    pub fn from_status(
+        status: &GovernanceProposalStatus,
+        voting_start_epoch: Epoch,
+        voting_end_epoch: Epoch,
+        epoch: Epoch,
+    ) -> Vec<Self> {
+        let with_tally = |kind, epoch| Self {
+            result: Some(status.result.clone()),
+            yay_votes: Some(status.yay_votes.clone()),
+            nay_votes: Some(status.nay_votes.clone()),
+            abstain_votes: Some(status.abstain_votes.clone()),
+            ..Self::new(status.id, kind, epoch)
+        };
+
+        match status.result {
+            GovernanceProposalResult::Pending => vec![],
+            GovernanceProposalResult::VotingPeriod => vec![
+                Self::new(
+                    status.id,
+                    GovernanceProposalEventKind::VotingStarted,
+                    voting_start_epoch,
+                ),
+                with_tally(GovernanceProposalEventKind::Tally, epoch),
+            ],
+            GovernanceProposalResult::Passed
+            | GovernanceProposalResult::Rejected => vec![with_tally(
+                GovernanceProposalEventKind::Ended,
+                voting_end_epoch,
+            )],
+        }
+    }
+
+    pub fn activated(
+        proposal: &ProposalEnded,
+        epoch: Epoch,
+        block_height: BlockHeight,
+    ) -> Self {
+        let result = if proposal.passed {
+            GovernanceProposalResult::Passed
+        } else {
+            GovernanceProposalResult::Rejected
+        };
+
+        Self {
+            block_height: Some(block_height),
+            result: Some(result),
+            has_proposal_code: Some(proposal.has_proposal_code),
+            // The ledger only runs the proposal code of passed proposals
+            proposal_code_succeeded: (proposal.has_proposal_code
+                && proposal.passed)
+                .then_some(proposal.proposal_code_exit_status),
+            ..Self::new(
+                proposal.proposal_id,
+                GovernanceProposalEventKind::Activated,
+                epoch,
+            )
+        }
+    }
+}
+
 impl Distribution<GovernanceProposalKind> for Standard {
     fn sample<R: rand::prelude::Rng + ?Sized>(
         &self,
@@ -278,3 +452,169 @@ impl Distribution<TallyType> for Standard {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(result: GovernanceProposalResult) -> GovernanceProposalStatus {
+        GovernanceProposalStatus {
+            id: 7,
+            result,
+            yay_votes: "100".to_string(),
+            nay_votes: "20".to_string(),
+            abstain_votes: "3".to_string(),
+        }
+    }
+
+    fn ended(
+        passed: bool,
+        has_proposal_code: bool,
+        proposal_code_exit_status: bool,
+    ) -> ProposalEnded {
+        ProposalEnded {
+            proposal_id: 7,
+            passed,
+            has_proposal_code,
+            proposal_code_exit_status,
+        }
+    }
+
+    #[test]
+    fn wasm_code_hash_and_size() {
+        let code = b"\0asm proposal code";
+        let wasm_code = ProposalWasmCode::new(code, &Checksums::default());
+
+        assert_eq!(
+            wasm_code.hash,
+            Hash::sha256(code).to_string().to_lowercase()
+        );
+        assert_eq!(wasm_code.hash.len(), 64);
+        assert_eq!(wasm_code.size, code.len() as u64);
+        assert_eq!(wasm_code.name, None);
+    }
+
+    #[test]
+    fn wasm_code_name_lookup() {
+        let code = b"\0asm tx code";
+        let hash = Hash::sha256(code).to_string().to_lowercase();
+
+        let mut checksums = Checksums::default();
+        checksums.add("tx_bond.wasm".to_string(), hash.clone());
+        let wasm_code = ProposalWasmCode::new(code, &checksums);
+        assert_eq!(wasm_code.name.as_deref(), Some("tx_bond"));
+
+        let mut checksums = Checksums::default();
+        checksums.add_with_ext("tx_bond.wasm".to_string(), hash);
+        let wasm_code = ProposalWasmCode::new(code, &checksums);
+        assert_eq!(wasm_code.name.as_deref(), Some("tx_bond"));
+    }
+
+    #[test]
+    fn from_status_pending() {
+        let events = GovernanceProposalEvent::from_status(
+            &status(GovernanceProposalResult::Pending),
+            10,
+            20,
+            5,
+        );
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn from_status_voting_period() {
+        let events = GovernanceProposalEvent::from_status(
+            &status(GovernanceProposalResult::VotingPeriod),
+            10,
+            20,
+            12,
+        );
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, GovernanceProposalEventKind::VotingStarted);
+        assert_eq!(events[0].epoch, 10);
+        assert_eq!(events[0].result, None);
+        assert_eq!(events[1].kind, GovernanceProposalEventKind::Tally);
+        assert_eq!(events[1].epoch, 12);
+        assert_eq!(
+            events[1].result,
+            Some(GovernanceProposalResult::VotingPeriod)
+        );
+        assert_eq!(events[1].yay_votes.as_deref(), Some("100"));
+        assert_eq!(events[1].nay_votes.as_deref(), Some("20"));
+        assert_eq!(events[1].abstain_votes.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn from_status_ended() {
+        for result in [
+            GovernanceProposalResult::Passed,
+            GovernanceProposalResult::Rejected,
+        ] {
+            let events = GovernanceProposalEvent::from_status(
+                &status(result.clone()),
+                10,
+                20,
+                25,
+            );
+
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].proposal_id, 7);
+            assert_eq!(events[0].kind, GovernanceProposalEventKind::Ended);
+            assert_eq!(events[0].epoch, 20);
+            assert_eq!(events[0].result, Some(result));
+        }
+    }
+
+    #[test]
+    fn activated_passed_with_code() {
+        let event = GovernanceProposalEvent::activated(
+            &ended(true, true, false),
+            30,
+            42,
+        );
+
+        assert_eq!(event.kind, GovernanceProposalEventKind::Activated);
+        assert_eq!(event.epoch, 30);
+        assert_eq!(event.block_height, Some(42));
+        assert_eq!(event.result, Some(GovernanceProposalResult::Passed));
+        assert_eq!(event.has_proposal_code, Some(true));
+        assert_eq!(event.proposal_code_succeeded, Some(false));
+    }
+
+    #[test]
+    fn activated_without_code_run() {
+        let rejected = GovernanceProposalEvent::activated(
+            &ended(false, true, false),
+            30,
+            42,
+        );
+        assert_eq!(rejected.result, Some(GovernanceProposalResult::Rejected));
+        assert_eq!(rejected.has_proposal_code, Some(true));
+        assert_eq!(rejected.proposal_code_succeeded, None);
+
+        let without_code = GovernanceProposalEvent::activated(
+            &ended(true, false, false),
+            30,
+            42,
+        );
+        assert_eq!(without_code.result, Some(GovernanceProposalResult::Passed));
+        assert_eq!(without_code.has_proposal_code, Some(false));
+        assert_eq!(without_code.proposal_code_succeeded, None);
+    }
+
+    #[test]
+    fn indexed_before_voting_start() {
+        let mut proposal = GovernanceProposal::fake(7);
+        proposal.voting_start_epoch = 10;
+
+        let running = GovernanceProposalEvent::indexed(&proposal, 15);
+        assert_eq!(running.kind, GovernanceProposalEventKind::Created);
+        assert_eq!(running.epoch, 9);
+        assert_eq!(running.block_height, None);
+
+        let pending = GovernanceProposalEvent::indexed(&proposal, 4);
+        assert_eq!(pending.epoch, 4);
+    }
+}
@@ -8,6 +8,14 @@ pub struct Reward {
     pub amount: Amount,
 }
 
+/// A successful claim of the rewards of a delegation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RewardClaim {
+    pub delegation_pair: DelegationPair,
+    pub inner_tx_id: Id,
+    pub amount: Amount,
+}
+
 impl Reward {
     pub fn fake(validator_id: u64) -> Self {
         let delegator_address =
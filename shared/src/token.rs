@@ -1,8 +1,10 @@
 use std::fmt::Display;
 
 use bigdecimal::BigDecimal;
+use namada_sdk::token::NATIVE_MAX_DECIMAL_PLACES;
 use serde::Serialize;
 
+use crate::balance::Denomination;
 use crate::id::Id;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
@@ -26,6 +28,17 @@ impl Display for Token {
     }
 }
 
+impl Token {
+    /// Denomination of the token on Namada. IBC tokens default to a zero
+    /// denomination, so their raw amounts are already in the base unit.
+    pub fn denomination(&self) -> Denomination {
+        match self {
+            Token::Ibc(_) => 0,
+            Token::Native(_) => NATIVE_MAX_DECIMAL_PLACES,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct IbcRateLimit {
     /// Address of the token in Namada
@@ -3,22 +3,29 @@ use std::fmt::Display;
 
 use anyhow::Context;
 use bigdecimal::BigDecimal;
+use namada_core::eth_bridge_pool::PendingTransfer;
+use namada_core::token::Amount as NamadaAmount;
 use namada_governance::{InitProposalData, VoteProposalData};
+use namada_ibc::apps::transfer::types::packet::PacketData as Ics20PacketData;
+use namada_sdk::account::{InitAccount, UpdateAccount};
 use namada_sdk::address::Address;
 use namada_sdk::borsh::BorshDeserialize;
 use namada_sdk::events::extend::MaspTxRef;
+use namada_sdk::ibc::core::channel::types::packet::Packet as IbcCorePacket;
 use namada_sdk::key::common::PublicKey;
 use namada_sdk::token::Transfer;
 use namada_sdk::uint::Uint;
+use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::pos::{
-    BecomeValidator, Bond, ClaimRewards, CommissionChange, MetaDataChange,
-    Redelegation, Unbond, Withdraw,
+    BecomeValidator, Bond, ClaimRewards, CommissionChange, ConsensusKeyChange,
+    MetaDataChange, Redelegation, Unbond, Withdraw,
 };
 use namada_tx::data::{TxType, compute_inner_tx_hash};
 use namada_tx::either::Either;
 use namada_tx::{Section, Tx};
 use serde::Serialize;
 
+use crate::balance::Amount;
 use crate::block::BlockHeight;
 use crate::block_result::{BlockResult, TxEventStatusCode};
 use crate::checksums::Checksums;
@@ -93,6 +100,12 @@ pub enum TransactionKind {
     ReactivateValidator(Option<Address>),
     DeactivateValidator(Option<Address>),
     UnjailValidator(Option<Address>),
+    InitAccount(Option<InitAccount>),
+    UpdateAccount(Option<UpdateAccount>),
+    ChangeConsensusKey(Option<ConsensusKeyChange>),
+    ResignSteward(Option<Address>),
+    UpdateStewardCommission(Option<UpdateStewardCommission>),
+    BridgePoolTransfer(Option<PendingTransfer>),
     Unknown(Option<UnknownTransaction>),
 }
 
@@ -248,6 +261,60 @@ impl TransactionKind {
                     };
                 TransactionKind::BecomeValidator(data.map(Box::new))
             }
+            "tx_init_account" => {
+                let data = if let Ok(data) = InitAccount::try_from_slice(data) {
+                    Some(data)
+                } else {
+                    None
+                };
+                TransactionKind::InitAccount(data)
+            }
+            "tx_update_account" => {
+                let data = if let Ok(data) = UpdateAccount::try_from_slice(data)
+                {
+                    Some(data)
+                } else {
+                    None
+                };
+                TransactionKind::UpdateAccount(data)
+            }
+            "tx_change_consensus_key" => {
+                let data = if let Ok(data) =
+                    ConsensusKeyChange::try_from_slice(data)
+                {
+                    Some(data)
+                } else {
+                    None
+                };
+                TransactionKind::ChangeConsensusKey(data)
+            }
+            "tx_resign_steward" => {
+                let data = if let Ok(data) = Address::try_from_slice(data) {
+                    Some(data)
+                } else {
+                    None
+                };
+                TransactionKind::ResignSteward(data)
+            }
+            "tx_update_steward_commission" => {
+                let data = if let Ok(data) =
+                    UpdateStewardCommission::try_from_slice(data)
+                {
+                    Some(data)
+                } else {
+                    None
+                };
+                TransactionKind::UpdateStewardCommission(data)
+            }
+            "tx_bridge_pool" => {
+                let data =
+                    if let Ok(data) = PendingTransfer::try_from_slice(data) {
+                        Some(data)
+                    } else {
+                        None
+                    };
+                TransactionKind::BridgePoolTransfer(data)
+            }
             _ => {
                 tracing::warn!("Unknown transaction kind: {}", tx_kind_name);
                 TransactionKind::Unknown(Some(UnknownTransaction {
@@ -322,6 +389,7 @@ pub struct InnerTransaction {
     pub extra_sections: HashMap<Id, Vec<u8>>,
     pub notes: u64,
     pub exit_code: TransactionExitStatus,
+    pub initialized_accounts: Vec<Id>,
 }
 
 impl InnerTransaction {
@@ -475,6 +543,8 @@ impl Transaction {
 
                     let inner_tx_status = block_results
                         .is_inner_tx_accepted(&wrapper_tx_id, &inner_tx_id);
+                    let initialized_accounts = block_results
+                        .initialized_accounts(&wrapper_tx_id, &inner_tx_id);
 
                     let extra_sections = transaction
                         .sections
@@ -582,6 +652,7 @@ impl Transaction {
                         notes,
                         exit_code: inner_tx_status,
                         kind: tx_kind,
+                        initialized_accounts,
                     };
 
                     inner_txs.push(inner_tx);
@@ -640,18 +711,25 @@ fn extract_masp_transaction(
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct IbcSequence {
-    pub sequence_number: String,
+/// Side of the channel namada is on for an IBC packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IbcPacketDirection {
+    /// Sent by namada to the counterparty chain
+    Outgoing,
+    /// Sent by the counterparty chain to namada
+    Incoming,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IbcPacketKey {
     pub source_port: String,
-    pub dest_port: String,
     pub source_channel: String,
+    pub dest_port: String,
     pub dest_channel: String,
-    pub timeout: u64,
-    pub tx_id: Id,
+    pub sequence: u64,
 }
 
-impl IbcSequence {
+impl IbcPacketKey {
     pub fn id(&self) -> String {
         format!(
             "{}/{}/{}/{}/{}",
@@ -659,54 +737,72 @@ impl IbcSequence {
             self.dest_channel,
             self.source_port,
             self.source_channel,
-            self.sequence_number
+            self.sequence
         )
     }
 }
 
-#[derive(Debug, Clone)]
-pub enum IbcAckStatus {
-    Success,
-    Fail,
-    Timeout,
-    Unknown,
+impl From<&IbcCorePacket> for IbcPacketKey {
+    fn from(packet: &IbcCorePacket) -> Self {
+        Self {
+            source_port: packet.port_id_on_a.to_string(),
+            source_channel: packet.chan_id_on_a.to_string(),
+            dest_port: packet.port_id_on_b.to_string(),
+            dest_channel: packet.chan_id_on_b.to_string(),
+            sequence: u64::from(packet.seq_on_a),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct IbcAck {
-    pub sequence_number: String,
-    pub source_port: String,
-    pub dest_port: String,
-    pub source_channel: String,
-    pub dest_channel: String,
-    pub status: IbcAckStatus,
+/// ICS20 transfer carried by the data of an IBC packet
+#[derive(Debug, Clone, PartialEq)]
+pub struct IbcPacketTransfer {
+    pub denom: String,
+    pub amount: BigDecimal,
+    pub sender: String,
+    pub receiver: String,
 }
 
-impl IbcAck {
-    pub fn id_source(&self) -> String {
-        format!(
-            "{}/{}/{}",
-            self.source_port, self.source_channel, self.sequence_number
-        )
-    }
-
-    pub fn id_dest(&self) -> String {
-        format!(
-            "{}/{}/{}",
-            self.dest_port, self.dest_channel, self.sequence_number
-        )
+impl IbcPacketTransfer {
+    /// Returns `None` for packets of applications other than ICS20
+    pub fn from_packet_data(data: &[u8]) -> Option<Self> {
+        let packet_data: Ics20PacketData = serde_json::from_slice(data).ok()?;
+        let amount: NamadaAmount = packet_data.token.amount.try_into().ok()?;
+
+        Some(Self {
+            denom: packet_data.token.denom.to_string(),
+            amount: Amount::from(amount).into(),
+            sender: packet_data.sender.to_string(),
+            receiver: packet_data.receiver.to_string(),
+        })
     }
+}
 
-    pub fn id(&self) -> String {
-        format!(
-            "{}/{}/{}/{}/{}",
-            self.dest_port,
-            self.dest_channel,
-            self.source_port,
-            self.source_channel,
-            self.sequence_number
-        )
-    }
+#[derive(Debug, Clone)]
+pub enum IbcPacketEvent {
+    /// A packet sent by namada
+    Sent {
+        key: IbcPacketKey,
+        transfer: Option<IbcPacketTransfer>,
+        timeout_timestamp: u64,
+        tx_id: Id,
+    },
+    /// A packet of the counterparty chain relayed to namada
+    Received {
+        key: IbcPacketKey,
+        transfer: Option<IbcPacketTransfer>,
+        timeout_timestamp: u64,
+        tx_id: Id,
+    },
+    /// The acknowledgement of a packet sent by namada, relayed back from the
+    /// counterparty chain. Error acknowledgements refund the sender.
+    Acknowledged {
+        key: IbcPacketKey,
+        success: bool,
+        tx_id: Id,
+    },
+    /// The timeout of a packet sent by namada, which refunds the sender
+    TimedOut { key: IbcPacketKey, tx_id: Id },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -807,3 +903,85 @@ impl IbcTokenFlow {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::gen_established_address;
+    use namada_sdk::borsh::BorshSerializeExt;
+
+    use super::*;
+
+    #[test]
+    fn test_ibc_packet_transfer_from_packet_data() {
+        let data = br#"{"amount":"1000000","denom":"transfer/channel-1/uosmo","receiver":"tnam1qq0zyq3y8yrlmq5hkufn7ktgfwc3qsc2gyzuh0pk","sender":"osmo1t6pyrf6zk9xj6nuq9tzn0s7wm0kpn2zj7zs5ae","memo":""}"#;
+
+        let transfer = IbcPacketTransfer::from_packet_data(data).unwrap();
+
+        assert_eq!(transfer.denom, "transfer/channel-1/uosmo");
+        assert_eq!(transfer.amount, BigDecimal::from(1000000));
+        assert_eq!(
+            transfer.sender,
+            "osmo1t6pyrf6zk9xj6nuq9tzn0s7wm0kpn2zj7zs5ae"
+        );
+        assert_eq!(
+            transfer.receiver,
+            "tnam1qq0zyq3y8yrlmq5hkufn7ktgfwc3qsc2gyzuh0pk"
+        );
+
+        assert!(IbcPacketTransfer::from_packet_data(b"{}").is_none());
+    }
+
+    #[test]
+    fn test_decode_resign_steward() {
+        let steward = gen_established_address("namada-indexer");
+
+        let kind = TransactionKind::from(
+            "tx_id",
+            "tx_resign_steward",
+            &steward.serialize_to_vec(),
+            gen_established_address("native-token"),
+        );
+
+        assert!(
+            matches!(kind, TransactionKind::ResignSteward(Some(address)) if address == steward)
+        );
+    }
+
+    #[test]
+    fn test_decode_update_steward_commission() {
+        let steward = gen_established_address("namada-indexer");
+        let target = gen_established_address("pgf-target");
+        let data = UpdateStewardCommission {
+            steward: steward.clone(),
+            commission: [(target.clone(), Default::default())]
+                .into_iter()
+                .collect(),
+        };
+
+        let kind = TransactionKind::from(
+            "tx_id",
+            "tx_update_steward_commission",
+            &data.serialize_to_vec(),
+            gen_established_address("native-token"),
+        );
+
+        let TransactionKind::UpdateStewardCommission(Some(decoded)) = kind
+        else {
+            panic!("Expected an update steward commission transaction");
+        };
+        assert_eq!(decoded.steward, steward);
+        assert!(decoded.commission.contains_key(&target));
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        let kind = TransactionKind::from(
+            "tx_id",
+            "tx_change_consensus_key",
+            &[1, 2, 3],
+            gen_established_address("native-token"),
+        );
+
+        assert!(matches!(kind, TransactionKind::ChangeConsensusKey(None)));
+    }
+}
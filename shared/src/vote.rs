@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
 use namada_governance::ProposalVote;
+use namada_governance::utils::ProposalVotes;
 use rand::distributions::{Distribution, Standard};
 use serde::{Deserialize, Serialize};
 
+use crate::balance::Amount;
 use crate::id::Id;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Hash, PartialEq, Eq)]
@@ -43,6 +47,102 @@ impl GovernanceVote {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GovernanceVotePower {
+    pub proposal_id: u64,
+    pub address: Id,
+    /// The voting power counted towards the tally for this voter
+    pub voting_power: Amount,
+    pub is_validator: bool,
+    /// Whether the delegator voted on a different side than at least one of
+    /// the validators it is bonded to
+    pub overrides_validator: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct GovernanceProposalVotingPower {
+    pub proposal_id: u64,
+    pub total_voting_power: Amount,
+    pub votes: Vec<GovernanceVotePower>,
+}
+
+impl GovernanceVotePower {
+    /// Splits the proposal votes into the voting power each voter contributes
+    /// to the tally, the same way the ledger tallies them: a delegator on the
+    /// same side as its validator is already counted within the validator
+    /// stake, while a delegator on a different side moves its bond away from
+    /// the validator vote.
+    pub fn from_proposal_votes(
+        proposal_id: u64,
+        votes: &ProposalVotes,
+    ) -> Vec<Self> {
+        let mut validators_power = votes
+            .validator_voting_power
+            .iter()
+            .map(|(validator, power)| (validator.clone(), Amount::from(*power)))
+            .collect::<HashMap<_, _>>();
+
+        let mut delegators = Vec::new();
+
+        for (delegator, delegations) in &votes.delegator_voting_power {
+            let Some(delegator_vote) = votes.delegators_vote.get(delegator)
+            else {
+                continue;
+            };
+
+            let mut voting_power = Amount::zero();
+            let mut overrides_validator = false;
+
+            for (validator, power) in delegations {
+                let power = Amount::from(*power);
+
+                match votes.validators_vote.get(validator) {
+                    Some(validator_vote)
+                        if validator_vote.is_same_side(delegator_vote) => {}
+                    Some(_) => {
+                        overrides_validator = true;
+                        voting_power = voting_power
+                            .checked_add(&power)
+                            .unwrap_or(voting_power);
+                        if let Some(validator_power) =
+                            validators_power.get_mut(validator)
+                        {
+                            *validator_power = validator_power
+                                .checked_sub(&power)
+                                .unwrap_or_else(Amount::zero);
+                        }
+                    }
+                    None => {
+                        voting_power = voting_power
+                            .checked_add(&power)
+                            .unwrap_or(voting_power);
+                    }
+                }
+            }
+
+            delegators.push(Self {
+                proposal_id,
+                address: Id::from(delegator.clone()),
+                voting_power,
+                is_validator: false,
+                overrides_validator,
+            });
+        }
+
+        validators_power
+            .into_iter()
+            .map(|(validator, voting_power)| Self {
+                proposal_id,
+                address: Id::from(validator),
+                voting_power,
+                is_validator: true,
+                overrides_validator: false,
+            })
+            .chain(delegators)
+            .collect()
+    }
+}
+
 impl Distribution<ProposalVoteKind> for Standard {
     fn sample<R: rand::prelude::Rng + ?Sized>(
         &self,
@@ -55,3 +155,75 @@ impl Distribution<ProposalVoteKind> for Standard {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::Address;
+    use namada_core::token::Amount as NamadaAmount;
+
+    use super::*;
+
+    fn power_of(powers: &[GovernanceVotePower], address: &Address) -> Amount {
+        powers
+            .iter()
+            .find(|power| power.address == Id::from(address.clone()))
+            .map(|power| power.voting_power.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn delegator_override_moves_power_away_from_validator() {
+        let validator =
+            namada_core::address::gen_established_address("validator");
+        let other_validator =
+            namada_core::address::gen_established_address("other-validator");
+        let overriding =
+            namada_core::address::gen_established_address("overriding");
+        let agreeing =
+            namada_core::address::gen_established_address("agreeing");
+
+        let mut votes = ProposalVotes::default();
+        votes.add_validator(
+            &validator,
+            NamadaAmount::from_u64(100),
+            ProposalVote::Yay,
+        );
+        votes.add_delegator(
+            &overriding,
+            &validator,
+            NamadaAmount::from_u64(30),
+            ProposalVote::Nay,
+        );
+        votes.add_delegator(
+            &overriding,
+            &other_validator,
+            NamadaAmount::from_u64(5),
+            ProposalVote::Nay,
+        );
+        votes.add_delegator(
+            &agreeing,
+            &validator,
+            NamadaAmount::from_u64(20),
+            ProposalVote::Yay,
+        );
+
+        let powers = GovernanceVotePower::from_proposal_votes(1, &votes);
+
+        assert_eq!(powers.len(), 3);
+        assert_eq!(
+            power_of(&powers, &validator),
+            Amount::from(NamadaAmount::from_u64(70))
+        );
+        assert_eq!(
+            power_of(&powers, &overriding),
+            Amount::from(NamadaAmount::from_u64(35))
+        );
+        assert_eq!(power_of(&powers, &agreeing), Amount::zero());
+        assert!(
+            powers
+                .iter()
+                .filter(|power| power.overrides_validator)
+                .all(|power| power.address == Id::from(overriding.clone()))
+        );
+    }
+}
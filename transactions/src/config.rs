@@ -2,6 +2,7 @@ use core::fmt;
 use std::fmt::Display;
 
 use shared::log_config::LogConfig;
+use shared::metrics::MetricsConfig;
 
 #[derive(clap::ValueEnum, Clone, Debug, Copy)]
 pub enum CargoEnv {
@@ -17,8 +18,15 @@ impl Display for CargoEnv {
 
 #[derive(clap::Parser)]
 pub struct AppConfig {
-    #[clap(long, env)]
-    pub tendermint_url: String,
+    #[clap(
+        long,
+        env,
+        value_delimiter = ',',
+        required = true,
+        help = "Comma separated list of RPC urls, requests fail over between \
+                them"
+    )]
+    pub tendermint_url: Vec<String>,
 
     #[clap(long, env, default_value_t = 1)]
     pub from_block_height: u32,
@@ -29,9 +37,43 @@ pub struct AppConfig {
     )]
     pub backfill_from: Option<u32>,
 
+    #[clap(
+        long,
+        env,
+        requires = "backfill_from",
+        help = "Last height to backfill. The range is split in chunks shared \
+                through the database by every worker backfilling it"
+    )]
+    pub backfill_to: Option<u32>,
+
+    #[clap(
+        long,
+        env,
+        default_value_t = 1000,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Number of heights claimed at once by a backfill worker"
+    )]
+    pub backfill_chunk_size: u32,
+
+    #[clap(long, env, help = "Name of the worker claiming backfill chunks")]
+    pub backfill_worker_id: Option<String>,
+
+    #[clap(
+        long,
+        env,
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Number of blocks fetched concurrently while backfilling or \
+                catching up with the chain"
+    )]
+    pub window_size: u32,
+
     #[clap(long, env)]
     pub database_url: String,
 
     #[clap(flatten)]
     pub log: LogConfig,
+
+    #[clap(flatten)]
+    pub metrics: MetricsConfig,
 }
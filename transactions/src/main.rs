@@ -1,46 +1,63 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::identity;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use bigdecimal::{BigDecimal, Zero};
 use chrono::{NaiveDateTime, Utc};
 use clap::Parser;
 use deadpool_diesel::postgres::Object;
+use futures::StreamExt;
 use orm::migrations::run_migrations;
-use shared::block::Block;
+use shared::block::{Block, BlockHeight, Epoch};
 use shared::block_result::BlockResult;
 use shared::checksums::Checksums;
+use shared::client::RpcClient;
 use shared::crawler::crawl;
-use shared::crawler_state::BlockCrawlerState;
-use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use shared::crawler_state::{BlockCrawlerState, CrawlerName};
+use shared::error::{
+    AsDbError, AsRpcError, AsTaskJoinError, ContextDbInteractError, MainError,
+};
+use shared::gas::GasEstimation;
 use shared::id::Id;
-use shared::transaction::{IbcTokenAction, IbcTokenFlow};
-use tendermint_rpc::HttpClient;
-use tendermint_rpc::client::CompatMode;
+use shared::masp::MaspEntry;
+use shared::metrics::{self, Stage};
+use shared::transaction::{
+    IbcPacketEvent, IbcTokenAction, IbcTokenFlow, InnerTransaction,
+    TransactionTarget, WrapperTransaction,
+};
+use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 use tokio::time::Instant;
+use tokio_retry::RetryIf;
+use tokio_retry::strategy::{ExponentialBackoff, jitter};
 use transactions::app_state::AppState;
 use transactions::config::AppConfig;
 use transactions::repository::{
-    block as block_repo, masp as masp_repo, transactions as transaction_repo,
+    backfill as backfill_repo, block as block_repo, masp as masp_repo,
+    transactions as transaction_repo,
 };
 use transactions::services::{
     db as db_service, namada as namada_service,
     tendermint as tendermint_service, tx as tx_service,
 };
 
+/// A claimed backfill chunk without progress for this long is given to
+/// another worker
+const BACKFILL_CHUNK_TIMEOUT_SECS: i64 = 600;
+
+/// Upper bound of the backoff between two attempts at fetching or committing
+/// a block
+const MAX_RETRY_DELAY_MS: u64 = 5000;
+
 #[tokio::main]
 async fn main() -> Result<(), MainError> {
     let config = AppConfig::parse();
 
     config.log.init();
+    config.metrics.init(CrawlerName::Transactions);
 
-    let client = Arc::new(
-        HttpClient::builder(config.tendermint_url.as_str().parse().unwrap())
-            .compat_mode(CompatMode::V0_37)
-            .build()
-            .unwrap(),
-    );
+    let client = Arc::new(RpcClient::new(&config.tendermint_url).unwrap());
 
     let mut checksums = Checksums::default();
     for code_path in Checksums::code_paths() {
@@ -61,6 +78,26 @@ async fn main() -> Result<(), MainError> {
         .context_db_interact_error()
         .into_db_error()?;
 
+    let window_size = config.window_size as usize;
+
+    if let (Some(from), Some(to)) = (config.backfill_from, config.backfill_to) {
+        let worker = config
+            .backfill_worker_id
+            .unwrap_or_else(|| format!("worker-{}", std::process::id()));
+
+        return backfill(
+            from,
+            to,
+            config.backfill_chunk_size,
+            worker,
+            window_size,
+            client,
+            conn,
+            checksums,
+        )
+        .await;
+    }
+
     let crawler_state = db_service::get_crawler_state(&conn).await;
 
     let next_block = match config.backfill_from {
@@ -76,6 +113,26 @@ async fn main() -> Result<(), MainError> {
         ),
     };
 
+    let progress = if config.backfill_from.is_none() {
+        Progress::CrawlerState
+    } else {
+        Progress::None
+    };
+
+    let next_block = if window_size > 1 {
+        catch_up(
+            next_block,
+            window_size,
+            client.clone(),
+            conn.clone(),
+            checksums.clone(),
+            progress,
+        )
+        .await?
+    } else {
+        next_block
+    };
+
     crawl(
         move |block_height| {
             crawling_fn(
@@ -83,7 +140,7 @@ async fn main() -> Result<(), MainError> {
                 client.clone(),
                 conn.clone(),
                 checksums.clone(),
-                config.backfill_from.is_none(),
+                progress,
             )
         },
         next_block,
@@ -92,12 +149,38 @@ async fn main() -> Result<(), MainError> {
     .await
 }
 
+/// What to record alongside the block data when committing a block
+#[derive(Clone, Copy, Debug)]
+enum Progress {
+    /// Keep the transactions crawler state in sync with the committed block
+    CrawlerState,
+    /// Record the progress of the backfill chunk starting at this height
+    BackfillChunk(BlockHeight),
+    None,
+}
+
+/// Everything queried from the RPC node for a block, ready to be committed
+#[derive(Clone)]
+struct BlockData {
+    block_height: BlockHeight,
+    block: Block,
+    tm_block_response: TendermintBlockResponse,
+    wrapper_txs: Vec<WrapperTransaction>,
+    inner_txs: Vec<InnerTransaction>,
+    transaction_sources: HashSet<TransactionTarget>,
+    masp_entries: Vec<MaspEntry>,
+    gas_estimates: Vec<GasEstimation>,
+    ibc_packets: Vec<IbcPacketEvent>,
+    ibc_token_flows: Vec<IbcTokenFlow>,
+    crawler_state: BlockCrawlerState,
+}
+
 async fn crawling_fn(
     block_height: u32,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
     conn: Arc<Object>,
     checksums: Checksums,
-    should_update_crawler_state: bool,
+    progress: Progress,
 ) -> Result<(), MainError> {
     let should_process = can_process(block_height, client.clone()).await?;
 
@@ -113,11 +196,229 @@ async fn crawling_fn(
         return Err(MainError::NoAction);
     }
 
+    let block_data = fetch_block(block_height, &client, checksums).await?;
+
+    commit_block(&conn, block_data, progress).await
+}
+
+/// Crawls the blocks up to the chain tip, fetching `window_size` blocks
+/// concurrently. Returns the next height to crawl once close to the tip.
+async fn catch_up(
+    mut next_block: BlockHeight,
+    window_size: usize,
+    client: Arc<RpcClient>,
+    conn: Arc<Object>,
+    checksums: Checksums,
+    progress: Progress,
+) -> Result<BlockHeight, MainError> {
+    loop {
+        // The regular crawler retries forever, let it take over
+        let Ok(last_block) = namada_service::get_last_block(&client)
+            .await
+            .inspect_err(|e| tracing::warn!("Error querying the tip: {}", e))
+        else {
+            return Ok(next_block);
+        };
+        metrics::record_chain_tip(last_block as u64);
+
+        if last_block < next_block.saturating_add(window_size as u32) {
+            return Ok(next_block);
+        }
+
+        tracing::info!(
+            from = next_block,
+            to = last_block,
+            "Catching up with the chain..."
+        );
+
+        crawl_range(
+            next_block,
+            last_block,
+            window_size,
+            client.clone(),
+            conn.clone(),
+            checksums.clone(),
+            progress,
+        )
+        .await?;
+
+        next_block = last_block + 1;
+    }
+}
+
+/// Backfills the range in chunks claimed through the database, so that
+/// several workers can share it. Exits once every chunk is done.
+#[allow(clippy::too_many_arguments)]
+async fn backfill(
+    from: BlockHeight,
+    to: BlockHeight,
+    chunk_size: u32,
+    worker: String,
+    window_size: usize,
+    client: Arc<RpcClient>,
+    conn: Arc<Object>,
+    checksums: Checksums,
+) -> Result<(), MainError> {
+    let last_block = namada_service::get_last_block(&client)
+        .await
+        .into_rpc_error()?;
+    let to = if to > last_block {
+        tracing::warn!(
+            "Backfill range ends after the last block, stopping at {}",
+            last_block
+        );
+        last_block
+    } else {
+        to
+    };
+
+    tracing::info!(from, to, worker, "Backfilling block range...");
+
+    RetryIf::spawn(
+        retry_strategy(),
+        || async {
+            conn.interact(move |conn| {
+                backfill_repo::insert_chunks(conn, from, to, chunk_size)
+            })
+            .await
+            .context_db_interact_error()
+            .and_then(identity)
+            .into_db_error()
+        },
+        is_transient,
+    )
+    .await?;
+
+    loop {
+        let stale_before = Utc::now().naive_utc()
+            - chrono::Duration::seconds(BACKFILL_CHUNK_TIMEOUT_SECS);
+        let chunk = RetryIf::spawn(
+            retry_strategy(),
+            || async {
+                conn.interact({
+                    let worker = worker.clone();
+                    move |conn| {
+                        backfill_repo::claim_chunk(conn, &worker, stale_before)
+                    }
+                })
+                .await
+                .context_db_interact_error()
+                .and_then(identity)
+                .into_db_error()
+            },
+            is_transient,
+        )
+        .await?;
+
+        let Some(chunk) = chunk else {
+            tracing::info!("No backfill chunk left, exiting...");
+            return Ok(());
+        };
+
+        let chunk_from = chunk.from_height as BlockHeight;
+        let chunk_to = chunk.to_height as BlockHeight;
+        // Resume a chunk abandoned by another worker
+        let first_block = chunk
+            .last_processed_block
+            .map(|height| height as BlockHeight + 1)
+            .unwrap_or(chunk_from);
+
+        tracing::info!(
+            from = first_block,
+            to = chunk_to,
+            "Claimed backfill chunk"
+        );
+
+        crawl_range(
+            first_block,
+            chunk_to,
+            window_size,
+            client.clone(),
+            conn.clone(),
+            checksums.clone(),
+            Progress::BackfillChunk(chunk_from),
+        )
+        .await?;
+
+        RetryIf::spawn(
+            retry_strategy(),
+            || async {
+                conn.interact(move |conn| {
+                    backfill_repo::complete_chunk(conn, chunk_from)
+                })
+                .await
+                .context_db_interact_error()
+                .and_then(identity)
+                .into_db_error()
+            },
+            is_transient,
+        )
+        .await?;
+    }
+}
+
+/// Fetches up to `window_size` blocks concurrently but commits them in height
+/// order
+async fn crawl_range(
+    from: BlockHeight,
+    to: BlockHeight,
+    window_size: usize,
+    client: Arc<RpcClient>,
+    conn: Arc<Object>,
+    checksums: Checksums,
+    progress: Progress,
+) -> Result<(), MainError> {
+    let mut blocks = futures::stream::iter(from..=to)
+        .map(|block_height| {
+            let client = client.clone();
+            let checksums = checksums.clone();
+
+            async move {
+                // Like `crawl`, never give up on a block because of the node
+                RetryIf::spawn(
+                    retry_strategy(),
+                    || fetch_block(block_height, &client, checksums.clone()),
+                    |e: &MainError| e.eq(&MainError::RpcError),
+                )
+                .await
+            }
+        })
+        .buffered(window_size);
+
+    while let Some(block_data) = blocks.next().await {
+        let block_data = block_data?;
+        RetryIf::spawn(
+            retry_strategy(),
+            || commit_block(&conn, block_data.clone(), progress),
+            is_transient,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn retry_strategy() -> impl Iterator<Item = Duration> {
+    ExponentialBackoff::from_millis(100)
+        .max_delay(Duration::from_millis(MAX_RETRY_DELAY_MS))
+        .map(jitter)
+}
+
+/// Errors caused by the node or the database being temporarily unavailable
+fn is_transient(e: &MainError) -> bool {
+    matches!(e, MainError::RpcError | MainError::Database)
+}
+
+async fn fetch_block(
+    block_height: BlockHeight,
+    client: &RpcClient,
+    checksums: Checksums,
+) -> Result<BlockData, MainError> {
     let start = Instant::now();
 
     tracing::debug!(block = block_height, "Query block...");
     let tm_block_response =
-        tendermint_service::query_raw_block_at_height(&client, block_height)
+        tendermint_service::query_raw_block_at_height(client, block_height)
             .await
             .into_rpc_error()?;
     tracing::debug!(
@@ -129,15 +430,14 @@ async fn crawling_fn(
     tracing::debug!(block = block_height, "Query block results...");
     let tm_block_results_response =
         tendermint_service::query_raw_block_results_at_height(
-            &client,
+            client,
             block_height,
         )
         .await
         .into_rpc_error()?;
-    let block_results = BlockResult::from(tm_block_results_response);
 
     let proposer_address_namada = namada_service::get_validator_namada_address(
-        &client,
+        client,
         &Id::from(&tm_block_response.block.header.proposer_address),
     )
     .await
@@ -151,45 +451,74 @@ async fn crawling_fn(
     );
 
     let native_token: namada_sdk::address::Address =
-        namada_service::get_native_token(&client)
+        namada_service::get_native_token(client)
             .await
             .into_rpc_error()?
             .into();
 
-    let epoch =
-        namada_service::get_epoch_at_block_height(&client, block_height)
-            .await
-            .into_rpc_error()?;
+    let epoch = namada_service::get_epoch_at_block_height(client, block_height)
+        .await
+        .into_rpc_error()?;
 
-    let block = Block::from(
-        &tm_block_response,
-        &block_results,
-        &proposer_address_namada,
-        checksums,
-        epoch,
-        block_height,
-        &native_token,
+    // Deserializing the transactions is cpu bound, keep it off the runtime
+    // threads so that the blocks of a window are parsed in parallel
+    let block_data = tokio::task::spawn_blocking(move || {
+        let block_results = BlockResult::from(tm_block_results_response);
+
+        let block = Block::from(
+            &tm_block_response,
+            &block_results,
+            &proposer_address_namada,
+            checksums,
+            epoch,
+            block_height,
+            &native_token,
+        );
+
+        parse_block(
+            block_height,
+            epoch,
+            block,
+            tm_block_response,
+            block_results,
+        )
+    })
+    .await
+    .context("Failed to parse block")
+    .into_task_join_error()?;
+
+    tracing::info!(
+        wrapper_txs = block_data.wrapper_txs.len(),
+        inner_txs = block_data.inner_txs.len(),
+        block = block_height,
+        time_taken = Instant::now().duration_since(start).as_secs_f64(),
+        "Queried block successfully",
+    );
+    metrics::record_stage_duration(
+        Stage::Rpc,
+        Instant::now().duration_since(start),
     );
 
+    Ok(block_data)
+}
+
+fn parse_block(
+    block_height: BlockHeight,
+    epoch: Epoch,
+    block: Block,
+    tm_block_response: TendermintBlockResponse,
+    block_results: BlockResult,
+) -> BlockData {
     let inner_txs = block.inner_txs();
     let wrapper_txs = block.wrapper_txs();
     let transaction_sources = block.sources();
     let masp_entries = block.masp_entries();
     let gas_estimates = tx_service::get_gas_estimates(&block.transactions);
 
-    println!("{:?}", block.transactions);
-    println!("{:?}", gas_estimates);
-
-    let ibc_sequence_packet =
-        tx_service::get_ibc_packets(&block_results, &block.transactions);
-    let ibc_ack_packet = tx_service::get_ibc_ack_packet(&inner_txs);
+    let ibc_packets =
+        tx_service::get_ibc_packet_events(&block_results, &block.transactions);
 
     let ibc_token_flows = {
-        let epoch =
-            namada_service::get_epoch_at_block_height(&client, block_height)
-                .await
-                .into_rpc_error()?;
-
         let mut flows_map = HashMap::new();
 
         tx_service::get_ibc_token_flows(&block_results).for_each(
@@ -217,13 +546,12 @@ async fn crawling_fn(
     };
 
     tracing::info!(
-        "Deserialized {} wrappers, {} inners, {} masp entries, {} ibc \
-         sequence numbers and {} ibc acks events...",
+        "Deserialized {} wrappers, {} inners, {} masp entries and {} ibc \
+         packet events...",
         wrapper_txs.len(),
         inner_txs.len(),
         masp_entries.len(),
-        ibc_sequence_packet.len(),
-        ibc_ack_packet.len()
+        ibc_packets.len()
     );
 
     // Because transaction crawler starts from block 1 we read timestamp from
@@ -234,15 +562,52 @@ async fn crawling_fn(
         last_processed_block: block_height,
     };
 
-    let first_checkpoint = Instant::now();
+    BlockData {
+        block_height,
+        block,
+        tm_block_response,
+        wrapper_txs,
+        inner_txs,
+        transaction_sources,
+        masp_entries,
+        gas_estimates,
+        ibc_packets,
+        ibc_token_flows,
+        crawler_state,
+    }
+}
 
-    tracing::info!(
-        wrapper_txs = wrapper_txs.len(),
-        inner_txs = inner_txs.len(),
-        block = block_height,
-        time_taken = first_checkpoint.duration_since(start).as_secs_f64(),
-        "Queried block successfully",
-    );
+async fn commit_block(
+    conn: &Object,
+    block_data: BlockData,
+    progress: Progress,
+) -> Result<(), MainError> {
+    let BlockData {
+        block_height,
+        block,
+        tm_block_response,
+        wrapper_txs,
+        inner_txs,
+        transaction_sources,
+        masp_entries,
+        gas_estimates,
+        ibc_packets,
+        ibc_token_flows,
+        crawler_state,
+    } = block_data;
+
+    let inserted_rows = [
+        ("blocks", 1),
+        ("wrapper_transactions", wrapper_txs.len()),
+        ("inner_transactions", inner_txs.len()),
+        ("ibc_packets", ibc_packets.len()),
+        ("ibc_token_flows", ibc_token_flows.len()),
+        ("transaction_history", transaction_sources.len()),
+        ("gas_estimations", gas_estimates.len()),
+        ("masp_pool", masp_entries.len()),
+    ];
+
+    let first_checkpoint = Instant::now();
 
     conn.interact(move |conn| {
         conn.build_transaction()
@@ -262,21 +627,26 @@ async fn crawling_fn(
                     inner_txs,
                 )?;
 
-                if should_update_crawler_state {
-                    transaction_repo::insert_crawler_state(
-                        transaction_conn,
-                        crawler_state,
-                    )?;
+                match progress {
+                    Progress::CrawlerState => {
+                        transaction_repo::insert_crawler_state(
+                            transaction_conn,
+                            crawler_state,
+                        )?;
+                    }
+                    Progress::BackfillChunk(chunk_from) => {
+                        backfill_repo::update_chunk_progress(
+                            transaction_conn,
+                            chunk_from,
+                            block_height,
+                        )?;
+                    }
+                    Progress::None => {}
                 }
 
-                transaction_repo::insert_ibc_sequence(
-                    transaction_conn,
-                    ibc_sequence_packet,
-                )?;
-
-                transaction_repo::update_ibc_sequence(
+                transaction_repo::upsert_ibc_packets(
                     transaction_conn,
-                    ibc_ack_packet,
+                    ibc_packets,
                 )?;
 
                 transaction_repo::upsert_ibc_token_flows(
@@ -313,13 +683,23 @@ async fn crawling_fn(
             .as_secs_f64(),
         "Inserted block into database"
     );
+    metrics::record_stage_duration(
+        Stage::Db,
+        second_checkpoint.duration_since(first_checkpoint),
+    );
+    for (table, rows) in inserted_rows {
+        metrics::record_inserted_rows(table, rows);
+    }
+    if let Progress::CrawlerState = progress {
+        metrics::record_last_processed(block_height as u64);
+    }
 
     Ok(())
 }
 
 async fn can_process(
     block_height: u32,
-    client: Arc<HttpClient>,
+    client: Arc<RpcClient>,
 ) -> Result<bool, MainError> {
     let last_block_height =
         namada_service::get_last_block(&client).await.map_err(|e| {
@@ -329,6 +709,7 @@ async fn can_process(
             );
             MainError::RpcError
         })?;
+    metrics::record_chain_tip(last_block_height as u64);
 
     Ok(last_block_height >= block_height)
 }
@@ -0,0 +1,114 @@
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension,
+    PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use orm::backfill::{
+    BackfillChunkDb, BackfillChunkInsertDb, BackfillChunkStatusDb,
+};
+use orm::schema::backfill_chunks;
+use shared::block::BlockHeight;
+
+/// Splits the range in chunks of `chunk_size` heights. Chunks that already
+/// exist, e.g. inserted by another worker, are left untouched.
+pub fn insert_chunks(
+    transaction_conn: &mut PgConnection,
+    from: BlockHeight,
+    to: BlockHeight,
+    chunk_size: u32,
+) -> anyhow::Result<()> {
+    let chunks = (from..=to)
+        .step_by(chunk_size as usize)
+        .map(|chunk_from| BackfillChunkInsertDb {
+            from_height: chunk_from as i32,
+            to_height: chunk_from.saturating_add(chunk_size - 1).min(to) as i32,
+        })
+        .collect::<Vec<_>>();
+
+    // Stay below the postgres limit of bind parameters
+    for chunks in chunks.chunks(10_000) {
+        diesel::insert_into(backfill_chunks::table)
+            .values(chunks)
+            .on_conflict_do_nothing()
+            .execute(transaction_conn)
+            .context("Failed to insert backfill chunks in db")?;
+    }
+
+    anyhow::Ok(())
+}
+
+/// Claims the first chunk that is either pending or whose worker did not
+/// report progress since `stale_before`
+pub fn claim_chunk(
+    conn: &mut PgConnection,
+    worker: &str,
+    stale_before: NaiveDateTime,
+) -> anyhow::Result<Option<BackfillChunkDb>> {
+    conn.transaction(|transaction_conn| {
+        let chunk = backfill_chunks::table
+            .filter(
+                backfill_chunks::status
+                    .eq(BackfillChunkStatusDb::Pending)
+                    .or(backfill_chunks::status
+                        .eq(BackfillChunkStatusDb::InProgress)
+                        .and(backfill_chunks::updated_at.lt(stale_before))),
+            )
+            .order(backfill_chunks::from_height.asc())
+            .select(BackfillChunkDb::as_select())
+            .for_update()
+            .skip_locked()
+            .first(transaction_conn)
+            .optional()?;
+
+        let Some(chunk) = chunk else {
+            return Ok(None);
+        };
+
+        diesel::update(backfill_chunks::table)
+            .filter(backfill_chunks::from_height.eq(chunk.from_height))
+            .set((
+                backfill_chunks::status.eq(BackfillChunkStatusDb::InProgress),
+                backfill_chunks::worker.eq(worker),
+                backfill_chunks::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .returning(BackfillChunkDb::as_returning())
+            .get_result(transaction_conn)
+            .map(Some)
+    })
+    .context("Failed to claim backfill chunk")
+}
+
+pub fn update_chunk_progress(
+    transaction_conn: &mut PgConnection,
+    from_height: BlockHeight,
+    last_processed_block: BlockHeight,
+) -> anyhow::Result<()> {
+    diesel::update(backfill_chunks::table)
+        .filter(backfill_chunks::from_height.eq(from_height as i32))
+        .set((
+            backfill_chunks::last_processed_block
+                .eq(last_processed_block as i32),
+            backfill_chunks::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update backfill chunk progress in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn complete_chunk(
+    transaction_conn: &mut PgConnection,
+    from_height: BlockHeight,
+) -> anyhow::Result<()> {
+    diesel::update(backfill_chunks::table)
+        .filter(backfill_chunks::from_height.eq(from_height as i32))
+        .set((
+            backfill_chunks::status.eq(BackfillChunkStatusDb::Done),
+            backfill_chunks::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to complete backfill chunk in db")?;
+
+    anyhow::Ok(())
+}
@@ -1,3 +1,4 @@
+pub mod backfill;
 pub mod block;
 pub mod masp;
 pub mod transactions;
@@ -3,18 +3,14 @@ use std::collections::HashSet;
 use anyhow::Context;
 use chrono::NaiveDateTime;
 use diesel::upsert::excluded;
-use diesel::{
-    ExpressionMethods, OptionalEmptyChangesetExtension, PgConnection,
-    RunQueryDsl,
-};
+use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
 use orm::crawler_state::{BlockStateInsertDb, CrawlerNameDb};
 use orm::gas::GasEstimationInsertDb;
 use orm::ibc::{
-    IbcAckInsertDb, IbcAckStatusDb, IbcSequencekStatusUpdateDb,
-    IbcTokenFlowsInsertDb,
+    IbcPacketInsertDb, IbcPacketStatusInsertDb, IbcTokenFlowsInsertDb,
 };
 use orm::schema::{
-    crawler_state, gas_estimations, ibc_ack, ibc_token_flows,
+    crawler_state, gas_estimations, ibc_packets, ibc_token_flows,
     inner_transactions, transaction_history, wrapper_transactions,
 };
 use orm::transactions::{
@@ -24,8 +20,8 @@ use orm::transactions::{
 use shared::crawler_state::{BlockCrawlerState, CrawlerName};
 use shared::gas::GasEstimation;
 use shared::transaction::{
-    IbcAck, IbcSequence, IbcTokenFlow, InnerTransaction, TransactionTarget,
-    WrapperTransaction,
+    IbcPacketDirection, IbcPacketEvent, IbcTokenFlow, InnerTransaction,
+    TransactionTarget, WrapperTransaction,
 };
 
 pub fn insert_inner_transactions(
@@ -108,38 +104,106 @@ pub fn update_crawler_timestamp(
     anyhow::Ok(())
 }
 
-pub fn insert_ibc_sequence(
+pub fn upsert_ibc_packets(
     transaction_conn: &mut PgConnection,
-    ibc_sequences: Vec<IbcSequence>,
+    ibc_packets: Vec<IbcPacketEvent>,
 ) -> anyhow::Result<()> {
-    diesel::insert_into(ibc_ack::table)
-        .values::<Vec<IbcAckInsertDb>>(
-            ibc_sequences
-                .into_iter()
-                .map(IbcAckInsertDb::from)
-                .collect(),
-        )
+    for packet in ibc_packets {
+        match packet {
+            IbcPacketEvent::Sent {
+                key,
+                transfer,
+                timeout_timestamp,
+                tx_id,
+            } => insert_ibc_packet(
+                transaction_conn,
+                IbcPacketInsertDb::new(
+                    IbcPacketDirection::Outgoing,
+                    key,
+                    transfer,
+                    timeout_timestamp,
+                    tx_id,
+                ),
+            )?,
+            IbcPacketEvent::Received {
+                key,
+                transfer,
+                timeout_timestamp,
+                tx_id,
+            } => insert_ibc_packet(
+                transaction_conn,
+                IbcPacketInsertDb::new(
+                    IbcPacketDirection::Incoming,
+                    key,
+                    transfer,
+                    timeout_timestamp,
+                    tx_id,
+                ),
+            )?,
+            IbcPacketEvent::Acknowledged {
+                key,
+                success,
+                tx_id,
+            } => upsert_ibc_packet_status(
+                transaction_conn,
+                IbcPacketStatusInsertDb::acknowledged(key, success, tx_id),
+            )?,
+            IbcPacketEvent::TimedOut { key, tx_id } => {
+                upsert_ibc_packet_status(
+                    transaction_conn,
+                    IbcPacketStatusInsertDb::timed_out(key, tx_id),
+                )?
+            }
+        }
+    }
+
+    anyhow::Ok(())
+}
+
+/// The packet can already exist if its outcome was committed first, in which
+/// case only the fields known when sending or receiving it are filled in and
+/// the status is left as is
+fn insert_ibc_packet(
+    transaction_conn: &mut PgConnection,
+    packet: IbcPacketInsertDb,
+) -> anyhow::Result<()> {
+    diesel::insert_into(ibc_packets::table)
+        .values(packet)
+        .on_conflict(ibc_packets::id)
+        .do_update()
+        .set((
+            ibc_packets::denom.eq(excluded(ibc_packets::denom)),
+            ibc_packets::amount.eq(excluded(ibc_packets::amount)),
+            ibc_packets::sender.eq(excluded(ibc_packets::sender)),
+            ibc_packets::receiver.eq(excluded(ibc_packets::receiver)),
+            ibc_packets::timeout_timestamp
+                .eq(excluded(ibc_packets::timeout_timestamp)),
+            ibc_packets::send_tx_id.eq(excluded(ibc_packets::send_tx_id)),
+            ibc_packets::recv_tx_id.eq(excluded(ibc_packets::recv_tx_id)),
+        ))
         .execute(transaction_conn)
-        .context("Failed to update crawler state in db")?;
+        .context("Failed to insert ibc packet in db")?;
 
     anyhow::Ok(())
 }
 
-pub fn update_ibc_sequence(
+fn upsert_ibc_packet_status(
     transaction_conn: &mut PgConnection,
-    ibc_acks: Vec<IbcAck>,
+    status: IbcPacketStatusInsertDb,
 ) -> anyhow::Result<()> {
-    for ack in ibc_acks {
-        let ack_update = IbcSequencekStatusUpdateDb {
-            status: IbcAckStatusDb::from(ack.status.clone()),
-        };
-        diesel::update(ibc_ack::table)
-            .set(ack_update)
-            .filter(ibc_ack::dsl::id.eq(ack.id()))
-            .execute(transaction_conn)
-            .optional_empty_changeset()
-            .context("Failed to update validator metadata in db")?;
-    }
+    diesel::insert_into(ibc_packets::table)
+        .values(status)
+        .on_conflict(ibc_packets::id)
+        .do_update()
+        .set((
+            ibc_packets::status.eq(excluded(ibc_packets::status)),
+            ibc_packets::refunded.eq(excluded(ibc_packets::refunded)),
+            ibc_packets::ack_tx_id.eq(excluded(ibc_packets::ack_tx_id)),
+            ibc_packets::timeout_tx_id.eq(excluded(ibc_packets::timeout_tx_id)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update ibc packet status in db")?;
+
     anyhow::Ok(())
 }
 
@@ -222,3 +286,112 @@ where
 
     anyhow::Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use diesel::{QueryDsl, SelectableHelper};
+    use orm::ibc::{IbcPacketDb, IbcPacketStatusDb};
+    use shared::id::Id;
+    use shared::transaction::{IbcPacketKey, IbcPacketTransfer};
+    use test_helpers::db::TestDb;
+
+    use super::*;
+
+    fn packet_key() -> IbcPacketKey {
+        IbcPacketKey {
+            source_port: "transfer".to_string(),
+            source_channel: "channel-0".to_string(),
+            dest_port: "transfer".to_string(),
+            dest_channel: "channel-7".to_string(),
+            sequence: 3,
+        }
+    }
+
+    fn sent_packet() -> IbcPacketEvent {
+        IbcPacketEvent::Sent {
+            key: packet_key(),
+            transfer: Some(IbcPacketTransfer {
+                denom: "tnam1nam".to_string(),
+                amount: 100.into(),
+                sender: "tnam1alice".to_string(),
+                receiver: "osmo1bob".to_string(),
+            }),
+            timeout_timestamp: 1_700_000_000,
+            tx_id: Id::Hash("send".to_string()),
+        }
+    }
+
+    fn query_packet(conn: &mut PgConnection) -> IbcPacketDb {
+        ibc_packets::table
+            .find(packet_key().id())
+            .select(IbcPacketDb::as_select())
+            .first(conn)
+            .expect("Failed to query ibc packet")
+    }
+
+    /// Test that an acknowledgement committed before its packet is kept once
+    /// the packet is indexed.
+    #[tokio::test]
+    async fn test_upsert_ibc_packets_ack_before_send() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            upsert_ibc_packets(
+                conn,
+                vec![IbcPacketEvent::Acknowledged {
+                    key: packet_key(),
+                    success: false,
+                    tx_id: Id::Hash("ack".to_string()),
+                }],
+            )?;
+            upsert_ibc_packets(conn, vec![sent_packet()])?;
+
+            let packet = query_packet(conn);
+
+            assert!(matches!(packet.status, IbcPacketStatusDb::Failed));
+            assert!(packet.refunded);
+            assert_eq!(packet.ack_tx_id.as_deref(), Some("ack"));
+            assert_eq!(packet.send_tx_id.as_deref(), Some("send"));
+            assert_eq!(packet.denom.as_deref(), Some("tnam1nam"));
+            assert_eq!(packet.amount, Some(100.into()));
+            assert_eq!(packet.sender.as_deref(), Some("tnam1alice"));
+            assert_eq!(packet.receiver.as_deref(), Some("osmo1bob"));
+            assert_eq!(packet.timeout_timestamp, 1_700_000_000);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that the status of a packet indexed first is updated.
+    #[tokio::test]
+    async fn test_upsert_ibc_packets_send_before_timeout() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            upsert_ibc_packets(conn, vec![sent_packet()])?;
+
+            let packet = query_packet(conn);
+            assert!(matches!(packet.status, IbcPacketStatusDb::Pending));
+
+            upsert_ibc_packets(
+                conn,
+                vec![IbcPacketEvent::TimedOut {
+                    key: packet_key(),
+                    tx_id: Id::Hash("timeout".to_string()),
+                }],
+            )?;
+
+            let packet = query_packet(conn);
+            assert!(matches!(packet.status, IbcPacketStatusDb::TimedOut));
+            assert!(packet.refunded);
+            assert_eq!(packet.timeout_tx_id.as_deref(), Some("timeout"));
+            assert_eq!(packet.send_tx_id.as_deref(), Some("send"));
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+}
@@ -5,12 +5,10 @@ use namada_sdk::queries::RPC;
 use namada_sdk::rpc;
 use namada_sdk::state::Key;
 use shared::block::{BlockHeight, Epoch};
+use shared::client::RpcClient;
 use shared::id::Id;
-use tendermint_rpc::HttpClient;
 
-pub async fn get_last_block(
-    client: &HttpClient,
-) -> anyhow::Result<BlockHeight> {
+pub async fn get_last_block(client: &RpcClient) -> anyhow::Result<BlockHeight> {
     let last_block = RPC
         .shell()
         .last_block(client)
@@ -22,7 +20,7 @@ pub async fn get_last_block(
         .map(|b| BlockHeight::from(b.height.0 as u32))
 }
 
-pub async fn get_native_token(client: &HttpClient) -> anyhow::Result<Id> {
+pub async fn get_native_token(client: &RpcClient) -> anyhow::Result<Id> {
     let native_token = RPC
         .shell()
         .native_token(client)
@@ -31,7 +29,7 @@ pub async fn get_native_token(client: &HttpClient) -> anyhow::Result<Id> {
     Ok(Id::from(native_token))
 }
 
-pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
+pub async fn get_current_epoch(client: &RpcClient) -> anyhow::Result<Epoch> {
     let epoch = rpc::query_epoch(client)
         .await
         .context("Failed to query Namada's current epoch")?;
@@ -40,7 +38,7 @@ pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
 }
 
 pub async fn get_epoch_at_block_height(
-    client: &HttpClient,
+    client: &RpcClient,
     block_height: BlockHeight,
 ) -> anyhow::Result<Epoch> {
     let block_height = NamadaSdkBlockHeight::from(block_height as u64);
@@ -56,7 +54,7 @@ pub async fn get_epoch_at_block_height(
 }
 
 pub async fn query_tx_code_hash(
-    client: &HttpClient,
+    client: &RpcClient,
     tx_code_path: &str,
 ) -> Option<String> {
     let hash_key = Key::wasm_hash(tx_code_path);
@@ -74,7 +72,7 @@ pub async fn query_tx_code_hash(
 }
 
 pub async fn get_validator_namada_address(
-    client: &HttpClient,
+    client: &RpcClient,
     tm_addr: &Id,
 ) -> anyhow::Result<Option<Id>> {
     let validator = RPC
@@ -1,11 +1,12 @@
 use anyhow::Context;
+use shared::client::RpcClient;
+use tendermint_rpc::Client;
 use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 use tendermint_rpc::endpoint::block_results::Response as TendermintBlockResultResponse;
-use tendermint_rpc::{Client, HttpClient};
 
 // TODO: map return to our type
 pub async fn query_raw_block_at_height(
-    client: &HttpClient,
+    client: &RpcClient,
     height: u32,
 ) -> anyhow::Result<TendermintBlockResponse> {
     client
@@ -16,7 +17,7 @@ pub async fn query_raw_block_at_height(
 
 // TODO: map return to our type
 pub async fn query_raw_block_results_at_height(
-    client: &HttpClient,
+    client: &RpcClient,
     height: u32,
 ) -> anyhow::Result<TendermintBlockResultResponse> {
     client
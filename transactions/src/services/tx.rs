@@ -5,8 +5,9 @@ use namada_sdk::ibc::core::handler::types::msgs::MsgEnvelope;
 use shared::block_result::{BlockResult, TxAttributesType};
 use shared::gas::GasEstimation;
 use shared::transaction::{
-    IbcAck, IbcAckStatus, IbcSequence, IbcTokenAction, InnerTransaction,
-    TransactionKind, WrapperTransaction, ibc_denom_received, ibc_denom_sent,
+    IbcPacketEvent, IbcPacketKey, IbcPacketTransfer, IbcTokenAction,
+    InnerTransaction, TransactionKind, WrapperTransaction, ibc_denom_received,
+    ibc_denom_sent,
 };
 
 pub fn get_ibc_token_flows(
@@ -41,10 +42,19 @@ pub fn get_ibc_token_flows(
     })
 }
 
-pub fn get_ibc_packets(
+pub fn get_ibc_packet_events(
     block_results: &BlockResult,
     txs: &[(WrapperTransaction, Vec<InnerTransaction>)],
-) -> Vec<IbcSequence> {
+) -> Vec<IbcPacketEvent> {
+    let mut events = get_ibc_sent_packets(block_results, txs);
+    events.extend(get_ibc_relayed_packets(txs));
+    events
+}
+
+fn get_ibc_sent_packets(
+    block_results: &BlockResult,
+    txs: &[(WrapperTransaction, Vec<InnerTransaction>)],
+) -> Vec<IbcPacketEvent> {
     let mut ibc_txs: Vec<_> = txs.iter().rev().fold(
         Default::default(),
         |mut acc, (wrapper_tx, inner_txs)| {
@@ -61,88 +71,94 @@ pub fn get_ibc_packets(
     block_results
         .end_events
         .iter()
-        .filter_map(|event| {
-            if let Some(attributes) = &event.attributes {
-                match attributes {
-                    TxAttributesType::SendPacket(packet) => Some(IbcSequence {
-                        sequence_number: packet.sequence.clone(),
+        .filter_map(|event| match event.attributes.as_ref()? {
+            TxAttributesType::SendPacket(packet) => {
+                let tx_id = ibc_txs
+                    .pop()
+                    .expect("Ibc packet should have a corresponding tx.");
+
+                Some(IbcPacketEvent::Sent {
+                    key: IbcPacketKey {
                         source_port: packet.source_port.clone(),
-                        dest_port: packet.dest_port.clone(),
                         source_channel: packet.source_channel.clone(),
+                        dest_port: packet.dest_port.clone(),
                         dest_channel: packet.dest_channel.clone(),
-                        timeout: packet.timeout_timestamp,
-                        tx_id: ibc_txs
-                            .pop()
-                            .expect("Ibc ack should have a corresponding tx."),
-                    }),
-                    _ => None,
-                }
-            } else {
-                None
+                        sequence: packet.sequence.parse().ok()?,
+                    },
+                    transfer: IbcPacketTransfer::from_packet_data(
+                        packet.data.as_bytes(),
+                    ),
+                    timeout_timestamp: packet.timeout_timestamp,
+                    tx_id,
+                })
             }
+            _ => None,
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
-pub fn get_ibc_ack_packet(inner_txs: &[InnerTransaction]) -> Vec<IbcAck> {
-    inner_txs.iter().filter_map(|tx| match tx.kind.clone() {
-        TransactionKind::IbcMsg(Some(ibc_message)) => match ibc_message.0 {
-            namada_sdk::ibc::IbcMessage::Envelope(msg_envelope) => {
-                match *msg_envelope {
-                    MsgEnvelope::Packet(packet_msg) => match packet_msg {
-                        PacketMsg::Recv(_) => None,
-                        PacketMsg::Ack(msg) => {
-                            let ack = match serde_json::from_slice::<
-                                AcknowledgementStatus,
-                            >(
-                                msg.acknowledgement.as_bytes()
-                            ) {
-                                Ok(status) => IbcAck {
-                                    sequence_number: msg.packet.seq_on_a.to_string(),
-                                    source_port: msg.packet.port_id_on_a.to_string(),
-                                    dest_port: msg.packet.port_id_on_b.to_string(),
-                                    source_channel: msg.packet.chan_id_on_a.to_string(),
-                                    dest_channel: msg.packet.chan_id_on_b.to_string(),
-                                    status: match status {
-                                        AcknowledgementStatus::Success(_) => IbcAckStatus::Success,
-                                        AcknowledgementStatus::Error(_) => IbcAckStatus::Fail,
-                                    },
-                                },
-                                Err(_) => IbcAck {
-                                    sequence_number: msg.packet.seq_on_a.to_string(),
-                                    source_port: msg.packet.port_id_on_a.to_string(),
-                                    dest_port: msg.packet.port_id_on_b.to_string(),
-                                    source_channel: msg.packet.chan_id_on_a.to_string(),
-                                    dest_channel: msg.packet.chan_id_on_b.to_string(),
-                                    status: IbcAckStatus::Unknown,
-                                },
-                            };
-                            Some(ack)
-                        }
-                        PacketMsg::Timeout(msg) => Some(IbcAck {
-                            sequence_number: msg.packet.seq_on_a.to_string(),
-                            source_port: msg.packet.port_id_on_a.to_string(),
-                            dest_port: msg.packet.port_id_on_b.to_string(),
-                            source_channel: msg.packet.chan_id_on_a.to_string(),
-                            dest_channel: msg.packet.chan_id_on_b.to_string(),
-                            status: IbcAckStatus::Timeout,
-                        }),
-                        PacketMsg::TimeoutOnClose(msg) => Some(IbcAck {
-                            sequence_number: msg.packet.seq_on_a.to_string(),
-                            source_port: msg.packet.port_id_on_a.to_string(),
-                            dest_port: msg.packet.port_id_on_b.to_string(),
-                            source_channel: msg.packet.chan_id_on_a.to_string(),
-                            dest_channel: msg.packet.chan_id_on_b.to_string(),
-                            status: IbcAckStatus::Timeout,
-                        }),
-                    },
-                    _ => None,
-                }
-            },
-            _ => None
-        },
-        _ => None,
-    }).collect()
+/// Packet messages submitted by relayers, failed relays (e.g. packets already
+/// relayed by someone else) are ignored
+fn get_ibc_relayed_packets(
+    txs: &[(WrapperTransaction, Vec<InnerTransaction>)],
+) -> Vec<IbcPacketEvent> {
+    txs.iter()
+        .flat_map(|(wrapper_tx, inner_txs)| {
+            inner_txs
+                .iter()
+                .filter(|inner_tx| inner_tx.was_successful(wrapper_tx))
+        })
+        .filter_map(|inner_tx| {
+            let TransactionKind::IbcMsg(Some(ibc_message)) = &inner_tx.kind
+            else {
+                return None;
+            };
+            let namada_sdk::ibc::IbcMessage::Envelope(msg_envelope) =
+                &ibc_message.0
+            else {
+                return None;
+            };
+            let MsgEnvelope::Packet(packet_msg) = msg_envelope.as_ref() else {
+                return None;
+            };
+            let tx_id = inner_tx.tx_id.clone();
+
+            let event = match packet_msg {
+                PacketMsg::Recv(msg) => IbcPacketEvent::Received {
+                    key: IbcPacketKey::from(&msg.packet),
+                    transfer: IbcPacketTransfer::from_packet_data(
+                        &msg.packet.data,
+                    ),
+                    timeout_timestamp: msg
+                        .packet
+                        .timeout_timestamp_on_b
+                        .nanoseconds(),
+                    tx_id,
+                },
+                PacketMsg::Ack(msg) => IbcPacketEvent::Acknowledged {
+                    key: IbcPacketKey::from(&msg.packet),
+                    // Acknowledgements of applications other than ICS20 can't
+                    // be decoded, we can only tell they were delivered
+                    success: serde_json::from_slice::<AcknowledgementStatus>(
+                        msg.acknowledgement.as_bytes(),
+                    )
+                    .map(|status| status.is_successful())
+                    .unwrap_or(true),
+                    tx_id,
+                },
+                PacketMsg::Timeout(msg) => IbcPacketEvent::TimedOut {
+                    key: IbcPacketKey::from(&msg.packet),
+                    tx_id,
+                },
+                PacketMsg::TimeoutOnClose(msg) => IbcPacketEvent::TimedOut {
+                    key: IbcPacketKey::from(&msg.packet),
+                    tx_id,
+                },
+            };
+
+            Some(event)
+        })
+        .collect()
 }
 
 pub fn get_gas_estimates(
@@ -219,6 +235,12 @@ pub fn get_gas_estimates(
                 | TransactionKind::ReactivateValidator(_)
                 | TransactionKind::DeactivateValidator(_)
                 | TransactionKind::UnjailValidator(_)
+                | TransactionKind::InitAccount(_)
+                | TransactionKind::UpdateAccount(_)
+                | TransactionKind::ChangeConsensusKey(_)
+                | TransactionKind::ResignSteward(_)
+                | TransactionKind::UpdateStewardCommission(_)
+                | TransactionKind::BridgePoolTransfer(_)
                 | TransactionKind::Unknown(_) => (),
             });
             gas_estimate
@@ -1,4 +1,5 @@
 use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
 use std::time::Duration;
 
 use axum::error_handling::HandleErrorLayer;
@@ -8,9 +9,8 @@ use axum::routing::get;
 use axum::{BoxError, Json, Router};
 use axum_prometheus::PrometheusMetricLayer;
 use lazy_static::lazy_static;
-use namada_sdk::tendermint_rpc::HttpClient;
-use namada_sdk::tendermint_rpc::client::CompatMode;
 use serde_json::json;
+use shared::client::RpcClient;
 use tower::ServiceBuilder;
 use tower::buffer::BufferLayer;
 use tower::limit::RateLimitLayer;
@@ -20,11 +20,13 @@ use tower_http::trace::TraceLayer;
 use crate::appstate::AppState;
 use crate::config::AppConfig;
 use crate::handler::{
-    balance as balance_handlers, block as block_handlers,
-    chain as chain_handlers, crawler_state as crawler_state_handlers,
-    gas as gas_handlers, governance as gov_handlers, ibc as ibc_handler,
-    masp as masp_handlers, pgf as pgf_service, pk as pk_handlers,
-    pos as pos_handlers, transaction as transaction_handlers,
+    account as account_handlers, balance as balance_handlers,
+    block as block_handlers, chain as chain_handlers,
+    crawler_state as crawler_state_handlers, gas as gas_handlers,
+    governance as gov_handlers, ibc as ibc_handler, masp as masp_handlers,
+    pgf as pgf_service, pk as pk_handlers, pos as pos_handlers,
+    search as search_handlers, subscription as subscription_handlers,
+    transaction as transaction_handlers,
 };
 use crate::state::common::CommonState;
 
@@ -43,23 +45,28 @@ impl ApplicationServer {
         let (prometheus_layer, metric_handle) = PrometheusMetricLayer::pair();
 
         let app_state = AppState::new(db_url, cache_url);
-        let client = HttpClient::builder(
-            config.tendermint_url.as_str().parse().unwrap(),
-        )
-        .compat_mode(CompatMode::V0_37)
-        .build()
-        .unwrap();
+        let client = Arc::new(RpcClient::new(&config.tendermint_url)?);
 
         let routes = {
             let common_state =
                 CommonState::new(client, config.clone(), app_state.clone());
 
+            tokio::spawn(common_state.subscription_service.clone().run());
+
             Router::new()
                 .route("/pos/validator", get(pos_handlers::get_validators))
                 .route(
                     "/pos/validator/all",
                     get(pos_handlers::get_all_validators),
                 )
+                .route(
+                    "/pos/validator/:address/history",
+                    get(pos_handlers::get_validator_history),
+                )
+                .route(
+                    "/pos/validator/:address/apr",
+                    get(pos_handlers::get_validator_apr),
+                )
                 .route("/pos/bond/:address", get(pos_handlers::get_bonds))
                 .route(
                     "/pos/merged-bonds/:address",
@@ -75,6 +82,14 @@ impl ApplicationServer {
                     get(pos_handlers::get_withdraws),
                 )
                 .route("/pos/reward/:address", get(pos_handlers::get_rewards))
+                .route(
+                    "/pos/reward/:address/history",
+                    get(pos_handlers::get_reward_history),
+                )
+                .route(
+                    "/pos/reward/:address/apr",
+                    get(pos_handlers::get_delegator_apr),
+                )
                 .route(
                     "/pos/voting-power",
                     get(pos_handlers::get_total_voting_power),
@@ -95,10 +110,26 @@ impl ApplicationServer {
                     "/gov/proposal/:id/data",
                     get(gov_handlers::get_proposal_data_by_proposal_id),
                 )
+                .route(
+                    "/gov/proposal/:id/wasm",
+                    get(gov_handlers::get_proposal_wasm_code),
+                )
+                .route(
+                    "/gov/proposal/:id/wasm/download",
+                    get(gov_handlers::download_proposal_wasm_code),
+                )
                 .route(
                     "/gov/proposal/:id/votes",
                     get(gov_handlers::get_governance_proposal_votes),
                 )
+                .route(
+                    "/gov/proposal/:id/breakdown",
+                    get(gov_handlers::get_governance_proposal_vote_breakdown),
+                )
+                .route(
+                    "/gov/proposal/:id/timeline",
+                    get(gov_handlers::get_governance_proposal_timeline),
+                )
                 .route(
                     "/gov/proposal/:id/votes/:address",
                     get(gov_handlers::get_governance_proposal_votes_by_address),
@@ -111,11 +142,20 @@ impl ApplicationServer {
                     "/account/:address",
                     get(balance_handlers::get_address_balance),
                 )
+                .route(
+                    "/account/:address/history",
+                    get(balance_handlers::get_address_balance_history),
+                )
+                .route(
+                    "/account/:address/info",
+                    get(account_handlers::get_account_info),
+                )
                 .route(
                     "/revealed-public-key/:address",
                     get(pk_handlers::get_revealed_pk),
                 )
                 .route("/gas/estimate", get(gas_handlers::get_gas_estimate))
+                .route("/gas/stats", get(gas_handlers::get_gas_stats))
                 .route(
                     "/gas-price/:token",
                     get(gas_handlers::get_gas_price_by_token),
@@ -133,6 +173,10 @@ impl ApplicationServer {
                     "/chain/history",
                     get(transaction_handlers::get_transaction_history),
                 )
+                .route(
+                    "/chain/history/export",
+                    get(transaction_handlers::export_transaction_history),
+                )
                 .route("/chain/parameters", get(chain_handlers::get_parameters))
                 .route("/chain/rpc-url", get(chain_handlers::get_rpc_url))
                 .route("/chain/token", get(chain_handlers::get_tokens))
@@ -149,6 +193,15 @@ impl ApplicationServer {
                     get(chain_handlers::get_last_processed_epoch),
                 )
                 .route("/ibc/:tx_id/status", get(ibc_handler::get_ibc_status))
+                .route("/ibc/channels", get(ibc_handler::get_ibc_channels))
+                .route(
+                    "/ibc/channel/:channel/packets/in-flight",
+                    get(ibc_handler::get_ibc_in_flight_packets),
+                )
+                .route(
+                    "/ibc/channel/:channel/packets/timed-out",
+                    get(ibc_handler::get_ibc_timed_out_packets),
+                )
                 .route(
                     "/ibc/rate-limits",
                     get(ibc_handler::get_ibc_rate_limits),
@@ -161,6 +214,10 @@ impl ApplicationServer {
                     "/ibc/token-throughput/:token",
                     get(ibc_handler::get_ibc_token_throughput),
                 )
+                .route(
+                    "/ibc/throughput-utilization",
+                    get(ibc_handler::get_ibc_throughput_utilization),
+                )
                 .route(
                     "/pgf/payments",
                     get(pgf_service::get_pgf_continuous_payments),
@@ -169,12 +226,19 @@ impl ApplicationServer {
                     "/pgf/paymenents/:proposal_id",
                     get(pgf_service::get_pgf_payment_by_proposal_id),
                 )
+                .route("/search", get(search_handlers::search))
                 .route(
                     "/crawlers/timestamps",
                     get(crawler_state_handlers::get_crawlers_timestamps),
                 )
                 // Server sent events endpoints
                 .route("/chain/status", get(chain_handlers::chain_status))
+                .route("/subscribe", get(subscription_handlers::subscribe_sse))
+                // Websocket endpoints
+                .route(
+                    "/subscribe/ws",
+                    get(subscription_handlers::subscribe_ws),
+                )
                 .route(
                     "/block/height/:value",
                     get(block_handlers::get_block_by_height),
@@ -183,6 +247,10 @@ impl ApplicationServer {
                     "/block/timestamp/:value",
                     get(block_handlers::get_block_by_timestamp),
                 )
+                .route(
+                    "/block/mismatches",
+                    get(block_handlers::get_block_mismatches),
+                )
                 .route(
                     "/masp/aggregates",
                     get(masp_handlers::get_masp_aggregates),
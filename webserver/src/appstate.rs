@@ -1,8 +1,13 @@
 use std::env;
 use std::process::exit;
+use std::time::Duration;
 
 use deadpool_diesel::postgres::{Object, Pool as DbPool};
-use deadpool_redis::{Config, Connection, Pool as CachePool};
+use deadpool_redis::{
+    Config, Connection, Pool as CachePool, PoolConfig, Timeouts,
+};
+
+const CACHE_TIMEOUT: Duration = Duration::from_millis(500);
 
 #[derive(Clone)]
 pub struct AppState {
@@ -36,8 +41,18 @@ impl AppState {
         };
 
         let cache = cache_url.map(|url| {
-            let cache_pool = Config::from_url(url)
-                .create_pool(Some(deadpool_redis::Runtime::Tokio1));
+            let mut config = Config::from_url(url);
+            // Fail fast so that an unreachable cache falls back to postgres
+            config.pool = Some(PoolConfig {
+                timeouts: Timeouts {
+                    wait: Some(CACHE_TIMEOUT),
+                    create: Some(CACHE_TIMEOUT),
+                    recycle: Some(CACHE_TIMEOUT),
+                },
+                ..PoolConfig::default()
+            });
+            let cache_pool =
+                config.create_pool(Some(deadpool_redis::Runtime::Tokio1));
 
             match cache_pool {
                 Ok(pool) => pool,
@@ -58,10 +73,20 @@ impl AppState {
         self.db.get().await.unwrap()
     }
 
+    pub fn is_cache_enabled(&self) -> bool {
+        self.cache.is_some()
+    }
+
     pub async fn get_cache_connection(&self) -> Option<Connection> {
         match &self.cache {
             None => None,
-            Some(cache) => Some(cache.get().await.unwrap()),
+            Some(cache) => cache
+                .get()
+                .await
+                .inspect_err(|e| {
+                    tracing::warn!("Error getting cache connection: {}", e)
+                })
+                .ok(),
         }
     }
 }
@@ -14,14 +14,25 @@ pub struct AppConfig {
     #[clap(long, env)]
     pub cache_url: Option<String>,
 
+    /// Time to live of the cached responses, in seconds
+    #[clap(long, env, default_value = "300")]
+    pub cache_ttl: usize,
+
     #[clap(long, env)]
     pub database_url: String,
 
     #[clap(long, env)]
     pub rps: Option<u64>,
 
-    #[clap(long, env)]
-    pub tendermint_url: String,
+    #[clap(
+        long,
+        env,
+        value_delimiter = ',',
+        required = true,
+        help = "Comma separated list of RPC urls, requests fail over between \
+                them"
+    )]
+    pub tendermint_url: Vec<String>,
 
     #[clap(flatten)]
     pub log: LogConfig,
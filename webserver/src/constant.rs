@@ -1 +1,7 @@
 pub const ITEM_PER_PAGE: u64 = 30;
+pub const SUBSCRIPTION_POLL_INTERVAL_SECS: u64 = 3;
+pub const SUBSCRIPTION_MAX_BLOCKS_PER_POLL: i32 = 100;
+pub const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 1024;
+pub const SEARCH_MAX_RESULTS: i64 = 20;
+pub const EXPORT_BATCH_SIZE: i64 = 1000;
+pub const GAS_ESTIMATE_SAFETY_MARGIN_PCT: u64 = 20;
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceQueryParams {
+    #[validate(range(min = 0))]
+    pub height: Option<i32>,
+    #[validate(range(min = 0))]
+    pub timestamp: Option<i64>,
+    pub omit_zero: Option<bool>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistoryQueryParams {
+    #[validate(range(min = 1, max = 10000))]
+    pub page: Option<u64>,
+    pub token: Option<String>,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct BlockMismatchQueryParams {
+    #[validate(range(min = 1, max = 10000))]
+    pub page: Option<u64>,
+}
@@ -37,10 +37,18 @@ pub struct GasEstimateQuery {
     pub signatures: Option<u64>,
     #[validate(range(min = 1, max = 100000))]
     pub tx_size: Option<u64>,
+    #[validate(range(min = 1, max = 1000000))]
+    pub last_blocks: Option<u64>,
+    #[validate(range(min = 1, max = 365))]
+    pub last_days: Option<u64>,
+    #[validate(range(min = 0, max = 100))]
+    pub safety_margin: Option<u64>,
 }
 
 impl GasEstimateQuery {
     pub fn is_valid(&self) -> Result<(), GasError> {
+        self.validate().map_err(|_| GasError::InvalidQueryParams)?;
+
         let res = [
             self.bond,
             self.claim_rewards,
@@ -60,10 +68,78 @@ impl GasEstimateQuery {
         .iter()
         .any(|field| field.is_some());
 
-        if res {
+        // Only one recency window can be used at a time
+        let conflicting_windows =
+            self.last_blocks.is_some() && self.last_days.is_some();
+
+        if res && !conflicting_windows {
             Ok(())
         } else {
             Err(GasError::InvalidQueryParams)
         }
     }
 }
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GasStatsInterval {
+    Day,
+    #[default]
+    Epoch,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct GasStatsQueryParams {
+    #[validate(range(min = 1, max = 10000))]
+    pub page: Option<u64>,
+    pub token: Option<String>,
+    #[serde(default)]
+    pub interval: GasStatsInterval,
+    pub from_epoch: Option<u32>,
+    pub to_epoch: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(value: serde_json::Value) -> GasEstimateQuery {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn is_valid_accepts_in_range_params() {
+        let query = query(serde_json::json!({ "bond": 2, "last_days": 7 }));
+        assert!(query.is_valid().is_ok());
+    }
+
+    #[test]
+    fn is_valid_rejects_out_of_range_params() {
+        assert!(
+            query(serde_json::json!({ "bond": 101 }))
+                .is_valid()
+                .is_err()
+        );
+        assert!(
+            query(serde_json::json!({ "bond": 1, "last_days": 1000 }))
+                .is_valid()
+                .is_err()
+        );
+        assert!(
+            query(serde_json::json!({ "bond": 1, "safety_margin": 500 }))
+                .is_valid()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn is_valid_rejects_conflicting_windows() {
+        let query = query(serde_json::json!({
+            "bond": 1,
+            "last_blocks": 10,
+            "last_days": 1,
+        }));
+        assert!(query.is_valid().is_err());
+    }
+}
@@ -32,4 +32,5 @@ pub struct ProposalQueryParams {
 pub struct ProposalVotesQueryparams {
     #[validate(range(min = 1, max = 10000))]
     pub page: Option<u64>,
+    pub cursor: Option<String>,
 }
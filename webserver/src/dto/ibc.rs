@@ -1,14 +1,49 @@
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IbcRateLimit {
     pub token_address: Option<String>,
     pub throughput_limit: Option<u64>,
+    pub epoch: Option<u32>,
+    pub from_epoch: Option<u32>,
+    pub to_epoch: Option<u32>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IbcTokenFlow {
     pub token_address: Option<String>,
+    pub epoch: Option<u32>,
+    pub from_epoch: Option<u32>,
+    pub to_epoch: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IbcTokenThroughput {
+    pub epoch: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IbcThroughputUtilization {
+    pub token_address: Option<String>,
+    pub from_epoch: Option<u32>,
+    pub to_epoch: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct IbcPacketsQueryParams {
+    #[validate(range(min = 1, max = 10000))]
+    pub page: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IbcChannelsQueryParams {
+    pub counterparty_chain_id: Option<String>,
+    pub from_epoch: Option<u32>,
+    pub to_epoch: Option<u32>,
 }
@@ -1,3 +1,5 @@
+pub mod balance;
+pub mod block;
 pub mod chain;
 pub mod crawler_state;
 pub mod gas;
@@ -6,4 +8,6 @@ pub mod ibc;
 pub mod masp;
 pub mod pgf;
 pub mod pos;
+pub mod search;
+pub mod subscription;
 pub mod transaction;
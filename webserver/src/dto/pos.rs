@@ -88,3 +88,34 @@ pub struct WithdrawsDto {
     #[validate(range(min = 1, max = 10000))]
     pub epoch: Option<u64>,
 }
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct ValidatorHistoryQueryParams {
+    #[validate(range(min = 1, max = 10000))]
+    pub page: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RewardLedgerKindDto {
+    Accrued,
+    Claimed,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardHistoryQueryParams {
+    #[validate(range(min = 1, max = 10000))]
+    pub page: Option<u64>,
+    pub validator: Option<String>,
+    pub kind: Option<RewardLedgerKindDto>,
+    pub from_epoch: Option<u32>,
+    pub to_epoch: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardAprQueryParams {
+    pub from_epoch: Option<u32>,
+    pub to_epoch: Option<u32>,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct SearchQueryParams {
+    #[validate(length(min = 1, max = 256))]
+    pub q: String,
+}
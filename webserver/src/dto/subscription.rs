@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::response::transaction::TransactionKind;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionTopic {
+    Blocks,
+    Transactions,
+    AddressActivity,
+    Proposals,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionQueryParams {
+    #[validate(length(min = 1))]
+    pub topics: Vec<SubscriptionTopic>,
+    #[serde(default)]
+    pub kinds: Vec<TransactionKind>,
+    #[serde(default)]
+    #[validate(length(max = 10))]
+    pub addresses: Vec<String>,
+}
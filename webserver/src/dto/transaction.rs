@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::response::transaction::{
+    TransactionKind, TransactionResult, TrasactionHistoryKind,
+};
+
 #[derive(Clone, Serialize, Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionHistoryQueryParams {
@@ -8,4 +12,35 @@ pub struct TransactionHistoryQueryParams {
     pub page: Option<u64>,
     #[validate(length(min = 1, max = 10))]
     pub addresses: Vec<String>,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub kinds: Vec<TransactionKind>,
+    pub token: Option<String>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+    /// Unix timestamp in seconds
+    pub from_timestamp: Option<i64>,
+    /// Unix timestamp in seconds
+    pub to_timestamp: Option<i64>,
+    pub direction: Option<TrasactionHistoryKind>,
+    pub exit_code: Option<TransactionResult>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionHistoryExportQueryParams {
+    #[validate(length(min = 1, max = 10))]
+    pub addresses: Vec<String>,
+    pub format: ExportFormat,
+    /// Unix timestamp in seconds
+    pub from_timestamp: Option<i64>,
+    /// Unix timestamp in seconds
+    pub to_timestamp: Option<i64>,
 }
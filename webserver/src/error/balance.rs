@@ -8,6 +8,10 @@ use crate::response::api::ApiErrorResponse;
 pub enum BalanceError {
     #[error("Proposal {0} not found")]
     NotFound(u64),
+    #[error("Only one of height or timestamp can be provided")]
+    HeightAndTimestamp,
+    #[error("Invalid timestamp {0}")]
+    InvalidTimestamp(i64),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -18,6 +22,8 @@ impl IntoResponse for BalanceError {
     fn into_response(self) -> Response {
         let status_code = match self {
             BalanceError::NotFound(_) => StatusCode::NOT_FOUND,
+            BalanceError::HeightAndTimestamp
+            | BalanceError::InvalidTimestamp(_) => StatusCode::BAD_REQUEST,
             BalanceError::Unknown(_) | BalanceError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
@@ -12,6 +12,12 @@ pub enum GovernanceError {
     NotFound(u64),
     #[error("Proposal {0} has no associated data")]
     DataNotFound(u64),
+    #[error("Proposal {0} has no wasm code")]
+    WasmCodeNotFound(u64),
+    #[error("Proposal {0} voting power has not been snapshotted yet")]
+    VotingPowerNotFound(u64),
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -24,6 +30,9 @@ impl IntoResponse for GovernanceError {
             GovernanceError::TooShortPattern(_) => StatusCode::BAD_REQUEST,
             GovernanceError::NotFound(_) => StatusCode::NOT_FOUND,
             GovernanceError::DataNotFound(_) => StatusCode::NOT_FOUND,
+            GovernanceError::VotingPowerNotFound(_) => StatusCode::NOT_FOUND,
+            GovernanceError::WasmCodeNotFound(_) => StatusCode::NOT_FOUND,
+            GovernanceError::InvalidCursor(_) => StatusCode::BAD_REQUEST,
             GovernanceError::Unknown(_) | GovernanceError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
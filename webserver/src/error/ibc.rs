@@ -8,6 +8,8 @@ use crate::response::api::ApiErrorResponse;
 pub enum IbcError {
     #[error("Revealed public key {0} not found")]
     NotFound(u64),
+    #[error("Throughput of token {0} at epoch {1} not found")]
+    TokenThroughputNotFound(String, u32),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -17,7 +19,9 @@ pub enum IbcError {
 impl IntoResponse for IbcError {
     fn into_response(self) -> axum::response::Response {
         let status_code = match self {
-            IbcError::NotFound(_) => StatusCode::NOT_FOUND,
+            IbcError::NotFound(_) | IbcError::TokenThroughputNotFound(_, _) => {
+                StatusCode::NOT_FOUND
+            }
             IbcError::Unknown(_) | IbcError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
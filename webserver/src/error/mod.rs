@@ -1,3 +1,4 @@
+pub mod account;
 pub mod api;
 pub mod balance;
 pub mod block;
@@ -10,4 +11,5 @@ pub mod masp;
 pub mod pgf;
 pub mod pos;
 pub mod revealed_pk;
+pub mod search;
 pub mod transaction;
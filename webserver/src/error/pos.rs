@@ -6,6 +6,8 @@ use crate::response::api::ApiErrorResponse;
 
 #[derive(Error, Debug)]
 pub enum PoSError {
+    #[error("Validator {0} not found")]
+    ValidatorNotFound(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -15,6 +17,7 @@ pub enum PoSError {
 impl IntoResponse for PoSError {
     fn into_response(self) -> Response {
         let status_code = match self {
+            PoSError::ValidatorNotFound(_) => StatusCode::NOT_FOUND,
             PoSError::Unknown(_) | PoSError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
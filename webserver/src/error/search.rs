@@ -0,0 +1,28 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+
+use crate::response::api::ApiErrorResponse;
+
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("Invalid search query: {0}")]
+    InvalidQuery(String),
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl IntoResponse for SearchError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            SearchError::InvalidQuery(_) => StatusCode::BAD_REQUEST,
+            SearchError::Unknown(_) | SearchError::Database(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
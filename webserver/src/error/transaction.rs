@@ -10,6 +10,12 @@ pub enum TransactionError {
     InvalidTxId,
     #[error("The tx id {0} does not exist")]
     TxIdNotFound(String),
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+    #[error("Invalid timestamp: {0}")]
+    InvalidTimestamp(i64),
+    #[error("Invalid height: {0}")]
+    InvalidHeight(u64),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Rpc error: {0}")]
@@ -23,6 +29,9 @@ impl IntoResponse for TransactionError {
         let status_code = match self {
             TransactionError::InvalidTxId => StatusCode::BAD_REQUEST,
             TransactionError::TxIdNotFound(_) => StatusCode::NOT_FOUND,
+            TransactionError::InvalidCursor(_) => StatusCode::BAD_REQUEST,
+            TransactionError::InvalidTimestamp(_) => StatusCode::BAD_REQUEST,
+            TransactionError::InvalidHeight(_) => StatusCode::BAD_REQUEST,
             TransactionError::Unknown(_)
             | TransactionError::Database(_)
             | TransactionError::Rpc(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -0,0 +1,19 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
+use axum_macros::debug_handler;
+
+use crate::error::api::ApiError;
+use crate::response::account::AccountInfo;
+use crate::state::common::CommonState;
+
+#[debug_handler]
+pub async fn get_account_info(
+    _headers: HeaderMap,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<AccountInfo>, ApiError> {
+    let account = state.account_service.get_account_info(address).await?;
+
+    Ok(Json(account))
+}
@@ -1,19 +1,51 @@
 use axum::Json;
 use axum::extract::{Path, State};
 use axum::http::HeaderMap;
+use axum_extra::extract::Query;
 use axum_macros::debug_handler;
 
+use crate::dto::balance::{BalanceHistoryQueryParams, BalanceQueryParams};
 use crate::error::api::ApiError;
-use crate::response::balance::AddressBalance;
+use crate::response::balance::{AddressBalance, AddressBalanceChange};
+use crate::response::utils::PaginatedResponse;
 use crate::state::common::CommonState;
 
 #[debug_handler]
 pub async fn get_address_balance(
     _headers: HeaderMap,
     Path(address): Path<String>,
+    Query(query): Query<BalanceQueryParams>,
     State(state): State<CommonState>,
 ) -> Result<Json<Vec<AddressBalance>>, ApiError> {
-    let balances = state.balance_service.get_address_balances(address).await?;
+    let balances = state
+        .balance_service
+        .get_address_balances(
+            address,
+            query.height,
+            query.timestamp,
+            query.omit_zero.unwrap_or(false),
+        )
+        .await?;
 
     Ok(Json(balances))
 }
+
+#[debug_handler]
+pub async fn get_address_balance_history(
+    _headers: HeaderMap,
+    Path(address): Path<String>,
+    Query(query): Query<BalanceHistoryQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<AddressBalanceChange>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+
+    let (balance_changes, total_pages, total_items) = state
+        .balance_service
+        .get_address_balance_history(address, query.token, page)
+        .await?;
+
+    let response =
+        PaginatedResponse::new(balance_changes, page, total_pages, total_items);
+
+    Ok(Json(response))
+}
@@ -1,10 +1,13 @@
 use axum::Json;
 use axum::extract::{Path, State};
 use axum::http::HeaderMap;
+use axum_extra::extract::Query;
 use axum_macros::debug_handler;
 
+use crate::dto::block::BlockMismatchQueryParams;
 use crate::error::api::ApiError;
-use crate::response::block::Block;
+use crate::response::block::{Block, BlockMismatch};
+use crate::response::utils::PaginatedResponse;
 use crate::state::common::CommonState;
 
 #[debug_handler]
@@ -28,3 +31,20 @@ pub async fn get_block_by_timestamp(
 
     Ok(Json(block))
 }
+
+#[debug_handler]
+pub async fn get_block_mismatches(
+    _headers: HeaderMap,
+    Query(query): Query<BlockMismatchQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<BlockMismatch>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+
+    let (mismatches, total_pages, total_items) =
+        state.block_service.get_block_mismatches(page).await?;
+
+    let response =
+        PaginatedResponse::new(mismatches, page, total_pages, total_items);
+
+    Ok(Json(response))
+}
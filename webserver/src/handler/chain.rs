@@ -1,4 +1,3 @@
-use std::convert::Infallible;
 use std::time::Duration;
 
 use axum::Json;
@@ -8,6 +7,7 @@ use axum::response::Sse;
 use axum::response::sse::{Event, KeepAlive};
 use axum_extra::extract::Query;
 use futures::Stream;
+use orm::crawler_state::CrawlerNameDb;
 use tokio_stream::StreamExt;
 
 use crate::dto::chain::TokenSupply as TokenSupplyDto;
@@ -26,7 +26,7 @@ struct ChainStatusEvent {
 
 pub async fn chain_status(
     State(state): State<CommonState>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
     let stream = tokio_stream::wrappers::IntervalStream::new(
         tokio::time::interval(Duration::from_secs(3)),
     )
@@ -34,23 +34,17 @@ pub async fn chain_status(
         let state = state.clone();
 
         async move {
-            let height = state
-                .chain_service
-                .find_last_processed_block()
-                .await
-                .expect("Failed to get last processed block");
-
-            let epoch = state
-                .chain_service
-                .find_last_processed_epoch()
-                .await
-                .expect("Failed to get last processed epoch");
-
-            let event =
-                serde_json::to_string(&ChainStatusEvent { height, epoch })
-                    .expect("Failed to serialize event");
-
-            Ok(Event::default().data(event))
+            let height = state.chain_service.find_last_processed_block().await;
+            let epoch = state.chain_service.find_last_processed_epoch().await;
+
+            match (height, epoch) {
+                (Ok(height), Ok(epoch)) => Event::default()
+                    .json_data(ChainStatusEvent { height, epoch }),
+                (Err(e), _) | (_, Err(e)) => {
+                    tracing::warn!("Failed to get chain status: {}", e);
+                    Ok(Event::default().comment("unavailable"))
+                }
+            }
         }
     });
 
@@ -61,24 +55,44 @@ pub async fn get_parameters(
     _headers: HeaderMap,
     State(state): State<CommonState>,
 ) -> Result<Json<Parameters>, ApiError> {
-    let parameters = state.chain_service.find_latest_parameters().await?;
+    let parameters = state
+        .cache_service
+        .get_or_insert(
+            "chain:parameters",
+            &(),
+            &[CrawlerNameDb::Parameters],
+            || state.chain_service.find_latest_parameters(),
+        )
+        .await?;
 
     Ok(Json(parameters))
 }
 
 pub async fn get_rpc_url(State(state): State<CommonState>) -> Json<RpcUrl> {
     Json(RpcUrl {
-        url: state.config.tendermint_url,
+        url: state.client.preferred_url(),
     })
 }
 
 pub async fn get_tokens(
     State(state): State<CommonState>,
 ) -> Result<Json<Vec<Token>>, ApiError> {
-    let tokens = state.chain_service.find_tokens().await?;
-    let res = tokens.into_iter().map(Token::from).collect();
+    let tokens = state
+        .cache_service
+        .get_or_insert(
+            "chain:token",
+            &(),
+            &[CrawlerNameDb::Chain, CrawlerNameDb::Transactions],
+            || async {
+                let tokens = state.chain_service.find_tokens().await?;
+                Ok::<_, ApiError>(
+                    tokens.into_iter().map(Token::from).collect::<Vec<_>>(),
+                )
+            },
+        )
+        .await?;
 
-    Ok(Json(res))
+    Ok(Json(tokens))
 }
 
 pub async fn get_last_processed_block(
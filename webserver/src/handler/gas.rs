@@ -3,9 +3,10 @@ use axum::extract::{Path, Query, State};
 use axum::http::HeaderMap;
 use axum_macros::debug_handler;
 
-use crate::dto::gas::GasEstimateQuery;
+use crate::dto::gas::{GasEstimateQuery, GasStatsQueryParams};
 use crate::error::api::ApiError;
-use crate::response::gas::{GasEstimate, GasPrice};
+use crate::response::gas::{GasEstimate, GasPrice, GasStats};
+use crate::response::utils::PaginatedResponse;
 use crate::state::common::CommonState;
 
 #[debug_handler]
@@ -49,15 +50,43 @@ pub async fn get_gas_estimate(
             query.shielding_transfer.unwrap_or(0),
             query.unshielding_transfer.unwrap_or(0),
             query.vote.unwrap_or(0),
-            query.ibc_unshielding_transfer.unwrap_or(0),
             query.ibc_shielding_transfer.unwrap_or(0),
+            query.ibc_unshielding_transfer.unwrap_or(0),
             query.ibc_transparent_transfer.unwrap_or(0),
             query.withdraw.unwrap_or(0),
             query.reveal_pk.unwrap_or(0),
             query.signatures.unwrap_or(2),
             query.tx_size.unwrap_or(0),
+            query.last_blocks,
+            query.last_days,
+            query.safety_margin,
         )
         .await?;
 
     Ok(Json(gas))
 }
+
+#[debug_handler]
+pub async fn get_gas_stats(
+    _headers: HeaderMap,
+    Query(query): Query<GasStatsQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<GasStats>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+
+    let (stats, total_pages, total_items) = state
+        .gas_service
+        .get_gas_stats(
+            query.interval,
+            query.token,
+            query.from_epoch,
+            query.to_epoch,
+            page,
+        )
+        .await?;
+
+    let response =
+        PaginatedResponse::new(stats, page, total_pages, total_items);
+
+    Ok(Json(response))
+}
@@ -1,12 +1,17 @@
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use axum::http::HeaderMap;
+use axum::http::{HeaderMap, HeaderName, header};
+use axum::response::IntoResponse;
 use axum_macros::debug_handler;
+use orm::crawler_state::CrawlerNameDb;
 
 use crate::dto::governance::{ProposalQueryParams, ProposalVotesQueryparams};
 use crate::error::api::ApiError;
 use crate::error::governance::GovernanceError;
-use crate::response::governance::{Proposal, ProposalVote};
+use crate::response::governance::{
+    Proposal, ProposalEvent, ProposalVote, ProposalVoteBreakdown,
+    ProposalWasmCode,
+};
 use crate::response::utils::PaginatedResponse;
 use crate::state::common::CommonState;
 
@@ -39,8 +44,19 @@ pub async fn get_all_governance_proposals(
     State(state): State<CommonState>,
 ) -> Result<Json<Vec<Proposal>>, ApiError> {
     let proposals = state
-        .gov_service
-        .find_all_governance_proposals(query.status, query.kind, query.pattern)
+        .cache_service
+        .get_or_insert(
+            "gov:proposal:all",
+            &query,
+            &[CrawlerNameDb::Chain, CrawlerNameDb::Governance],
+            || {
+                state.gov_service.find_all_governance_proposals(
+                    query.status.clone(),
+                    query.kind.clone(),
+                    query.pattern.clone(),
+                )
+            },
+        )
         .await?;
 
     Ok(Json(proposals))
@@ -83,6 +99,47 @@ pub async fn get_proposal_data_by_proposal_id(
     }
 }
 
+#[debug_handler]
+pub async fn get_proposal_wasm_code(
+    _headers: HeaderMap,
+    Path(proposal_id): Path<u64>,
+    State(state): State<CommonState>,
+) -> Result<Json<ProposalWasmCode>, ApiError> {
+    let wasm_code = state
+        .gov_service
+        .find_proposal_wasm_code(proposal_id)
+        .await?;
+
+    Ok(Json(wasm_code))
+}
+
+#[debug_handler]
+pub async fn download_proposal_wasm_code(
+    _headers: HeaderMap,
+    Path(proposal_id): Path<u64>,
+    State(state): State<CommonState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (wasm_code, bytes) = state
+        .gov_service
+        .find_proposal_wasm_code_bytes(proposal_id)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/wasm".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"proposal-{}.wasm\"",
+                    proposal_id
+                ),
+            ),
+            (HeaderName::from_static("x-wasm-sha256"), wasm_code.hash),
+        ],
+        bytes,
+    ))
+}
+
 #[debug_handler]
 pub async fn get_governance_proposal_votes(
     _headers: HeaderMap,
@@ -90,18 +147,56 @@ pub async fn get_governance_proposal_votes(
     Query(query): Query<ProposalVotesQueryparams>,
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<ProposalVote>>>, ApiError> {
+    if let Some(cursor) = query.cursor {
+        let (proposal_votes, next_cursor) = state
+            .gov_service
+            .find_governance_proposal_votes_by_cursor(proposal_id, cursor)
+            .await?;
+
+        return Ok(Json(PaginatedResponse::with_cursor(
+            proposal_votes,
+            next_cursor,
+        )));
+    }
+
     let page = query.page.unwrap_or(1);
-    let (proposal_votes, total_pages, total_votes) = state
+    let (proposal_votes, total_pages, total_votes, next_cursor) = state
         .gov_service
         .find_governance_proposal_votes(proposal_id, page)
         .await?;
 
-    Ok(Json(PaginatedResponse::new(
-        proposal_votes,
-        page,
-        total_pages,
-        total_votes,
-    )))
+    Ok(Json(
+        PaginatedResponse::new(proposal_votes, page, total_pages, total_votes)
+            .next_cursor(next_cursor),
+    ))
+}
+
+#[debug_handler]
+pub async fn get_governance_proposal_vote_breakdown(
+    _headers: HeaderMap,
+    Path(proposal_id): Path<u64>,
+    State(state): State<CommonState>,
+) -> Result<Json<ProposalVoteBreakdown>, ApiError> {
+    let breakdown = state
+        .gov_service
+        .find_governance_proposal_vote_breakdown(proposal_id)
+        .await?;
+
+    Ok(Json(breakdown))
+}
+
+#[debug_handler]
+pub async fn get_governance_proposal_timeline(
+    _headers: HeaderMap,
+    Path(proposal_id): Path<u64>,
+    State(state): State<CommonState>,
+) -> Result<Json<Vec<ProposalEvent>>, ApiError> {
+    let timeline = state
+        .gov_service
+        .find_governance_proposal_timeline(proposal_id)
+        .await?;
+
+    Ok(Json(timeline))
 }
 
 #[debug_handler]
@@ -4,14 +4,22 @@ use axum::http::HeaderMap;
 use axum_extra::extract::Query;
 use axum_macros::debug_handler;
 use bigdecimal::BigDecimal;
+use orm::crawler_state::CrawlerNameDb;
 
 use crate::dto::ibc::{
-    IbcRateLimit as IbcRateLimitDto, IbcTokenFlow as IbcTokenFlowDto,
+    IbcChannelsQueryParams, IbcPacketsQueryParams,
+    IbcRateLimit as IbcRateLimitDto,
+    IbcThroughputUtilization as IbcThroughputUtilizationDto,
+    IbcTokenFlow as IbcTokenFlowDto,
+    IbcTokenThroughput as IbcTokenThroughputDto,
 };
 use crate::error::api::ApiError;
 use crate::response::ibc::{
-    IbcAck, IbcRateLimit, IbcTokenFlow, IbcTokenThroughput,
+    IbcAck, IbcChannel, IbcPacket, IbcRateLimit, IbcThroughputUtilization,
+    IbcTokenFlow, IbcTokenThroughput,
 };
+use crate::response::utils::PaginatedResponse;
+use crate::service::ibc::IbcService;
 use crate::state::common::CommonState;
 
 #[debug_handler]
@@ -25,6 +33,64 @@ pub async fn get_ibc_status(
     Ok(Json(ibc_ack_status))
 }
 
+#[debug_handler]
+pub async fn get_ibc_channels(
+    _headers: HeaderMap,
+    Query(query): Query<IbcChannelsQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<Vec<IbcChannel>>, ApiError> {
+    let channels = state
+        .ibc_service
+        .get_channels(
+            query.counterparty_chain_id,
+            query.from_epoch,
+            query.to_epoch,
+        )
+        .await?;
+
+    Ok(Json(channels))
+}
+
+#[debug_handler]
+pub async fn get_ibc_in_flight_packets(
+    _headers: HeaderMap,
+    Path(channel): Path<String>,
+    Query(query): Query<IbcPacketsQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<IbcPacket>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+
+    let (packets, total_pages, total_items) = state
+        .ibc_service
+        .get_in_flight_packets(channel, page)
+        .await?;
+
+    let response =
+        PaginatedResponse::new(packets, page, total_pages, total_items);
+
+    Ok(Json(response))
+}
+
+#[debug_handler]
+pub async fn get_ibc_timed_out_packets(
+    _headers: HeaderMap,
+    Path(channel): Path<String>,
+    Query(query): Query<IbcPacketsQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<IbcPacket>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+
+    let (packets, total_pages, total_items) = state
+        .ibc_service
+        .get_timed_out_packets(channel, page)
+        .await?;
+
+    let response =
+        PaginatedResponse::new(packets, page, total_pages, total_items);
+
+    Ok(Json(response))
+}
+
 #[debug_handler]
 pub async fn get_ibc_rate_limits(
     Query(query): Query<IbcRateLimitDto>,
@@ -35,6 +101,11 @@ pub async fn get_ibc_rate_limits(
         .get_throughput_limits(
             query.token_address,
             query.throughput_limit.map(BigDecimal::from),
+            IbcService::epoch_range(
+                query.epoch,
+                query.from_epoch,
+                query.to_epoch,
+            ),
         )
         .await?;
 
@@ -47,8 +118,22 @@ pub async fn get_ibc_token_flows(
     State(state): State<CommonState>,
 ) -> Result<Json<Vec<IbcTokenFlow>>, ApiError> {
     let token_flows = state
-        .ibc_service
-        .get_token_flows(query.token_address)
+        .cache_service
+        .get_or_insert(
+            "ibc:token-flows",
+            &query,
+            &[CrawlerNameDb::Transactions],
+            || {
+                state.ibc_service.get_token_flows(
+                    query.token_address.clone(),
+                    IbcService::epoch_range(
+                        query.epoch,
+                        query.from_epoch,
+                        query.to_epoch,
+                    ),
+                )
+            },
+        )
         .await?;
 
     Ok(Json(token_flows))
@@ -58,9 +143,30 @@ pub async fn get_ibc_token_flows(
 pub async fn get_ibc_token_throughput(
     //_headers: HeaderMap,
     Path(token): Path<String>,
+    Query(query): Query<IbcTokenThroughputDto>,
     State(state): State<CommonState>,
 ) -> Result<Json<IbcTokenThroughput>, ApiError> {
-    let throughput = state.ibc_service.get_token_throughput(token).await?;
+    let throughput = state
+        .ibc_service
+        .get_token_throughput(token, query.epoch)
+        .await?;
 
     Ok(Json(throughput))
 }
+
+#[debug_handler]
+pub async fn get_ibc_throughput_utilization(
+    Query(query): Query<IbcThroughputUtilizationDto>,
+    State(state): State<CommonState>,
+) -> Result<Json<Vec<IbcThroughputUtilization>>, ApiError> {
+    let utilization = state
+        .ibc_service
+        .get_throughput_utilization(
+            query.token_address,
+            query.from_epoch,
+            query.to_epoch,
+        )
+        .await?;
+
+    Ok(Json(utilization))
+}
@@ -3,6 +3,7 @@ use axum::extract::State;
 use axum::http::HeaderMap;
 use axum_extra::extract::Query;
 use axum_macros::debug_handler;
+use orm::crawler_state::CrawlerNameDb;
 
 use crate::dto::masp::MaspAggregatesQueryParams;
 use crate::error::api::ApiError;
@@ -16,8 +17,17 @@ pub async fn get_masp_aggregates(
     Query(query): Query<MaspAggregatesQueryParams>,
 ) -> Result<Json<Vec<MaspPoolAggregateResponse>>, ApiError> {
     let masp_aggregates = state
-        .masp_service
-        .find_all_masp_aggregates(query.token)
+        .cache_service
+        .get_or_insert(
+            "masp:aggregates",
+            &query,
+            &[CrawlerNameDb::Transactions],
+            || {
+                state
+                    .masp_service
+                    .find_all_masp_aggregates(query.token.clone())
+            },
+        )
         .await?;
 
     Ok(Json(masp_aggregates))
@@ -1,3 +1,4 @@
+pub mod account;
 pub mod balance;
 pub mod block;
 pub mod chain;
@@ -9,4 +10,6 @@ pub mod masp;
 pub mod pgf;
 pub mod pk;
 pub mod pos;
+pub mod search;
+pub mod subscription;
 pub mod transaction;
@@ -3,15 +3,18 @@ use axum::extract::{Path, State};
 use axum::http::HeaderMap;
 use axum_extra::extract::Query;
 use axum_macros::debug_handler;
+use orm::crawler_state::CrawlerNameDb;
 
 use crate::dto::pos::{
-    AllValidatorsQueryParams, BondsDto, UnbondsDto, ValidatorQueryParams,
-    ValidatorStateDto, WithdrawsDto,
+    AllValidatorsQueryParams, BondsDto, RewardAprQueryParams,
+    RewardHistoryQueryParams, UnbondsDto, ValidatorHistoryQueryParams,
+    ValidatorQueryParams, ValidatorStateDto, WithdrawsDto,
 };
 use crate::error::api::ApiError;
 use crate::response::pos::{
-    Bond, MergedBond, Reward, TotalVotingPower, Unbond, ValidatorWithId,
-    Withdraw,
+    Bond, DelegatorApr, MergedBond, Reward, RewardLedgerEntry,
+    TotalVotingPower, Unbond, ValidatorApr, ValidatorEpochSnapshot,
+    ValidatorWithId, Withdraw,
 };
 use crate::response::utils::PaginatedResponse;
 use crate::state::common::CommonState;
@@ -41,11 +44,39 @@ pub async fn get_all_validators(
     State(state): State<CommonState>,
 ) -> Result<Json<Vec<ValidatorWithId>>, ApiError> {
     let states = query.state.unwrap_or_else(ValidatorStateDto::all);
-    let validators = state.pos_service.get_all_validators(states).await?;
+    let validators = state
+        .cache_service
+        .get_or_insert(
+            "pos:validator:all",
+            &states,
+            &[CrawlerNameDb::Chain, CrawlerNameDb::Pos],
+            || state.pos_service.get_all_validators(states.clone()),
+        )
+        .await?;
 
     Ok(Json(validators))
 }
 
+#[debug_handler]
+pub async fn get_validator_history(
+    _headers: HeaderMap,
+    Query(query): Query<ValidatorHistoryQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<ValidatorEpochSnapshot>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+
+    let (snapshots, total_pages, total_snapshots) = state
+        .pos_service
+        .get_validator_history(address, page)
+        .await?;
+
+    let response =
+        PaginatedResponse::new(snapshots, page, total_pages, total_snapshots);
+
+    Ok(Json(response))
+}
+
 #[debug_handler]
 pub async fn get_bonds(
     _headers: HeaderMap,
@@ -156,6 +187,63 @@ pub async fn get_rewards(
     Ok(Json(rewards))
 }
 
+#[debug_handler]
+pub async fn get_reward_history(
+    _headers: HeaderMap,
+    Query(query): Query<RewardHistoryQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<RewardLedgerEntry>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+
+    let (entries, total_pages, total_entries) = state
+        .pos_service
+        .get_reward_history(
+            address,
+            query.validator,
+            query.kind,
+            query.from_epoch,
+            query.to_epoch,
+            page,
+        )
+        .await?;
+
+    let response =
+        PaginatedResponse::new(entries, page, total_pages, total_entries);
+
+    Ok(Json(response))
+}
+
+#[debug_handler]
+pub async fn get_delegator_apr(
+    _headers: HeaderMap,
+    Query(query): Query<RewardAprQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<DelegatorApr>, ApiError> {
+    let apr = state
+        .pos_service
+        .get_delegator_apr(address, query.from_epoch, query.to_epoch)
+        .await?;
+
+    Ok(Json(apr))
+}
+
+#[debug_handler]
+pub async fn get_validator_apr(
+    _headers: HeaderMap,
+    Query(query): Query<RewardAprQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<ValidatorApr>, ApiError> {
+    let apr = state
+        .pos_service
+        .get_validator_apr(address, query.from_epoch, query.to_epoch)
+        .await?;
+
+    Ok(Json(apr))
+}
+
 #[debug_handler]
 pub async fn get_total_voting_power(
     _headers: HeaderMap,
@@ -0,0 +1,21 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum_extra::extract::Query;
+use axum_macros::debug_handler;
+
+use crate::dto::search::SearchQueryParams;
+use crate::error::api::ApiError;
+use crate::response::search::SearchResult;
+use crate::state::common::CommonState;
+
+#[debug_handler]
+pub async fn search(
+    _headers: HeaderMap,
+    Query(query): Query<SearchQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<Vec<SearchResult>>, ApiError> {
+    let results = state.search_service.search(query.q).await?;
+
+    Ok(Json(results))
+}
@@ -0,0 +1,61 @@
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::sse::{Event, KeepAlive};
+use axum::response::{IntoResponse, Sse};
+use axum_extra::extract::Query;
+use futures::{Stream, StreamExt};
+
+use crate::dto::subscription::SubscriptionQueryParams;
+use crate::response::subscription::SubscriptionEvent;
+use crate::state::common::CommonState;
+
+pub async fn subscribe_sse(
+    Query(query): Query<SubscriptionQueryParams>,
+    State(state): State<CommonState>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let stream = state
+        .subscription_service
+        .subscribe(query)
+        .map(|event| Event::default().event(event.name()).json_data(&event));
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+pub async fn subscribe_ws(
+    ws: WebSocketUpgrade,
+    Query(query): Query<SubscriptionQueryParams>,
+    State(state): State<CommonState>,
+) -> impl IntoResponse {
+    let stream = state.subscription_service.subscribe(query);
+
+    ws.on_upgrade(move |socket| forward_events(socket, stream))
+}
+
+async fn forward_events(
+    mut socket: WebSocket,
+    stream: impl Stream<Item = SubscriptionEvent>,
+) {
+    let mut stream = std::pin::pin!(stream);
+
+    loop {
+        tokio::select! {
+            event = stream.next() => {
+                let Some(event) = event else {
+                    break;
+                };
+                let Ok(event) = serde_json::to_string(&event) else {
+                    continue;
+                };
+                if socket.send(Message::Text(event)).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,15 @@
 use axum::Json;
+use axum::body::Body;
 use axum::extract::{Path, State};
-use axum::http::HeaderMap;
+use axum::http::{HeaderMap, header};
+use axum::response::IntoResponse;
 use axum_extra::extract::Query;
 use axum_macros::debug_handler;
 
-use crate::dto::transaction::TransactionHistoryQueryParams;
+use crate::dto::transaction::{
+    ExportFormat, TransactionHistoryExportQueryParams,
+    TransactionHistoryQueryParams,
+};
 use crate::error::api::ApiError;
 use crate::error::transaction::TransactionError;
 use crate::response::transaction::{
@@ -70,19 +75,57 @@ pub async fn get_transaction_history(
     Query(query): Query<TransactionHistoryQueryParams>,
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<TransactionHistory>>>, ApiError> {
+    if let Some(cursor) = query.cursor.clone() {
+        let (transactions, next_cursor) = state
+            .transaction_service
+            .get_addresses_history_by_cursor(query, cursor)
+            .await?;
+
+        return Ok(Json(PaginatedResponse::with_cursor(
+            transactions,
+            next_cursor,
+        )));
+    }
+
     let page = query.page.unwrap_or(1);
 
-    let (transactions, total_pages, total_items) = state
+    let (transactions, total_pages, total_items, next_cursor) = state
         .transaction_service
-        .get_addresses_history(query.addresses, page)
+        .get_addresses_history(query, page)
         .await?;
 
     let response =
-        PaginatedResponse::new(transactions, page, total_pages, total_items);
+        PaginatedResponse::new(transactions, page, total_pages, total_items)
+            .next_cursor(next_cursor);
 
     Ok(Json(response))
 }
 
+#[debug_handler]
+pub async fn export_transaction_history(
+    _headers: HeaderMap,
+    Query(query): Query<TransactionHistoryExportQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (content_type, extension) = match query.format {
+        ExportFormat::Csv => ("text/csv", "csv"),
+        ExportFormat::Jsonl => ("application/x-ndjson", "jsonl"),
+    };
+
+    let stream = state.transaction_service.export_addresses_history(query)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"history.{}\"", extension),
+            ),
+        ],
+        Body::from_stream(stream),
+    ))
+}
+
 fn is_valid_hash(hash: &str) -> Result<(), TransactionError> {
     if hash.len().eq(&64) {
         Ok(())
@@ -0,0 +1,49 @@
+use axum::async_trait;
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
+use orm::accounts::AccountDb;
+use orm::schema::accounts;
+
+use crate::appstate::AppState;
+
+#[derive(Clone)]
+pub struct AccountRepository {
+    pub(crate) app_state: AppState,
+}
+
+#[async_trait]
+pub trait AccountRepositoryTrait {
+    fn new(app_state: AppState) -> Self;
+
+    async fn find_account_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<AccountDb>, String>;
+}
+
+#[async_trait]
+impl AccountRepositoryTrait for AccountRepository {
+    fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+
+    async fn find_account_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<AccountDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            accounts::table
+                .filter(accounts::dsl::address.eq(address))
+                .select(AccountDb::as_select())
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
@@ -1,10 +1,13 @@
 use axum::async_trait;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
-use orm::balances::BalanceDb;
-use orm::schema::token;
-use orm::token::TokenDb;
+use diesel::{
+    ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use orm::balances::{BalanceChangeDb, BalanceDb};
+use orm::schema::{balance_changes, blocks, ibc_token, token};
+use orm::token::{IbcTokenDb, TokenDb};
 use orm::views::balances;
 
+use super::utils::{Paginate, PaginatedResponseDb};
 use crate::appstate::AppState;
 
 #[derive(Clone)]
@@ -16,12 +19,30 @@ pub struct BalanceRepo {
 pub trait BalanceRepoTrait {
     fn new(app_state: AppState) -> Self;
 
-    async fn get_all_token(&self) -> Result<Vec<TokenDb>, String>;
+    async fn get_all_token(
+        &self,
+    ) -> Result<Vec<(TokenDb, Option<IbcTokenDb>)>, String>;
 
     async fn get_address_balances(
         &self,
         address: String,
     ) -> Result<Vec<BalanceDb>, String>;
+
+    async fn get_address_balances_at_height(
+        &self,
+        address: String,
+        height: i32,
+    ) -> Result<Vec<BalanceDb>, String>;
+
+    async fn get_address_balance_history(
+        &self,
+        address: String,
+        token: Option<String>,
+        page: i64,
+    ) -> Result<
+        PaginatedResponseDb<(BalanceChangeDb, Option<chrono::NaiveDateTime>)>,
+        String,
+    >;
 }
 
 #[async_trait]
@@ -47,13 +68,99 @@ impl BalanceRepoTrait for BalanceRepo {
         .map_err(|e| e.to_string())
     }
 
-    async fn get_all_token(&self) -> Result<Vec<TokenDb>, String> {
+    /// Gets the latest balance change of each token at or before the given
+    /// height
+    async fn get_address_balances_at_height(
+        &self,
+        address: String,
+        height: i32,
+    ) -> Result<Vec<BalanceDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            balance_changes::table
+                .filter(balance_changes::dsl::owner.eq(address))
+                .filter(balance_changes::dsl::height.le(height))
+                .distinct_on(balance_changes::dsl::token)
+                .order((
+                    balance_changes::dsl::token,
+                    balance_changes::dsl::height.desc(),
+                ))
+                .select((
+                    balance_changes::dsl::owner,
+                    balance_changes::dsl::token,
+                    balance_changes::dsl::raw_amount,
+                ))
+                .load::<BalanceDb>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn get_address_balance_history(
+        &self,
+        address: String,
+        token: Option<String>,
+        page: i64,
+    ) -> Result<
+        PaginatedResponseDb<(BalanceChangeDb, Option<chrono::NaiveDateTime>)>,
+        String,
+    > {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query =
+                balance_changes::table
+                    .inner_join(blocks::table.on(
+                        balance_changes::dsl::height.eq(blocks::dsl::height),
+                    ))
+                    .filter(balance_changes::dsl::owner.eq(address))
+                    .into_boxed();
+
+            if let Some(token) = token {
+                query = query.filter(balance_changes::dsl::token.eq(token));
+            }
+
+            query
+                .order((
+                    balance_changes::dsl::height.desc(),
+                    balance_changes::dsl::token,
+                ))
+                .select((
+                    (
+                        balance_changes::dsl::owner,
+                        balance_changes::dsl::token,
+                        balance_changes::dsl::raw_amount,
+                        balance_changes::dsl::height,
+                    ),
+                    blocks::dsl::timestamp,
+                ))
+                .paginate(page)
+                .load_and_count_pages::<(
+                    BalanceChangeDb,
+                    Option<chrono::NaiveDateTime>,
+                )>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn get_all_token(
+        &self,
+    ) -> Result<Vec<(TokenDb, Option<IbcTokenDb>)>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
             token::table
-                .distinct()
-                .select(TokenDb::as_select())
+                .left_join(
+                    ibc_token::table.on(token::address.eq(ibc_token::address)),
+                )
+                .select((
+                    TokenDb::as_select(),
+                    Option::<IbcTokenDb>::as_select(),
+                ))
                 .get_results(conn)
         })
         .await
@@ -1,8 +1,10 @@
 use axum::async_trait;
+use chrono::NaiveDateTime;
 use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
-use orm::blocks::BlockDb;
-use orm::schema::blocks;
+use orm::blocks::{BlockDb, BlockMismatchDb};
+use orm::schema::{block_mismatches, blocks};
 
+use super::utils::{Paginate, PaginatedResponseDb};
 use crate::appstate::AppState;
 
 #[derive(Clone)]
@@ -21,8 +23,13 @@ pub trait BlockRepositoryTrait {
 
     async fn find_block_by_timestamp(
         &self,
-        timestamp: i64,
+        timestamp: NaiveDateTime,
     ) -> Result<Option<BlockDb>, String>;
+
+    async fn find_block_mismatches(
+        &self,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<BlockMismatchDb>, String>;
 }
 
 #[async_trait]
@@ -51,12 +58,9 @@ impl BlockRepositoryTrait for BlockRepository {
     /// Gets the last block preceeding the given timestamp
     async fn find_block_by_timestamp(
         &self,
-        timestamp: i64,
+        timestamp: NaiveDateTime,
     ) -> Result<Option<BlockDb>, String> {
         let conn = self.app_state.get_db_connection().await;
-        let timestamp = chrono::DateTime::from_timestamp(timestamp, 0)
-            .expect("Invalid timestamp")
-            .naive_utc();
 
         conn.interact(move |conn| {
             blocks::table
@@ -69,4 +73,22 @@ impl BlockRepositoryTrait for BlockRepository {
         .await
         .map_err(|e| e.to_string())
     }
+
+    async fn find_block_mismatches(
+        &self,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<BlockMismatchDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            block_mismatches::table
+                .order(block_mismatches::id.desc())
+                .select(BlockMismatchDb::as_select())
+                .paginate(page)
+                .load_and_count_pages(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
 }
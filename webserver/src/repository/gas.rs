@@ -1,16 +1,76 @@
 use axum::async_trait;
 use bigdecimal::BigDecimal;
-use diesel::dsl::{avg, count, max, min};
-use diesel::sql_types::{BigInt, Integer, Nullable, Numeric};
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::dsl::{avg, count, max, min, sql};
+use diesel::sql_types::{BigInt, Integer, Nullable, Numeric, Text};
 use diesel::{
     ExpressionMethods, IntoSql, JoinOnDsl, QueryDsl, RunQueryDsl,
     SelectableHelper,
 };
-use orm::gas::GasPriceDb;
-use orm::schema::{gas_estimations, gas_price, wrapper_transactions};
+use orm::gas::{GasPriceDb, GasPriceEpochDb};
+use orm::schema::{
+    blocks, gas_estimations, gas_price, gas_price_per_epoch,
+    wrapper_transactions,
+};
+use orm::transactions::TransactionResultDb;
 
+use super::utils::{Paginate, PaginatedResponseDb};
 use crate::appstate::AppState;
 
+/// min, max, avg, p50, p90, p99 and count of the gas used by the matching
+/// transactions
+pub type GasEstimateStatsDb = (
+    Option<i32>,
+    Option<i32>,
+    Option<BigDecimal>,
+    Option<i32>,
+    Option<i32>,
+    Option<i32>,
+    i64,
+);
+
+/// token, period, number of transactions, fees paid, median amount per gas
+/// unit, gas used, gas limit and gas used over gas limit
+pub type GasStatsDb = (
+    String,
+    Option<String>,
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+    Option<String>,
+);
+
+#[derive(Clone, Copy, Debug)]
+pub enum GasStatsPeriod {
+    Day,
+    Epoch,
+}
+
+/// Restricts the gas estimates to the transactions indexed recently
+#[derive(Clone, Copy, Debug)]
+pub enum GasEstimateWindow {
+    All,
+    LastBlocks(u64),
+    LastDays(u64),
+}
+
+/// Returns the power of two bucket containing `value`, e.g. 1000 falls in
+/// [512, 1023]
+fn bucket_bounds(value: u64) -> (i32, i32) {
+    let Some(exponent) = value.checked_ilog2() else {
+        return (0, 0);
+    };
+    let lower_bound = 1_u64 << exponent;
+    let upper_bound = lower_bound.saturating_add(lower_bound - 1);
+
+    (
+        i32::try_from(lower_bound).unwrap_or(i32::MAX),
+        i32::try_from(upper_bound).unwrap_or(i32::MAX),
+    )
+}
+
 #[derive(Clone)]
 pub struct GasRepository {
     pub(crate) app_state: AppState,
@@ -46,7 +106,24 @@ pub trait GasRepositoryTrait {
         reveal_pk: u64,
         signatures: u64,
         tx_size: u64,
-    ) -> Result<(Option<i32>, Option<i32>, Option<BigDecimal>, i64), String>;
+        window: GasEstimateWindow,
+    ) -> Result<GasEstimateStatsDb, String>;
+
+    async fn find_gas_stats(
+        &self,
+        period: GasStatsPeriod,
+        token: Option<String>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<GasStatsDb>, String>;
+
+    async fn find_gas_price_history(
+        &self,
+        token: Option<String>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<GasPriceEpochDb>, String>;
 }
 
 #[async_trait]
@@ -104,34 +181,52 @@ impl GasRepositoryTrait for GasRepository {
         reveal_pk: u64,
         signatures: u64,
         tx_size: u64,
-    ) -> Result<(Option<i32>, Option<i32>, Option<BigDecimal>, i64), String>
-    {
-        const TX_SIZE_WINDOW_UPPERBOUND_PCT: f64 = 0.10;
-        const TX_SIGNATURES_WINDOW_UPPERBOUND: u64 = 5;
-
+        window: GasEstimateWindow,
+    ) -> Result<GasEstimateStatsDb, String> {
         let (signature_lower_bound, signature_upper_bound) = if signatures == 0
         {
             (2_i32, 5_i32)
         } else {
-            (
-                signatures as i32,
-                (signatures + TX_SIGNATURES_WINDOW_UPPERBOUND) as i32,
-            )
+            bucket_bounds(signatures)
         };
 
         let (tx_size_lower_bound, tx_size_upper_bound) = if tx_size == 0 {
             (0_i32, 100000_i32)
         } else {
-            (
-                tx_size as i32,
-                (tx_size as f64 * (1f64 + TX_SIZE_WINDOW_UPPERBOUND_PCT)).ceil()
-                    as i32,
-            )
+            bucket_bounds(tx_size)
         };
 
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
+            let from_height = match window {
+                GasEstimateWindow::All => None,
+                GasEstimateWindow::LastBlocks(last_blocks) => blocks::table
+                    .select(max(blocks::height))
+                    .first::<Option<i32>>(conn)?
+                    .map(|height| {
+                        let last_blocks =
+                            i32::try_from(last_blocks).unwrap_or(i32::MAX);
+                        height.saturating_sub(last_blocks).saturating_add(1)
+                    }),
+                GasEstimateWindow::LastDays(last_days) => {
+                    let from_timestamp = i64::try_from(last_days)
+                        .ok()
+                        .and_then(Duration::try_days)
+                        .and_then(|days| {
+                            Utc::now().naive_utc().checked_sub_signed(days)
+                        })
+                        .unwrap_or(NaiveDateTime::MIN);
+
+                    // No block in the window means no estimate either
+                    let height = blocks::table
+                        .filter(blocks::timestamp.ge(from_timestamp))
+                        .select(min(blocks::height))
+                        .first::<Option<i32>>(conn)?;
+                    Some(height.unwrap_or(i32::MAX))
+                }
+            };
+
             gas_estimations::table
                 .filter(gas_estimations::dsl::bond.eq(bond as i32))
                 .filter(
@@ -159,20 +254,39 @@ impl GasRepositoryTrait for GasRepository {
                         .eq(unshielding_transfer as i32),
                 )
                 .filter(gas_estimations::dsl::vote_proposal.eq(vote as i32))
-                .filter(gas_estimations::dsl::ibc_shielding_transfer.eq(ibc_shielding_transfer as i32))
-                .filter(gas_estimations::dsl::ibc_unshielding_transfer.eq(ibc_unshielding_transfer as i32))
-                .filter(gas_estimations::dsl::ibc_msg_transfer.eq(ibc_transparent_transfer as i32))
+                .filter(
+                    gas_estimations::dsl::ibc_shielding_transfer
+                        .eq(ibc_shielding_transfer as i32),
+                )
+                .filter(
+                    gas_estimations::dsl::ibc_unshielding_transfer
+                        .eq(ibc_unshielding_transfer as i32),
+                )
+                .filter(
+                    gas_estimations::dsl::ibc_msg_transfer
+                        .eq(ibc_transparent_transfer as i32),
+                )
                 .filter(gas_estimations::dsl::withdraw.eq(withdraw as i32))
                 .filter(gas_estimations::dsl::reveal_pk.eq(reveal_pk as i32))
-                // For the signatures and the tx size we look for similar indexed txs in a certain range
-                .filter(gas_estimations::dsl::signatures.between(signature_lower_bound, signature_upper_bound))
-                .filter(gas_estimations::dsl::tx_size.between(tx_size_lower_bound, tx_size_upper_bound))
+                // For the signatures and the tx size we look for similar
+                // indexed txs in the same bucket
+                .filter(
+                    gas_estimations::dsl::signatures
+                        .between(signature_lower_bound, signature_upper_bound),
+                )
+                .filter(
+                    gas_estimations::dsl::tx_size
+                        .between(tx_size_lower_bound, tx_size_upper_bound),
+                )
                 .inner_join(
                     wrapper_transactions::table
                         .on(gas_estimations::dsl::wrapper_id
                             .eq(wrapper_transactions::dsl::id)),
                 )
-                .limit(100)
+                .filter(
+                    wrapper_transactions::dsl::block_height
+                        .ge(from_height.unwrap_or(0)),
+                )
                 .select((
                     min(wrapper_transactions::dsl::gas_used)
                         .into_sql::<Nullable<Integer>>(),
@@ -180,15 +294,163 @@ impl GasRepositoryTrait for GasRepository {
                         .into_sql::<Nullable<Integer>>(),
                     avg(wrapper_transactions::dsl::gas_used)
                         .into_sql::<Nullable<Numeric>>(),
+                    sql::<Nullable<Integer>>(
+                        "percentile_disc(0.5) WITHIN GROUP (ORDER BY \
+                         wrapper_transactions.gas_used)",
+                    ),
+                    sql::<Nullable<Integer>>(
+                        "percentile_disc(0.9) WITHIN GROUP (ORDER BY \
+                         wrapper_transactions.gas_used)",
+                    ),
+                    sql::<Nullable<Integer>>(
+                        "percentile_disc(0.99) WITHIN GROUP (ORDER BY \
+                         wrapper_transactions.gas_used)",
+                    ),
                     count(wrapper_transactions::dsl::gas_used)
                         .into_sql::<BigInt>(),
                 ))
-                .get_result::<(Option<i32>, Option<i32>, Option<BigDecimal>, i64)>(
-                    conn,
+                .get_result::<GasEstimateStatsDb>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_gas_stats(
+        &self,
+        period: GasStatsPeriod,
+        token: Option<String>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<GasStatsDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let (period_select, period_group) = match period {
+                GasStatsPeriod::Day => (
+                    "TO_CHAR(blocks.timestamp, 'YYYY-MM-DD')",
+                    "TO_CHAR(blocks.timestamp, 'YYYY-MM-DD')",
+                ),
+                GasStatsPeriod::Epoch => {
+                    ("CAST(blocks.epoch AS TEXT)", "blocks.epoch")
+                }
+            };
+
+            // NB: We're using a raw select because `CAST` and ordered-set
+            // aggregates are not available in the diesel dsl. Fees are charged
+            // on the gas used, like in the transaction history.
+            let select_statement = sql::<(
+                Text,
+                Nullable<Text>,
+                Text,
+                Text,
+                Nullable<Text>,
+                Text,
+                Text,
+                Nullable<Text>,
+            )>(&format!(
+                "wrapper_transactions.fee_token, {period_select}, \
+                 CAST(COUNT(*) AS TEXT), \
+                 CAST(COALESCE(SUM(wrapper_transactions.gas_used * \
+                 CAST(wrapper_transactions.amount_per_gas_unit AS NUMERIC)), \
+                 0) AS TEXT), CAST(percentile_disc(0.5) WITHIN GROUP (ORDER \
+                 BY CAST(wrapper_transactions.amount_per_gas_unit AS \
+                 NUMERIC)) AS TEXT), \
+                 CAST(COALESCE(SUM(wrapper_transactions.gas_used), 0) AS \
+                 TEXT), CAST(SUM(CAST(wrapper_transactions.gas_limit AS \
+                 NUMERIC)) AS TEXT), \
+                 CAST(ROUND(SUM(wrapper_transactions.gas_used) / \
+                 NULLIF(SUM(CAST(wrapper_transactions.gas_limit AS NUMERIC)), \
+                 0), 4) AS TEXT)"
+            ));
+
+            // Rejected wrappers don't pay any fee
+            let mut query = wrapper_transactions::table
+                .inner_join(blocks::table)
+                .filter(
+                    wrapper_transactions::dsl::exit_code
+                        .eq(TransactionResultDb::Applied),
                 )
+                .group_by(sql::<(Text, Nullable<Text>)>(&format!(
+                    "wrapper_transactions.fee_token, {period_group}"
+                )))
+                .select(select_statement)
+                .order(sql::<Nullable<Text>>(&format!(
+                    "{period_group} DESC, wrapper_transactions.fee_token ASC"
+                )))
+                .into_boxed();
+
+            if let Some(token) = token {
+                query = query
+                    .filter(wrapper_transactions::dsl::fee_token.eq(token));
+            }
+
+            if let Some(from_epoch) = from_epoch {
+                query = query.filter(blocks::dsl::epoch.ge(from_epoch));
+            }
+
+            if let Some(to_epoch) = to_epoch {
+                query = query.filter(blocks::dsl::epoch.le(to_epoch));
+            }
+
+            query.paginate(page).load_and_count_pages(conn)
         })
         .await
         .map_err(|e| e.to_string())?
         .map_err(|e| e.to_string())
     }
+
+    async fn find_gas_price_history(
+        &self,
+        token: Option<String>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<GasPriceEpochDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = gas_price_per_epoch::table.into_boxed();
+
+            if let Some(token) = token {
+                query = query.filter(gas_price_per_epoch::dsl::token.eq(token));
+            }
+
+            if let Some(from_epoch) = from_epoch {
+                query = query
+                    .filter(gas_price_per_epoch::dsl::epoch.ge(from_epoch));
+            }
+
+            if let Some(to_epoch) = to_epoch {
+                query =
+                    query.filter(gas_price_per_epoch::dsl::epoch.le(to_epoch));
+            }
+
+            query.select(GasPriceEpochDb::as_select()).get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_bounds_groups_values_by_power_of_two() {
+        assert_eq!(bucket_bounds(1), (1, 1));
+        assert_eq!(bucket_bounds(2), (2, 3));
+        assert_eq!(bucket_bounds(3), (2, 3));
+        assert_eq!(bucket_bounds(1000), (512, 1023));
+    }
+
+    #[test]
+    fn bucket_bounds_does_not_overflow() {
+        assert_eq!(bucket_bounds(0), (0, 0));
+        assert_eq!(bucket_bounds(u64::MAX), (i32::MAX, i32::MAX));
+        assert_eq!(bucket_bounds(1 << 31), (i32::MAX, i32::MAX));
+        assert_eq!(bucket_bounds((1 << 31) - 1), (1 << 30, i32::MAX));
+    }
 }
@@ -1,6 +1,8 @@
 use axum::async_trait;
-use diesel::dsl::IntoBoxed;
+use bigdecimal::BigDecimal;
+use diesel::dsl::{IntoBoxed, count_star, sql, sum};
 use diesel::pg::Pg;
+use diesel::sql_types::BigInt;
 use diesel::{
     BoolExpressionMethods, ExpressionMethods, PgTextExpressionMethods,
     QueryDsl, RunQueryDsl, SelectableHelper,
@@ -8,12 +10,23 @@ use diesel::{
 use orm::governance_proposal::{
     GovernanceProposalDb, GovernanceProposalKindDb, GovernanceProposalResultDb,
 };
-use orm::governance_votes::GovernanceProposalVoteDb;
-use orm::schema::{governance_proposals, governance_votes};
+use orm::governance_votes::{GovernanceProposalVoteDb, GovernanceVoteKindDb};
+use orm::proposal_events::ProposalEventDb;
+use orm::schema::{governance_proposals, governance_votes, proposal_events};
 
 use crate::appstate::AppState;
 use crate::repository::utils::{Paginate, PaginatedResponseDb};
 
+/// Voting power of the votes of a proposal grouped by voter kind and vote:
+/// is_validator, vote, voting power, voters, overriding voters
+pub type GovernanceVotePowerDb = (
+    Option<bool>,
+    GovernanceVoteKindDb,
+    Option<BigDecimal>,
+    i64,
+    i64,
+);
+
 #[derive(Clone)]
 pub struct GovernanceRepo {
     pub(crate) app_state: AppState,
@@ -49,6 +62,13 @@ pub trait GovernanceRepoTrait {
         page: i64,
     ) -> Result<PaginatedResponseDb<GovernanceProposalVoteDb>, String>;
 
+    async fn find_governance_proposal_votes_by_cursor(
+        &self,
+        proposal_id: i32,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<GovernanceProposalVoteDb>, String>;
+
     async fn find_governance_proposal_votes_by_address(
         &self,
         proposal_id: i32,
@@ -59,6 +79,16 @@ pub trait GovernanceRepoTrait {
         &self,
         voter_address: String,
     ) -> Result<Vec<GovernanceProposalVoteDb>, String>;
+
+    async fn find_governance_proposal_vote_power(
+        &self,
+        proposal_id: i32,
+    ) -> Result<Vec<GovernanceVotePowerDb>, String>;
+
+    async fn find_proposal_events(
+        &self,
+        proposal_id: i32,
+    ) -> Result<Vec<ProposalEventDb>, String>;
 }
 
 #[async_trait]
@@ -135,8 +165,9 @@ impl GovernanceRepoTrait for GovernanceRepo {
 
         conn.interact(move |conn| {
             governance_votes::table
-                .filter(governance_votes::dsl::id.eq(proposal_id))
+                .filter(governance_votes::dsl::proposal_id.eq(proposal_id))
                 .select(GovernanceProposalVoteDb::as_select())
+                .order(governance_votes::dsl::id.asc())
                 .paginate(page)
                 .load_and_count_pages(conn)
         })
@@ -145,6 +176,34 @@ impl GovernanceRepoTrait for GovernanceRepo {
         .map_err(|e| e.to_string())
     }
 
+    async fn find_governance_proposal_votes_by_cursor(
+        &self,
+        proposal_id: i32,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<GovernanceProposalVoteDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = governance_votes::table
+                .filter(governance_votes::dsl::proposal_id.eq(proposal_id))
+                .into_boxed();
+
+            if let Some(id) = after {
+                query = query.filter(governance_votes::dsl::id.gt(id));
+            }
+
+            query
+                .select(GovernanceProposalVoteDb::as_select())
+                .order(governance_votes::dsl::id.asc())
+                .limit(limit)
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
     async fn find_governance_proposal_votes_by_address(
         &self,
         proposal_id: i32,
@@ -154,7 +213,7 @@ impl GovernanceRepoTrait for GovernanceRepo {
 
         conn.interact(move |conn| {
             governance_votes::table
-                .filter(governance_votes::dsl::id.eq(proposal_id).and(
+                .filter(governance_votes::dsl::proposal_id.eq(proposal_id).and(
                     governance_votes::dsl::voter_address.eq(voter_address),
                 ))
                 .select(GovernanceProposalVoteDb::as_select())
@@ -181,6 +240,61 @@ impl GovernanceRepoTrait for GovernanceRepo {
         .map_err(|e| e.to_string())?
         .map_err(|e| e.to_string())
     }
+
+    async fn find_governance_proposal_vote_power(
+        &self,
+        proposal_id: i32,
+    ) -> Result<Vec<GovernanceVotePowerDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            governance_votes::table
+                .filter(
+                    governance_votes::dsl::proposal_id
+                        .eq(proposal_id)
+                        .and(governance_votes::dsl::voting_power.is_not_null()),
+                )
+                .group_by((
+                    governance_votes::dsl::is_validator,
+                    governance_votes::dsl::kind,
+                ))
+                .select((
+                    governance_votes::dsl::is_validator,
+                    governance_votes::dsl::kind,
+                    sum(governance_votes::dsl::voting_power),
+                    count_star(),
+                    sql::<BigInt>(
+                        "COUNT(*) FILTER (WHERE overrides_validator)",
+                    ),
+                ))
+                .load::<GovernanceVotePowerDb>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_proposal_events(
+        &self,
+        proposal_id: i32,
+    ) -> Result<Vec<ProposalEventDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            proposal_events::table
+                .filter(proposal_events::dsl::proposal_id.eq(proposal_id))
+                .select(ProposalEventDb::as_select())
+                .order((
+                    proposal_events::dsl::epoch.asc(),
+                    proposal_events::dsl::kind.asc(),
+                    proposal_events::dsl::id.asc(),
+                ))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
 }
 
 #[allow(clippy::needless_lifetimes)]
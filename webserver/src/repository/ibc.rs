@@ -1,14 +1,30 @@
 use axum::async_trait;
 use bigdecimal::BigDecimal;
 use diesel::{
-    ExpressionMethods, NullableExpressionMethods, QueryDsl, RunQueryDsl,
-    SelectableHelper,
+    BoolExpressionMethods, ExpressionMethods, NullableExpressionMethods,
+    QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use orm::ibc::{
+    IbcChannelDb, IbcPacketDb, IbcPacketDirectionDb, IbcPacketStatusDb,
+};
+use orm::schema::{
+    ibc_channels, ibc_packets, ibc_rate_limits, ibc_token, ibc_token_flows,
 };
-use orm::ibc::IbcAckDb;
-use orm::schema::{ibc_ack, ibc_rate_limits, ibc_token_flows};
 
+use super::utils::{Paginate, PaginatedResponseDb};
 use crate::appstate::AppState;
 
+/// Token address, ibc trace, and total deposit and withdraw of an ibc token
+pub type TraceFlow = (String, String, Option<BigDecimal>, Option<BigDecimal>);
+
+/// Epochs of the per-epoch ibc tables to query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EpochRange {
+    Latest,
+    /// Inclusive bounds, a missing bound is unbounded
+    Between(Option<i32>, Option<i32>),
+}
+
 #[derive(Clone)]
 pub struct IbcRepository {
     pub(crate) app_state: AppState,
@@ -18,26 +34,60 @@ pub struct IbcRepository {
 pub trait IbcRepositoryTrait {
     fn new(app_state: AppState) -> Self;
 
-    async fn find_ibc_ack(
+    async fn find_ibc_packet_by_tx_id(
+        &self,
+        tx_id: String,
+    ) -> Result<Option<IbcPacketDb>, String>;
+
+    async fn find_in_flight_packets(
         &self,
-        id: String,
-    ) -> Result<Option<IbcAckDb>, String>;
+        channel: String,
+        now: i64,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<IbcPacketDb>, String>;
+
+    async fn find_timed_out_packets(
+        &self,
+        channel: String,
+        now: i64,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<IbcPacketDb>, String>;
 
     async fn get_throughput_limits(
         &self,
         token_address: Option<String>,
         matching_rate_limit: Option<BigDecimal>,
-    ) -> Result<Vec<(String, String)>, String>;
+        epochs: EpochRange,
+    ) -> Result<Vec<(String, String, String)>, String>;
 
     async fn get_token_flows(
         &self,
         token_address: Option<String>,
-    ) -> Result<Vec<(String, String, String)>, String>;
+        epochs: EpochRange,
+    ) -> Result<Vec<(String, String, String, String)>, String>;
 
     async fn get_token_throughput(
         &self,
         token_address: String,
     ) -> Result<(String, String), String>;
+
+    async fn get_throughput_utilization(
+        &self,
+        token_address: Option<String>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<(String, String, String, String, Option<String>)>, String>;
+
+    async fn find_ibc_channels(
+        &self,
+        counterparty_chain_id: Option<String>,
+    ) -> Result<Vec<IbcChannelDb>, String>;
+
+    async fn get_trace_flows(
+        &self,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<TraceFlow>, String>;
 }
 
 #[async_trait]
@@ -46,16 +96,20 @@ impl IbcRepositoryTrait for IbcRepository {
         Self { app_state }
     }
 
-    async fn find_ibc_ack(
+    async fn find_ibc_packet_by_tx_id(
         &self,
-        id: String,
-    ) -> Result<Option<IbcAckDb>, String> {
+        tx_id: String,
+    ) -> Result<Option<IbcPacketDb>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
-            ibc_ack::table
-                .filter(ibc_ack::dsl::tx_hash.eq(id))
-                .select(IbcAckDb::as_select())
+            ibc_packets::table
+                .filter(
+                    ibc_packets::dsl::send_tx_id
+                        .eq(&tx_id)
+                        .or(ibc_packets::dsl::recv_tx_id.eq(&tx_id)),
+                )
+                .select(IbcPacketDb::as_select())
                 .first(conn)
                 .ok()
         })
@@ -63,11 +117,79 @@ impl IbcRepositoryTrait for IbcRepository {
         .map_err(|e| e.to_string())
     }
 
+    async fn find_in_flight_packets(
+        &self,
+        channel: String,
+        now: i64,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<IbcPacketDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            // A timeout timestamp of 0 means the packet never times out
+            ibc_packets::table
+                .filter(
+                    ibc_packets::dsl::direction
+                        .eq(IbcPacketDirectionDb::Outgoing),
+                )
+                .filter(ibc_packets::dsl::source_channel.eq(channel))
+                .filter(ibc_packets::dsl::status.eq(IbcPacketStatusDb::Pending))
+                .filter(
+                    ibc_packets::dsl::timeout_timestamp
+                        .eq(0)
+                        .or(ibc_packets::dsl::timeout_timestamp.gt(now)),
+                )
+                .order(ibc_packets::dsl::sequence.desc())
+                .select(IbcPacketDb::as_select())
+                .paginate(page)
+                .load_and_count_pages(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_timed_out_packets(
+        &self,
+        channel: String,
+        now: i64,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<IbcPacketDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            // Expired packets are included even if no relayer submitted the
+            // timeout yet, they can't be received by the counterparty anymore
+            ibc_packets::table
+                .filter(
+                    ibc_packets::dsl::direction
+                        .eq(IbcPacketDirectionDb::Outgoing),
+                )
+                .filter(ibc_packets::dsl::source_channel.eq(channel))
+                .filter(
+                    ibc_packets::dsl::status
+                        .eq(IbcPacketStatusDb::TimedOut)
+                        .or(ibc_packets::dsl::status
+                            .eq(IbcPacketStatusDb::Pending)
+                            .and(ibc_packets::dsl::timeout_timestamp.ne(0))
+                            .and(ibc_packets::dsl::timeout_timestamp.le(now))),
+                )
+                .order(ibc_packets::dsl::sequence.desc())
+                .select(IbcPacketDb::as_select())
+                .paginate(page)
+                .load_and_count_pages(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
     async fn get_throughput_limits(
         &self,
         matching_token_address: Option<String>,
         matching_rate_limit: Option<BigDecimal>,
-    ) -> Result<Vec<(String, String)>, String> {
+        epochs: EpochRange,
+    ) -> Result<Vec<(String, String, String)>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
@@ -76,45 +198,49 @@ impl IbcRepositoryTrait for IbcRepository {
             diesel::alias!(ibc_rate_limits as ibc_rate_limits_alias: IbcRateLimitsAlias);
 
             // NB: We're using a raw select because `CAST` is not available in the diesel dsl. :(
-            let select_statement = diesel::dsl::sql::<(diesel::sql_types::Text, diesel::sql_types::Text)>(
+            let select_statement = diesel::dsl::sql::<(diesel::sql_types::Text, diesel::sql_types::Text, diesel::sql_types::Text)>(
                 &format!(
-                    "{}, CAST({} AS TEXT)",
+                    "{}, CAST({} AS TEXT), CAST({} AS TEXT)",
                     ibc_rate_limits::dsl::address::NAME,
                     ibc_rate_limits::dsl::throughput_limit::NAME,
+                    ibc_rate_limits::dsl::epoch::NAME,
                 ),
             );
 
-            let max_epoch_where_clause =
-                ibc_rate_limits::dsl::epoch.nullable().eq(ibc_rate_limits_alias
-                    .select(diesel::dsl::max(ibc_rate_limits_alias.field(ibc_rate_limits::dsl::epoch)))
-                    .single_value());
+            let mut query = ibc_rate_limits::table.into_boxed();
+
+            match epochs {
+                EpochRange::Latest => {
+                    query = query.filter(
+                        ibc_rate_limits::dsl::epoch.nullable().eq(ibc_rate_limits_alias
+                            .select(diesel::dsl::max(ibc_rate_limits_alias.field(ibc_rate_limits::dsl::epoch)))
+                            .single_value()),
+                    );
+                }
+                EpochRange::Between(from_epoch, to_epoch) => {
+                    if let Some(from_epoch) = from_epoch {
+                        query = query.filter(ibc_rate_limits::dsl::epoch.ge(from_epoch));
+                    }
+                    if let Some(to_epoch) = to_epoch {
+                        query = query.filter(ibc_rate_limits::dsl::epoch.le(to_epoch));
+                    }
+                }
+            }
 
-            match (matching_token_address, matching_rate_limit) {
-                (None, None) => ibc_rate_limits::table
-                    .filter(max_epoch_where_clause)
-                    .select(select_statement)
-                    .load(conn),
-                (Some(token), None) => ibc_rate_limits::table
-                    .filter(ibc_rate_limits::dsl::address.eq(&token))
-                    .filter(max_epoch_where_clause)
-                    .select(select_statement)
-                    .load(conn),
-                (None, Some(limit)) => ibc_rate_limits::table
-                    .filter(ibc_rate_limits::dsl::throughput_limit.eq(&limit))
-                    .filter(max_epoch_where_clause)
-                    .select(select_statement)
-                    .load(conn),
-                (Some(token), Some(limit)) => ibc_rate_limits::table
-                    .filter(
-                        ibc_rate_limits::dsl::throughput_limit
-                            .eq(&limit)
-                    )
-                    .filter(ibc_rate_limits::dsl::address.eq(&token))
-                    .filter(max_epoch_where_clause)
-                    .select(select_statement)
-                    .load(conn),
+            if let Some(token) = matching_token_address {
+                query = query.filter(ibc_rate_limits::dsl::address.eq(token));
             }
-            .map_err(|e| e.to_string())
+
+            if let Some(limit) = matching_rate_limit {
+                query = query.filter(ibc_rate_limits::dsl::throughput_limit.eq(limit));
+            }
+
+            query
+                .order(ibc_rate_limits::dsl::epoch.asc())
+                .then_order_by(ibc_rate_limits::dsl::address.asc())
+                .select(select_statement)
+                .load(conn)
+                .map_err(|e| e.to_string())
         })
         .await
         .map_err(|e| e.to_string())?
@@ -123,7 +249,8 @@ impl IbcRepositoryTrait for IbcRepository {
     async fn get_token_flows(
         &self,
         matching_token_address: Option<String>,
-    ) -> Result<Vec<(String, String, String)>, String> {
+        epochs: EpochRange,
+    ) -> Result<Vec<(String, String, String, String)>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
@@ -134,35 +261,50 @@ impl IbcRepositoryTrait for IbcRepository {
             // NB: We're using a raw select because `CAST` is not available in the diesel dsl. :(
             let select_statement =
                 diesel::dsl::sql::<(
+                    diesel::sql_types::Text,
                     diesel::sql_types::Text,
                     diesel::sql_types::Text,
                     diesel::sql_types::Text
                 )>(
                     &format!(
-                        "{}, CAST({} AS TEXT), CAST({} AS TEXT)",
+                        "{}, CAST({} AS TEXT), CAST({} AS TEXT), CAST({} AS TEXT)",
                         ibc_token_flows::dsl::address::NAME,
                         ibc_token_flows::dsl::withdraw::NAME,
                         ibc_token_flows::dsl::deposit::NAME,
+                        ibc_token_flows::dsl::epoch::NAME,
                     ),
                 );
 
-            let max_epoch_where_clause =
-                ibc_token_flows::dsl::epoch.nullable().eq(ibc_token_flows_alias
-                    .select(diesel::dsl::max(ibc_token_flows_alias.field(ibc_token_flows::dsl::epoch)))
-                    .single_value());
+            let mut query = ibc_token_flows::table.into_boxed();
 
-            match matching_token_address {
-                None => ibc_token_flows::table
-                    .filter(max_epoch_where_clause)
-                    .select(select_statement)
-                    .load(conn),
-                Some(token) => ibc_token_flows::table
-                    .filter(ibc_token_flows::dsl::address.eq(&token))
-                    .filter(max_epoch_where_clause)
-                    .select(select_statement)
-                    .load(conn),
+            match epochs {
+                EpochRange::Latest => {
+                    query = query.filter(
+                        ibc_token_flows::dsl::epoch.nullable().eq(ibc_token_flows_alias
+                            .select(diesel::dsl::max(ibc_token_flows_alias.field(ibc_token_flows::dsl::epoch)))
+                            .single_value()),
+                    );
+                }
+                EpochRange::Between(from_epoch, to_epoch) => {
+                    if let Some(from_epoch) = from_epoch {
+                        query = query.filter(ibc_token_flows::dsl::epoch.ge(from_epoch));
+                    }
+                    if let Some(to_epoch) = to_epoch {
+                        query = query.filter(ibc_token_flows::dsl::epoch.le(to_epoch));
+                    }
+                }
             }
-            .map_err(|e| e.to_string())
+
+            if let Some(token) = matching_token_address {
+                query = query.filter(ibc_token_flows::dsl::address.eq(token));
+            }
+
+            query
+                .order(ibc_token_flows::dsl::epoch.asc())
+                .then_order_by(ibc_token_flows::dsl::address.asc())
+                .select(select_statement)
+                .load(conn)
+                .map_err(|e| e.to_string())
         })
         .await
         .map_err(|e| e.to_string())?
@@ -214,4 +356,140 @@ impl IbcRepositoryTrait for IbcRepository {
         .await
         .map_err(|e| e.to_string())?
     }
+
+    async fn get_throughput_utilization(
+        &self,
+        matching_token_address: Option<String>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<(String, String, String, String, Option<String>)>, String>
+    {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            use diesel::{Column, JoinOnDsl};
+
+            // NB: We're using a raw select because `CAST` is not available in
+            // the diesel dsl. :(
+            let select_statement = diesel::dsl::sql::<(
+                diesel::sql_types::Text,
+                diesel::sql_types::Text,
+                diesel::sql_types::Text,
+                diesel::sql_types::Text,
+                diesel::sql_types::Nullable<diesel::sql_types::Text>,
+            )>(&format!(
+                "{flows}.{address}, CAST({flows}.{epoch} AS TEXT), \
+                 CAST(ABS({withdraw} - {deposit}) AS TEXT), CAST({limit} AS \
+                 TEXT), CAST(ABS({withdraw} - {deposit}) / NULLIF({limit}, 0) \
+                 AS TEXT)",
+                flows = "ibc_token_flows",
+                address = ibc_token_flows::dsl::address::NAME,
+                epoch = ibc_token_flows::dsl::epoch::NAME,
+                withdraw = ibc_token_flows::dsl::withdraw::NAME,
+                deposit = ibc_token_flows::dsl::deposit::NAME,
+                limit = ibc_rate_limits::dsl::throughput_limit::NAME,
+            ));
+
+            // The limit that applies to a flow is the one of the same epoch
+            let mut query = ibc_token_flows::table
+                .inner_join(
+                    ibc_rate_limits::table.on(ibc_token_flows::dsl::address
+                        .eq(ibc_rate_limits::dsl::address)
+                        .and(
+                            ibc_token_flows::dsl::epoch
+                                .eq(ibc_rate_limits::dsl::epoch),
+                        )),
+                )
+                .into_boxed();
+
+            if let Some(token) = matching_token_address {
+                query = query.filter(ibc_token_flows::dsl::address.eq(token));
+            }
+
+            if let Some(from_epoch) = from_epoch {
+                query =
+                    query.filter(ibc_token_flows::dsl::epoch.ge(from_epoch));
+            }
+
+            if let Some(to_epoch) = to_epoch {
+                query = query.filter(ibc_token_flows::dsl::epoch.le(to_epoch));
+            }
+
+            query
+                .order(ibc_token_flows::dsl::epoch.asc())
+                .then_order_by(ibc_token_flows::dsl::address.asc())
+                .select(select_statement)
+                .load(conn)
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn find_ibc_channels(
+        &self,
+        counterparty_chain_id: Option<String>,
+    ) -> Result<Vec<IbcChannelDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = ibc_channels::table.into_boxed();
+
+            if let Some(chain_id) = counterparty_chain_id {
+                query = query.filter(
+                    ibc_channels::dsl::counterparty_chain_id.eq(chain_id),
+                );
+            }
+
+            query
+                .order((
+                    ibc_channels::dsl::port_id.asc(),
+                    ibc_channels::dsl::channel_id.asc(),
+                ))
+                .select(IbcChannelDb::as_select())
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn get_trace_flows(
+        &self,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<TraceFlow>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            use diesel::JoinOnDsl;
+
+            let mut query = ibc_token::table
+                .inner_join(ibc_token_flows::table.on(
+                    ibc_token_flows::dsl::address.eq(ibc_token::dsl::address),
+                ))
+                .group_by((ibc_token::dsl::address, ibc_token::dsl::ibc_trace))
+                .select((
+                    ibc_token::dsl::address,
+                    ibc_token::dsl::ibc_trace,
+                    diesel::dsl::sum(ibc_token_flows::dsl::deposit),
+                    diesel::dsl::sum(ibc_token_flows::dsl::withdraw),
+                ))
+                .into_boxed();
+
+            if let Some(from_epoch) = from_epoch {
+                query =
+                    query.filter(ibc_token_flows::dsl::epoch.ge(from_epoch));
+            }
+
+            if let Some(to_epoch) = to_epoch {
+                query = query.filter(ibc_token_flows::dsl::epoch.le(to_epoch));
+            }
+
+            query.load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
 }
@@ -1,3 +1,4 @@
+pub mod account;
 pub mod balance;
 pub mod block;
 pub mod chain;
@@ -8,5 +9,7 @@ pub mod masp;
 pub mod pgf;
 pub mod pos;
 pub mod revealed_pk;
+pub mod search;
+pub mod subscription;
 pub mod tranasaction;
 pub mod utils;
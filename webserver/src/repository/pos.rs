@@ -1,6 +1,6 @@
 use axum::async_trait;
 use bigdecimal::BigDecimal;
-use diesel::dsl::{sql, sum};
+use diesel::dsl::{count_distinct, count_star, sql, sum};
 use diesel::sql_types::Integer;
 use diesel::{
     BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl,
@@ -9,16 +9,25 @@ use diesel::{
 use orm::bond::BondDb;
 use orm::crawler_state::{CrawlerNameDb, EpochCrawlerStateDb};
 use orm::helpers::OrderByDb;
-use orm::pos_rewards::PoSRewardDb;
-use orm::schema::{bonds, crawler_state, pos_rewards, unbonds, validators};
+use orm::pos_rewards::{PoSRewardDb, PosRewardLedgerDb, PosRewardLedgerKindDb};
+use orm::schema::{
+    blocks, bonds, crawler_state, pos_reward_ledger, pos_rewards, unbonds,
+    validator_epoch_snapshots, validators,
+};
 use orm::unbond::UnbondDb;
 use orm::validators::{
-    ValidatorDb, ValidatorSortByDb, ValidatorStateDb, validator_sort_by,
+    ValidatorDb, ValidatorEpochSnapshotDb, ValidatorSortByDb, ValidatorStateDb,
+    validator_sort_by,
 };
 
 use super::utils::{Paginate, PaginatedResponseDb};
 use crate::appstate::AppState;
 
+/// validator id, rewards accrued, sum of the bonded amounts, number of epochs
+/// and number of delegators
+pub type AccruedRewardsDb =
+    (i32, Option<BigDecimal>, Option<BigDecimal>, i64, i64);
+
 #[derive(Clone)]
 pub struct PosRepository {
     pub(crate) app_state: AppState,
@@ -94,7 +103,47 @@ pub trait PosRepositoryTrait {
 
     async fn get_total_voting_power(&self) -> Result<Option<i64>, String>;
 
+    async fn find_validator_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<ValidatorDb>, String>;
+
+    async fn find_validator_snapshots(
+        &self,
+        validator_id: i32,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<ValidatorEpochSnapshotDb>, String>;
+
+    async fn count_blocks_per_epoch(
+        &self,
+        epochs: Vec<i32>,
+    ) -> Result<Vec<(Option<i32>, i64)>, String>;
+
+    async fn count_proposed_blocks_per_epoch(
+        &self,
+        proposer: String,
+        epochs: Vec<i32>,
+    ) -> Result<Vec<(Option<i32>, i64)>, String>;
+
     async fn get_state(&self) -> Result<EpochCrawlerStateDb, String>;
+
+    async fn find_reward_ledger(
+        &self,
+        owner: String,
+        validator: Option<String>,
+        kind: Option<PosRewardLedgerKindDb>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<(PosRewardLedgerDb, ValidatorDb)>, String>;
+
+    async fn get_accrued_rewards(
+        &self,
+        owner: Option<String>,
+        validator_id: Option<i32>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<AccruedRewardsDb>, String>;
 }
 
 #[async_trait]
@@ -352,6 +401,84 @@ impl PosRepositoryTrait for PosRepository {
         .map_err(|e| e.to_string())
     }
 
+    async fn find_validator_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<ValidatorDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validators::table
+                .filter(validators::dsl::namada_address.eq(address))
+                .select(ValidatorDb::as_select())
+                .first(conn)
+                .ok()
+        })
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_validator_snapshots(
+        &self,
+        validator_id: i32,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<ValidatorEpochSnapshotDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validator_epoch_snapshots::table
+                .filter(
+                    validator_epoch_snapshots::dsl::validator_id
+                        .eq(validator_id),
+                )
+                .order(validator_epoch_snapshots::dsl::epoch.desc())
+                .select(ValidatorEpochSnapshotDb::as_select())
+                .paginate(page)
+                .load_and_count_pages(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn count_blocks_per_epoch(
+        &self,
+        epochs: Vec<i32>,
+    ) -> Result<Vec<(Option<i32>, i64)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .filter(blocks::dsl::epoch.eq_any(epochs))
+                .group_by(blocks::dsl::epoch)
+                .select((blocks::dsl::epoch, count_star()))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn count_proposed_blocks_per_epoch(
+        &self,
+        proposer: String,
+        epochs: Vec<i32>,
+    ) -> Result<Vec<(Option<i32>, i64)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .filter(blocks::dsl::epoch.eq_any(epochs))
+                .filter(blocks::dsl::proposer.eq(proposer))
+                .group_by(blocks::dsl::epoch)
+                .select((blocks::dsl::epoch, count_star()))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
     async fn get_state(&self) -> Result<EpochCrawlerStateDb, String> {
         let conn = self.app_state.get_db_connection().await;
 
@@ -368,4 +495,108 @@ impl PosRepositoryTrait for PosRepository {
         .map_err(|e| e.to_string())?
         .map_err(|e| e.to_string())
     }
+
+    async fn find_reward_ledger(
+        &self,
+        owner: String,
+        validator: Option<String>,
+        kind: Option<PosRewardLedgerKindDb>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+        page: i64,
+    ) -> Result<PaginatedResponseDb<(PosRewardLedgerDb, ValidatorDb)>, String>
+    {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = pos_reward_ledger::table
+                .inner_join(validators::table)
+                .filter(pos_reward_ledger::dsl::owner.eq(owner))
+                .into_boxed();
+
+            if let Some(validator) = validator {
+                query =
+                    query.filter(validators::dsl::namada_address.eq(validator));
+            }
+
+            if let Some(kind) = kind {
+                query = query.filter(pos_reward_ledger::dsl::kind.eq(kind));
+            }
+
+            if let Some(from_epoch) = from_epoch {
+                query =
+                    query.filter(pos_reward_ledger::dsl::epoch.ge(from_epoch));
+            }
+
+            if let Some(to_epoch) = to_epoch {
+                query =
+                    query.filter(pos_reward_ledger::dsl::epoch.le(to_epoch));
+            }
+
+            query
+                .order(pos_reward_ledger::dsl::epoch.desc())
+                .then_order_by(pos_reward_ledger::dsl::id.desc())
+                .select((
+                    pos_reward_ledger::all_columns,
+                    validators::all_columns,
+                ))
+                .paginate(page)
+                .load_and_count_pages::<(PosRewardLedgerDb, ValidatorDb)>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn get_accrued_rewards(
+        &self,
+        owner: Option<String>,
+        validator_id: Option<i32>,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<AccruedRewardsDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = pos_reward_ledger::table
+                .filter(
+                    pos_reward_ledger::dsl::kind
+                        .eq(PosRewardLedgerKindDb::Accrued),
+                )
+                .group_by(pos_reward_ledger::dsl::validator_id)
+                .select((
+                    pos_reward_ledger::dsl::validator_id,
+                    sum(pos_reward_ledger::dsl::raw_amount),
+                    sum(pos_reward_ledger::dsl::bonded_amount),
+                    count_distinct(pos_reward_ledger::dsl::epoch),
+                    count_distinct(pos_reward_ledger::dsl::owner),
+                ))
+                .into_boxed();
+
+            if let Some(owner) = owner {
+                query = query.filter(pos_reward_ledger::dsl::owner.eq(owner));
+            }
+
+            if let Some(validator_id) = validator_id {
+                query = query.filter(
+                    pos_reward_ledger::dsl::validator_id.eq(validator_id),
+                );
+            }
+
+            if let Some(from_epoch) = from_epoch {
+                query =
+                    query.filter(pos_reward_ledger::dsl::epoch.ge(from_epoch));
+            }
+
+            if let Some(to_epoch) = to_epoch {
+                query =
+                    query.filter(pos_reward_ledger::dsl::epoch.le(to_epoch));
+            }
+
+            query.load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
 }
@@ -0,0 +1,301 @@
+use axum::async_trait;
+use diesel::dsl::sql;
+use diesel::sql_types::{Bool, Float4, Nullable, Text};
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension,
+    PgTextExpressionMethods, QueryDsl, RunQueryDsl, define_sql_function,
+};
+use orm::schema::{
+    blocks, governance_proposals, inner_transactions, validators,
+    wrapper_transactions,
+};
+
+use crate::appstate::AppState;
+
+// Provided by the pg_trgm extension
+define_sql_function!(fn similarity(x: Nullable<Text>, y: Text) -> Nullable<Float4>);
+
+#[derive(Clone)]
+pub struct SearchRepository {
+    pub(crate) app_state: AppState,
+}
+
+#[async_trait]
+pub trait SearchRepositoryTrait {
+    fn new(app_state: AppState) -> Self;
+
+    async fn find_wrapper_tx(
+        &self,
+        id: String,
+    ) -> Result<Option<(String, i32)>, String>;
+
+    async fn find_inner_tx(
+        &self,
+        id: String,
+    ) -> Result<Option<(String, String)>, String>;
+
+    async fn find_block_by_hash(
+        &self,
+        hash: String,
+    ) -> Result<Option<(i32, Option<String>)>, String>;
+
+    async fn find_block_by_height(
+        &self,
+        height: i32,
+    ) -> Result<Option<(i32, Option<String>)>, String>;
+
+    async fn find_proposal(
+        &self,
+        id: i32,
+    ) -> Result<Option<(i32, String)>, String>;
+
+    async fn find_validator_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<(String, Option<String>, Option<String>)>, String>;
+
+    async fn search_validators(
+        &self,
+        pattern: String,
+        limit: i64,
+    ) -> Result<
+        Vec<(
+            String,
+            Option<String>,
+            Option<String>,
+            Option<f32>,
+            Option<f32>,
+        )>,
+        String,
+    >;
+
+    async fn search_proposals(
+        &self,
+        pattern: String,
+        limit: i64,
+    ) -> Result<Vec<(i32, String, f32)>, String>;
+}
+
+#[async_trait]
+impl SearchRepositoryTrait for SearchRepository {
+    fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+
+    async fn find_wrapper_tx(
+        &self,
+        id: String,
+    ) -> Result<Option<(String, i32)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            wrapper_transactions::table
+                .filter(wrapper_transactions::dsl::id.eq(id))
+                .select((
+                    wrapper_transactions::dsl::id,
+                    wrapper_transactions::dsl::block_height,
+                ))
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_inner_tx(
+        &self,
+        id: String,
+    ) -> Result<Option<(String, String)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            inner_transactions::table
+                .filter(inner_transactions::dsl::id.eq(id))
+                .select((
+                    inner_transactions::dsl::id,
+                    inner_transactions::dsl::wrapper_id,
+                ))
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_block_by_hash(
+        &self,
+        hash: String,
+    ) -> Result<Option<(i32, Option<String>)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .filter(blocks::dsl::hash.eq(hash))
+                .select((blocks::dsl::height, blocks::dsl::hash))
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_block_by_height(
+        &self,
+        height: i32,
+    ) -> Result<Option<(i32, Option<String>)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .filter(blocks::dsl::height.eq(height))
+                .select((blocks::dsl::height, blocks::dsl::hash))
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_proposal(
+        &self,
+        id: i32,
+    ) -> Result<Option<(i32, String)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            governance_proposals::table
+                .filter(governance_proposals::dsl::id.eq(id))
+                .select((
+                    governance_proposals::dsl::id,
+                    governance_proposals::dsl::content,
+                ))
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_validator_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<(String, Option<String>, Option<String>)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validators::table
+                .filter(validators::dsl::namada_address.eq(address))
+                .select((
+                    validators::dsl::namada_address,
+                    validators::dsl::name,
+                    validators::dsl::email,
+                ))
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn search_validators(
+        &self,
+        pattern: String,
+        limit: i64,
+    ) -> Result<
+        Vec<(
+            String,
+            Option<String>,
+            Option<String>,
+            Option<f32>,
+            Option<f32>,
+        )>,
+        String,
+    > {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            // The trigram indexes also serve ilike queries
+            let like_pattern = format!("%{}%", escape_like(&pattern));
+
+            validators::table
+                .filter(
+                    validators::dsl::name
+                        .ilike(like_pattern.clone())
+                        .or(validators::dsl::email.ilike(like_pattern)),
+                )
+                .select((
+                    validators::dsl::namada_address,
+                    validators::dsl::name,
+                    validators::dsl::email,
+                    similarity(validators::dsl::name, pattern.clone()),
+                    similarity(validators::dsl::email, pattern.clone()),
+                ))
+                .order(
+                    similarity(validators::dsl::name, pattern.clone()).desc(),
+                )
+                .then_order_by(
+                    similarity(validators::dsl::email, pattern).desc(),
+                )
+                .limit(limit)
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn search_proposals(
+        &self,
+        pattern: String,
+        limit: i64,
+    ) -> Result<Vec<(i32, String, f32)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            // NB: full text search is not available in the diesel dsl, the
+            // expression has to match the one of the index to use it
+            let matches = sql::<Bool>(
+                "to_tsvector('simple', content) @@ plainto_tsquery('simple', ",
+            )
+            .bind::<Text, _>(pattern.clone())
+            .sql(")");
+            // Normalization 32 scales the rank to [0, 1)
+            let rank = || {
+                sql::<Float4>(
+                    "ts_rank(to_tsvector('simple', content), \
+                     plainto_tsquery('simple', ",
+                )
+                .bind::<Text, _>(pattern.clone())
+                .sql("), 32)")
+            };
+
+            governance_proposals::table
+                .filter(matches)
+                .select((
+                    governance_proposals::dsl::id,
+                    governance_proposals::dsl::content,
+                    rank(),
+                ))
+                .order(rank().desc())
+                .then_order_by(governance_proposals::dsl::id.desc())
+                .limit(limit)
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
+
+fn escape_like(pattern: &str) -> String {
+    pattern
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,214 @@
+use axum::async_trait;
+use diesel::{
+    ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use orm::blocks::BlockDb;
+use orm::governance_proposal::GovernanceProposalResultDb;
+use orm::schema::{
+    blocks, governance_proposals, inner_transactions, transaction_history,
+    wrapper_transactions,
+};
+use orm::transactions::{
+    InnerTransactionDb, TransactionHistoryDb, WrapperTransactionDb,
+};
+
+use crate::appstate::AppState;
+
+#[derive(Clone)]
+pub struct SubscriptionRepository {
+    pub(crate) app_state: AppState,
+}
+
+#[async_trait]
+pub trait SubscriptionRepositoryTrait {
+    fn new(app_state: AppState) -> Self;
+
+    async fn find_last_block_height(&self) -> Result<Option<i32>, String>;
+
+    async fn find_last_transaction_height(&self)
+    -> Result<Option<i32>, String>;
+
+    async fn find_blocks_after(
+        &self,
+        height: i32,
+        limit: i64,
+    ) -> Result<Vec<BlockDb>, String>;
+
+    async fn find_wrapper_txs_between(
+        &self,
+        from_height: i32,
+        to_height: i32,
+    ) -> Result<Vec<WrapperTransactionDb>, String>;
+
+    async fn find_inner_txs_between(
+        &self,
+        from_height: i32,
+        to_height: i32,
+    ) -> Result<Vec<InnerTransactionDb>, String>;
+
+    async fn find_history_between(
+        &self,
+        from_height: i32,
+        to_height: i32,
+    ) -> Result<Vec<(TransactionHistoryDb, InnerTransactionDb, i32)>, String>;
+
+    async fn find_proposals_result(
+        &self,
+    ) -> Result<Vec<(i32, GovernanceProposalResultDb)>, String>;
+}
+
+#[async_trait]
+impl SubscriptionRepositoryTrait for SubscriptionRepository {
+    fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+
+    async fn find_last_block_height(&self) -> Result<Option<i32>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .select(diesel::dsl::max(blocks::dsl::height))
+                .first(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_last_transaction_height(
+        &self,
+    ) -> Result<Option<i32>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            wrapper_transactions::table
+                .select(diesel::dsl::max(
+                    wrapper_transactions::dsl::block_height,
+                ))
+                .first(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_blocks_after(
+        &self,
+        height: i32,
+        limit: i64,
+    ) -> Result<Vec<BlockDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .filter(blocks::dsl::height.gt(height))
+                .order(blocks::dsl::height.asc())
+                .limit(limit)
+                .select(BlockDb::as_select())
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_wrapper_txs_between(
+        &self,
+        from_height: i32,
+        to_height: i32,
+    ) -> Result<Vec<WrapperTransactionDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            wrapper_transactions::table
+                .filter(wrapper_transactions::dsl::block_height.gt(from_height))
+                .filter(wrapper_transactions::dsl::block_height.le(to_height))
+                .order(wrapper_transactions::dsl::block_height.asc())
+                .select(WrapperTransactionDb::as_select())
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_inner_txs_between(
+        &self,
+        from_height: i32,
+        to_height: i32,
+    ) -> Result<Vec<InnerTransactionDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            inner_transactions::table
+                .inner_join(
+                    wrapper_transactions::table
+                        .on(inner_transactions::dsl::wrapper_id
+                            .eq(wrapper_transactions::dsl::id)),
+                )
+                .filter(wrapper_transactions::dsl::block_height.gt(from_height))
+                .filter(wrapper_transactions::dsl::block_height.le(to_height))
+                .order(wrapper_transactions::dsl::block_height.asc())
+                .select(InnerTransactionDb::as_select())
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_history_between(
+        &self,
+        from_height: i32,
+        to_height: i32,
+    ) -> Result<Vec<(TransactionHistoryDb, InnerTransactionDb, i32)>, String>
+    {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            transaction_history::table
+                .inner_join(
+                    inner_transactions::table
+                        .on(transaction_history::dsl::inner_tx_id
+                            .eq(inner_transactions::dsl::id)),
+                )
+                .inner_join(
+                    wrapper_transactions::table
+                        .on(inner_transactions::dsl::wrapper_id
+                            .eq(wrapper_transactions::dsl::id)),
+                )
+                .filter(wrapper_transactions::dsl::block_height.gt(from_height))
+                .filter(wrapper_transactions::dsl::block_height.le(to_height))
+                .order(wrapper_transactions::dsl::block_height.asc())
+                .then_order_by(transaction_history::dsl::id.asc())
+                .select((
+                    transaction_history::all_columns,
+                    inner_transactions::all_columns,
+                    wrapper_transactions::dsl::block_height,
+                ))
+                .load::<(TransactionHistoryDb, InnerTransactionDb, i32)>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_proposals_result(
+        &self,
+    ) -> Result<Vec<(i32, GovernanceProposalResultDb)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            governance_proposals::table
+                .select((
+                    governance_proposals::dsl::id,
+                    governance_proposals::dsl::result,
+                ))
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
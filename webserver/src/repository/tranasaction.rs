@@ -1,17 +1,54 @@
 use axum::async_trait;
+use chrono::NaiveDateTime;
+use diesel::dsl::{Eq, InnerJoinOn, IntoBoxed, sql};
+use diesel::pg::Pg;
+use diesel::sql_types::{Bool, Text};
 use diesel::{
-    ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl, SelectableHelper,
+    BoolExpressionMethods, ExpressionMethods, JoinOnDsl,
+    NullableExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper,
 };
 use orm::schema::{
-    inner_transactions, transaction_history, wrapper_transactions,
+    blocks, inner_transactions, transaction_history, wrapper_transactions,
 };
 use orm::transactions::{
-    InnerTransactionDb, TransactionHistoryDb, WrapperTransactionDb,
+    InnerTransactionDb, TransactionHistoryDb, TransactionHistoryKindDb,
+    TransactionKindDb, TransactionResultDb, WrapperTransactionDb,
 };
 
 use super::utils::{Paginate, PaginatedResponseDb};
 use crate::appstate::AppState;
 
+type TransactionHistoryJoin = InnerJoinOn<
+    InnerJoinOn<
+        transaction_history::table,
+        inner_transactions::table,
+        Eq<transaction_history::inner_tx_id, inner_transactions::id>,
+    >,
+    wrapper_transactions::table,
+    Eq<inner_transactions::wrapper_id, wrapper_transactions::id>,
+>;
+
+pub type TransactionHistoryExportRowDb = (
+    TransactionHistoryDb,
+    InnerTransactionDb,
+    WrapperTransactionDb,
+    Option<NaiveDateTime>,
+);
+
+/// Optional filters of the transaction history, all the given ones have to
+/// match
+#[derive(Clone, Debug, Default)]
+pub struct TransactionHistoryFilters {
+    pub kinds: Vec<TransactionKindDb>,
+    pub token: Option<String>,
+    pub from_height: Option<i32>,
+    pub to_height: Option<i32>,
+    pub from_timestamp: Option<NaiveDateTime>,
+    pub to_timestamp: Option<NaiveDateTime>,
+    pub direction: Option<TransactionHistoryKindDb>,
+    pub exit_code: Option<TransactionResultDb>,
+}
+
 #[derive(Clone)]
 pub struct TransactionRepository {
     pub(crate) app_state: AppState,
@@ -36,15 +73,31 @@ pub trait TransactionRepositoryTrait {
     async fn find_addresses_history(
         &self,
         addresses: Vec<String>,
+        filters: TransactionHistoryFilters,
         page: i64,
     ) -> Result<
         PaginatedResponseDb<(TransactionHistoryDb, InnerTransactionDb, i32)>,
         String,
     >;
+    async fn find_addresses_history_by_cursor(
+        &self,
+        addresses: Vec<String>,
+        filters: TransactionHistoryFilters,
+        after: Option<(i32, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<(TransactionHistoryDb, InnerTransactionDb, i32)>, String>;
     async fn find_txs_by_block_height(
         &self,
         block_height: i32,
     ) -> Result<Vec<WrapperTransactionDb>, String>;
+    async fn find_addresses_history_export(
+        &self,
+        addresses: Vec<String>,
+        from_timestamp: Option<NaiveDateTime>,
+        to_timestamp: Option<NaiveDateTime>,
+        after: Option<(i32, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<TransactionHistoryExportRowDb>, String>;
 }
 
 #[async_trait]
@@ -107,6 +160,7 @@ impl TransactionRepositoryTrait for TransactionRepository {
     async fn find_addresses_history(
         &self,
         addresses: Vec<String>,
+        filters: TransactionHistoryFilters,
         page: i64,
     ) -> Result<
         PaginatedResponseDb<(TransactionHistoryDb, InnerTransactionDb, i32)>,
@@ -115,14 +169,71 @@ impl TransactionRepositoryTrait for TransactionRepository {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
-            transaction_history::table
-                .filter(transaction_history::dsl::target.eq_any(addresses))
-                .inner_join(inner_transactions::table.on(transaction_history::dsl::inner_tx_id.eq(inner_transactions::dsl::id)))
-                .inner_join(wrapper_transactions::table.on(inner_transactions::dsl::wrapper_id.eq(wrapper_transactions::dsl::id)))
+            addresses_history(addresses, filters)
                 .order(wrapper_transactions::dsl::block_height.desc())
-                .select((transaction_history::all_columns, inner_transactions::all_columns, wrapper_transactions::dsl::block_height))
+                .then_order_by(transaction_history::dsl::inner_tx_id.desc())
+                .then_order_by(transaction_history::dsl::id.desc())
+                .select((
+                    transaction_history::all_columns,
+                    inner_transactions::all_columns,
+                    wrapper_transactions::dsl::block_height,
+                ))
                 .paginate(page)
-                .load_and_count_pages::<(TransactionHistoryDb, InnerTransactionDb, i32)>(conn)
+                .load_and_count_pages::<(
+                    TransactionHistoryDb,
+                    InnerTransactionDb,
+                    i32,
+                )>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_addresses_history_by_cursor(
+        &self,
+        addresses: Vec<String>,
+        filters: TransactionHistoryFilters,
+        after: Option<(i32, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<(TransactionHistoryDb, InnerTransactionDb, i32)>, String>
+    {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = addresses_history(addresses, filters);
+
+            if let Some((block_height, inner_tx_id, id)) = after {
+                query = query.filter(
+                    wrapper_transactions::dsl::block_height
+                        .lt(block_height)
+                        .or(wrapper_transactions::dsl::block_height
+                            .eq(block_height)
+                            .and(
+                                transaction_history::dsl::inner_tx_id
+                                    .lt(inner_tx_id.clone()),
+                            ))
+                        .or(wrapper_transactions::dsl::block_height
+                            .eq(block_height)
+                            .and(
+                                transaction_history::dsl::inner_tx_id
+                                    .eq(inner_tx_id),
+                            )
+                            .and(transaction_history::dsl::id.lt(id))),
+                );
+            }
+
+            query
+                .order(wrapper_transactions::dsl::block_height.desc())
+                .then_order_by(transaction_history::dsl::inner_tx_id.desc())
+                .then_order_by(transaction_history::dsl::id.desc())
+                .select((
+                    transaction_history::all_columns,
+                    inner_transactions::all_columns,
+                    wrapper_transactions::dsl::block_height,
+                ))
+                .limit(limit)
+                .load::<(TransactionHistoryDb, InnerTransactionDb, i32)>(conn)
         })
         .await
         .map_err(|e| e.to_string())?
@@ -147,4 +258,157 @@ impl TransactionRepositoryTrait for TransactionRepository {
         .map_err(|e| e.to_string())?
         .map_err(|e| e.to_string())
     }
+
+    /// Oldest first, as exports are read in chronological order
+    async fn find_addresses_history_export(
+        &self,
+        addresses: Vec<String>,
+        from_timestamp: Option<NaiveDateTime>,
+        to_timestamp: Option<NaiveDateTime>,
+        after: Option<(i32, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<TransactionHistoryExportRowDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = transaction_history::table
+                .inner_join(
+                    inner_transactions::table
+                        .on(transaction_history::dsl::inner_tx_id
+                            .eq(inner_transactions::dsl::id)),
+                )
+                .inner_join(
+                    wrapper_transactions::table
+                        .on(inner_transactions::dsl::wrapper_id
+                            .eq(wrapper_transactions::dsl::id)),
+                )
+                .left_join(
+                    blocks::table.on(wrapper_transactions::dsl::block_height
+                        .eq(blocks::dsl::height)),
+                )
+                .filter(transaction_history::dsl::target.eq_any(addresses))
+                .into_boxed();
+
+            if let Some(from_timestamp) = from_timestamp {
+                query = query.filter(blocks::dsl::timestamp.ge(from_timestamp));
+            }
+
+            if let Some(to_timestamp) = to_timestamp {
+                query = query.filter(blocks::dsl::timestamp.le(to_timestamp));
+            }
+
+            if let Some((block_height, inner_tx_id, id)) = after {
+                query = query.filter(
+                    wrapper_transactions::dsl::block_height
+                        .gt(block_height)
+                        .or(wrapper_transactions::dsl::block_height
+                            .eq(block_height)
+                            .and(
+                                transaction_history::dsl::inner_tx_id
+                                    .gt(inner_tx_id.clone()),
+                            ))
+                        .or(wrapper_transactions::dsl::block_height
+                            .eq(block_height)
+                            .and(
+                                transaction_history::dsl::inner_tx_id
+                                    .eq(inner_tx_id),
+                            )
+                            .and(transaction_history::dsl::id.gt(id))),
+                );
+            }
+
+            query
+                .order(wrapper_transactions::dsl::block_height.asc())
+                .then_order_by(transaction_history::dsl::inner_tx_id.asc())
+                .then_order_by(transaction_history::dsl::id.asc())
+                .select((
+                    transaction_history::all_columns,
+                    inner_transactions::all_columns,
+                    WrapperTransactionDb::as_select(),
+                    blocks::dsl::timestamp.nullable(),
+                ))
+                .limit(limit)
+                .load::<TransactionHistoryExportRowDb>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
+
+fn addresses_history<'a>(
+    addresses: Vec<String>,
+    filters: TransactionHistoryFilters,
+) -> IntoBoxed<'a, TransactionHistoryJoin, Pg> {
+    let mut query = transaction_history::table
+        .inner_join(
+            inner_transactions::table.on(transaction_history::dsl::inner_tx_id
+                .eq(inner_transactions::dsl::id)),
+        )
+        .inner_join(
+            wrapper_transactions::table.on(inner_transactions::dsl::wrapper_id
+                .eq(wrapper_transactions::dsl::id)),
+        )
+        .filter(transaction_history::dsl::target.eq_any(addresses))
+        .into_boxed();
+
+    if !filters.kinds.is_empty() {
+        query =
+            query.filter(inner_transactions::dsl::kind.eq_any(filters.kinds));
+    }
+
+    // Matches any token field in the tx data, so both plain and ibc
+    // transfers are covered
+    if let Some(token) = filters.token {
+        query = query.filter(
+            sql::<Bool>(
+                "jsonb_path_exists(CAST(inner_transactions.data AS JSONB), \
+                 '$.**.token ? (@ == $token)', jsonb_build_object('token', ",
+            )
+            .bind::<Text, _>(token)
+            .sql("))"),
+        );
+    }
+
+    if let Some(from_height) = filters.from_height {
+        query = query
+            .filter(wrapper_transactions::dsl::block_height.ge(from_height));
+    }
+
+    if let Some(to_height) = filters.to_height {
+        query =
+            query.filter(wrapper_transactions::dsl::block_height.le(to_height));
+    }
+
+    if let Some(from_timestamp) = filters.from_timestamp {
+        query = query.filter(
+            wrapper_transactions::dsl::block_height.nullable().ge(
+                blocks::table
+                    .filter(blocks::dsl::timestamp.ge(from_timestamp))
+                    .select(diesel::dsl::min(blocks::dsl::height))
+                    .single_value(),
+            ),
+        );
+    }
+
+    if let Some(to_timestamp) = filters.to_timestamp {
+        query = query.filter(
+            wrapper_transactions::dsl::block_height.nullable().le(
+                blocks::table
+                    .filter(blocks::dsl::timestamp.le(to_timestamp))
+                    .select(diesel::dsl::max(blocks::dsl::height))
+                    .single_value(),
+            ),
+        );
+    }
+
+    if let Some(direction) = filters.direction {
+        query = query.filter(transaction_history::dsl::kind.eq(direction));
+    }
+
+    if let Some(exit_code) = filters.exit_code {
+        query = query.filter(inner_transactions::dsl::exit_code.eq(exit_code));
+    }
+
+    query
 }
@@ -0,0 +1,31 @@
+use orm::accounts::AccountDb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    pub address: String,
+    pub vp_code_hash: Option<String>,
+    pub public_keys: Vec<String>,
+    pub threshold: Option<u64>,
+    pub created_at_height: Option<u64>,
+    pub updated_at_height: u64,
+}
+
+impl From<AccountDb> for AccountInfo {
+    fn from(value: AccountDb) -> Self {
+        Self {
+            address: value.address,
+            vp_code_hash: value.vp_code_hash,
+            public_keys: value
+                .public_keys
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .collect(),
+            threshold: value.threshold.map(|threshold| threshold as u64),
+            created_at_height: value.created_at.map(|height| height as u64),
+            updated_at_height: value.updated_at as u64,
+        }
+    }
+}
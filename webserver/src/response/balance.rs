@@ -1,18 +1,67 @@
-use orm::balances::BalanceDb;
+use orm::balances::{BalanceChangeDb, BalanceDb};
+use orm::token::{IbcTokenDb, TokenDb};
 use serde::{Deserialize, Serialize};
+use shared::balance::{Amount, DenominatedAmount};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressBalance {
     pub token_address: String,
     pub min_denom_amount: String,
+    pub denominated_amount: String,
+    pub denomination: u8,
+    pub ibc_trace: Option<String>,
 }
 
-impl From<BalanceDb> for AddressBalance {
-    fn from(value: BalanceDb) -> Self {
+impl AddressBalance {
+    pub fn from(
+        token: TokenDb,
+        ibc_token: Option<IbcTokenDb>,
+        balance: Option<&BalanceDb>,
+    ) -> Self {
+        let amount = balance
+            .map(|balance| Amount::from(&balance.raw_amount))
+            .unwrap_or_else(Amount::zero);
+        let denomination = token.denomination as u8;
+
+        Self {
+            token_address: token.address,
+            min_denom_amount: amount.to_string(),
+            denominated_amount: DenominatedAmount::from((amount, denomination))
+                .to_string_precise(),
+            denomination,
+            ibc_trace: ibc_token.map(|ibc_token| ibc_token.ibc_trace),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.min_denom_amount == Amount::zero().to_string()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBalanceChange {
+    pub token_address: String,
+    pub min_denom_amount: String,
+    pub block_height: i32,
+    pub timestamp: Option<i64>,
+}
+
+impl From<(BalanceChangeDb, Option<chrono::NaiveDateTime>)>
+    for AddressBalanceChange
+{
+    fn from(
+        (balance_change, timestamp): (
+            BalanceChangeDb,
+            Option<chrono::NaiveDateTime>,
+        ),
+    ) -> Self {
         Self {
-            token_address: value.token,
-            min_denom_amount: value.raw_amount.to_string(),
+            token_address: balance_change.token,
+            min_denom_amount: balance_change.raw_amount.to_string(),
+            block_height: balance_change.height,
+            timestamp: timestamp.map(|t| t.and_utc().timestamp()),
         }
     }
 }
@@ -1,4 +1,4 @@
-use orm::blocks::BlockDb;
+use orm::blocks::{BlockDb, BlockMismatchDb, BlockMismatchKindDb};
 use orm::transactions::WrapperTransactionDb;
 use serde::{Deserialize, Serialize};
 
@@ -40,3 +40,39 @@ impl Block {
         }
     }
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockMismatchKind {
+    Hash,
+    ParentHash,
+    AppHash,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockMismatch {
+    pub height: i32,
+    pub kind: BlockMismatchKind,
+    pub indexed_value: String,
+    pub rpc_value: String,
+    pub detected_at: String,
+}
+
+impl From<BlockMismatchDb> for BlockMismatch {
+    fn from(value: BlockMismatchDb) -> Self {
+        Self {
+            height: value.height,
+            kind: match value.kind {
+                BlockMismatchKindDb::Hash => BlockMismatchKind::Hash,
+                BlockMismatchKindDb::ParentHash => {
+                    BlockMismatchKind::ParentHash
+                }
+                BlockMismatchKindDb::AppHash => BlockMismatchKind::AppHash,
+            },
+            indexed_value: value.indexed_value,
+            rpc_value: value.rpc_value,
+            detected_at: value.detected_at.and_utc().timestamp().to_string(),
+        }
+    }
+}
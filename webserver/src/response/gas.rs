@@ -23,5 +23,25 @@ pub struct GasEstimate {
     pub min: u64,
     pub max: u64,
     pub avg: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub suggested_gas_limit: u64,
     pub total_estimates: u64,
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasStats {
+    pub token: String,
+    /// The epoch or the day (YYYY-MM-DD) the stats are aggregated over
+    pub period: Option<String>,
+    pub total_transactions: u64,
+    pub fees_paid: String,
+    pub median_amount_per_gas_unit: Option<String>,
+    pub gas_used: String,
+    pub gas_limit: String,
+    pub gas_utilization: Option<String>,
+    /// The minimum gas price of the token, only available per epoch
+    pub min_gas_price: Option<String>,
+}
@@ -6,11 +6,13 @@ use orm::governance_proposal::{
     GovernanceProposalTallyTypeDb,
 };
 use orm::governance_votes::{GovernanceProposalVoteDb, GovernanceVoteKindDb};
+use orm::proposal_events::{ProposalEventDb, ProposalEventKindDb};
 use serde::{Deserialize, Serialize};
 use sha256::digest;
 use subtle_encoding::hex;
 
 use super::utils::{epoch_progress, time_between_epochs};
+use crate::service::utils::raw_amount_to_nam;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -54,6 +56,20 @@ impl Display for TallyType {
     }
 }
 
+impl From<GovernanceProposalTallyTypeDb> for TallyType {
+    fn from(value: GovernanceProposalTallyTypeDb) -> Self {
+        match value {
+            GovernanceProposalTallyTypeDb::TwoFifths => TallyType::TwoFifths,
+            GovernanceProposalTallyTypeDb::OneHalfOverOneThird => {
+                TallyType::OneHalfOverOneThird
+            }
+            GovernanceProposalTallyTypeDb::LessOneHalfOverOneThirdNay => {
+                TallyType::LessOneHalfOverOneThirdNay
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum VoteType {
@@ -63,7 +79,7 @@ pub enum VoteType {
     Unknown,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum ProposalStatus {
     Pending,
@@ -73,6 +89,18 @@ pub enum ProposalStatus {
     Unknown,
 }
 
+impl From<GovernanceProposalResultDb> for ProposalStatus {
+    fn from(value: GovernanceProposalResultDb) -> Self {
+        match value {
+            GovernanceProposalResultDb::Passed => ProposalStatus::Passed,
+            GovernanceProposalResultDb::Rejected => ProposalStatus::Rejected,
+            GovernanceProposalResultDb::Pending => ProposalStatus::Pending,
+            GovernanceProposalResultDb::Unknown => ProposalStatus::Unknown,
+            GovernanceProposalResultDb::VotingPeriod => ProposalStatus::Voting,
+        }
+    }
+}
+
 impl Display for ProposalStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -105,6 +133,15 @@ pub struct Proposal {
     pub yay_votes: String,
     pub nay_votes: String,
     pub abstain_votes: String,
+    pub wasm_code: Option<ProposalWasmCode>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalWasmCode {
+    pub hash: String,
+    pub size: u64,
+    pub name: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -113,6 +150,60 @@ pub struct ProposalVote {
     pub proposal_id: u64,
     pub vote: VoteType,
     pub voter_address: String,
+    pub power: Option<String>,
+    pub is_validator: Option<bool>,
+    pub overrides_validator: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProposalEventKind {
+    Created,
+    VotingStarted,
+    Tally,
+    Ended,
+    Activated,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalEvent {
+    pub kind: ProposalEventKind,
+    pub epoch: u64,
+    pub block_height: Option<u64>,
+    pub inner_tx_id: Option<String>,
+    pub status: Option<ProposalStatus>,
+    pub yay_votes: Option<String>,
+    pub nay_votes: Option<String>,
+    pub abstain_votes: Option<String>,
+    pub has_proposal_code: Option<bool>,
+    pub proposal_code_succeeded: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VotePowerBreakdown {
+    pub yay: String,
+    pub nay: String,
+    pub abstain: String,
+    pub voters: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalVoteBreakdown {
+    pub proposal_id: u64,
+    pub tally_type: TallyType,
+    pub status: ProposalStatus,
+    pub total_voting_power: String,
+    pub validators: VotePowerBreakdown,
+    pub delegators: VotePowerBreakdown,
+    pub overriding_delegators: u64,
+    pub turnout: String,
+    pub turnout_percentage: String,
+    pub quorum: String,
+    pub quorum_progress: String,
+    pub quorum_reached: bool,
 }
 
 impl Proposal {
@@ -174,17 +265,7 @@ impl Proposal {
                     ProposalType::DefaultWithWasm
                 }
             },
-            tally_type: match value.tally_type {
-                GovernanceProposalTallyTypeDb::TwoFifths => {
-                    TallyType::TwoFifths
-                }
-                GovernanceProposalTallyTypeDb::OneHalfOverOneThird => {
-                    TallyType::OneHalfOverOneThird
-                }
-                GovernanceProposalTallyTypeDb::LessOneHalfOverOneThirdNay => {
-                    TallyType::LessOneHalfOverOneThirdNay
-                }
-            },
+            tally_type: TallyType::from(value.tally_type),
             data: match value.kind {
                 GovernanceProposalKindDb::DefaultWithWasm => {
                     value.data.map(|data| {
@@ -204,24 +285,33 @@ impl Proposal {
             current_time: time_now.to_string(),
             activation_time: activation_time.to_string(),
 
-            status: match value.result {
-                GovernanceProposalResultDb::Passed => ProposalStatus::Passed,
-                GovernanceProposalResultDb::Rejected => {
-                    ProposalStatus::Rejected
-                }
-                GovernanceProposalResultDb::Pending => ProposalStatus::Pending,
-                GovernanceProposalResultDb::Unknown => ProposalStatus::Unknown,
-                GovernanceProposalResultDb::VotingPeriod => {
-                    ProposalStatus::Voting
-                }
-            },
+            status: ProposalStatus::from(value.result),
             yay_votes: value.yay_votes,
             nay_votes: value.nay_votes,
             abstain_votes: value.abstain_votes,
+            wasm_code: ProposalWasmCode::from_proposal_db(
+                value.wasm_code_hash,
+                value.wasm_code_size,
+                value.wasm_code_name,
+            ),
         }
     }
 }
 
+impl ProposalWasmCode {
+    pub fn from_proposal_db(
+        hash: Option<String>,
+        size: Option<i32>,
+        name: Option<String>,
+    ) -> Option<Self> {
+        Some(Self {
+            hash: hash?,
+            size: size? as u64,
+            name,
+        })
+    }
+}
+
 impl From<GovernanceProposalVoteDb> for ProposalVote {
     fn from(value: GovernanceProposalVoteDb) -> Self {
         Self {
@@ -233,6 +323,81 @@ impl From<GovernanceProposalVoteDb> for ProposalVote {
                 GovernanceVoteKindDb::Unknown => VoteType::Unknown,
             },
             voter_address: value.voter_address,
+            power: value
+                .voting_power
+                .map(|power| raw_amount_to_nam(power.to_string())),
+            is_validator: value.is_validator,
+            overrides_validator: value.overrides_validator,
+        }
+    }
+}
+
+impl From<ProposalEventDb> for ProposalEvent {
+    fn from(value: ProposalEventDb) -> Self {
+        let kind = match value.kind {
+            ProposalEventKindDb::Created => ProposalEventKind::Created,
+            ProposalEventKindDb::VotingStarted => {
+                ProposalEventKind::VotingStarted
+            }
+            ProposalEventKindDb::Tally => ProposalEventKind::Tally,
+            ProposalEventKindDb::Ended => ProposalEventKind::Ended,
+            ProposalEventKindDb::Activated => ProposalEventKind::Activated,
+        };
+
+        Self {
+            kind,
+            epoch: value.epoch as u64,
+            block_height: value.block_height.map(|height| height as u64),
+            inner_tx_id: value.inner_tx_id,
+            status: value.result.map(ProposalStatus::from),
+            yay_votes: value.yay_votes,
+            nay_votes: value.nay_votes,
+            abstain_votes: value.abstain_votes,
+            has_proposal_code: value.has_proposal_code,
+            proposal_code_succeeded: value.proposal_code_succeeded,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wasm_code_from_proposal_db() {
+        let wasm_code = ProposalWasmCode::from_proposal_db(
+            Some("ab".repeat(32)),
+            Some(1024),
+            Some("tx_bond".to_string()),
+        )
+        .expect("Wasm code should be set");
+
+        assert_eq!(wasm_code.hash, "ab".repeat(32));
+        assert_eq!(wasm_code.size, 1024);
+        assert_eq!(wasm_code.name.as_deref(), Some("tx_bond"));
+
+        let unnamed = ProposalWasmCode::from_proposal_db(
+            Some("ab".repeat(32)),
+            Some(1),
+            None,
+        )
+        .expect("Wasm code should be set");
+        assert_eq!(unnamed.name, None);
+    }
+
+    #[test]
+    fn wasm_code_from_proposal_db_without_code() {
+        assert!(ProposalWasmCode::from_proposal_db(None, None, None).is_none());
+        assert!(
+            ProposalWasmCode::from_proposal_db(
+                Some("ab".repeat(32)),
+                None,
+                None
+            )
+            .is_none()
+        );
+        assert!(
+            ProposalWasmCode::from_proposal_db(None, Some(1), None).is_none()
+        );
+    }
+}
@@ -1,3 +1,7 @@
+use orm::ibc::{
+    IbcChannelDb, IbcChannelStateDb, IbcPacketDb, IbcPacketDirectionDb,
+    IbcPacketStatusDb,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -14,6 +18,81 @@ pub enum IbcAckStatus {
 pub struct IbcAck {
     pub status: IbcAckStatus,
     pub timeout: Option<i64>,
+    pub packet: Option<IbcPacket>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IbcPacketDirection {
+    Outgoing,
+    Incoming,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IbcPacketStatus {
+    Pending,
+    Received,
+    Acknowledged,
+    Failed,
+    TimedOut,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IbcPacket {
+    pub direction: IbcPacketDirection,
+    pub source_port: String,
+    pub source_channel: String,
+    pub dest_port: String,
+    pub dest_channel: String,
+    pub sequence: u64,
+    pub denom: Option<String>,
+    pub amount: Option<String>,
+    pub sender: Option<String>,
+    pub receiver: Option<String>,
+    pub timeout_timestamp: i64,
+    pub status: IbcPacketStatus,
+    pub refunded: bool,
+    pub send_tx_id: Option<String>,
+    pub recv_tx_id: Option<String>,
+    pub ack_tx_id: Option<String>,
+    pub timeout_tx_id: Option<String>,
+}
+
+impl From<IbcPacketDb> for IbcPacket {
+    fn from(packet: IbcPacketDb) -> Self {
+        Self {
+            direction: match packet.direction {
+                IbcPacketDirectionDb::Outgoing => IbcPacketDirection::Outgoing,
+                IbcPacketDirectionDb::Incoming => IbcPacketDirection::Incoming,
+            },
+            source_port: packet.source_port,
+            source_channel: packet.source_channel,
+            dest_port: packet.dest_port,
+            dest_channel: packet.dest_channel,
+            sequence: packet.sequence as u64,
+            denom: packet.denom,
+            amount: packet.amount.map(|amount| amount.to_string()),
+            sender: packet.sender,
+            receiver: packet.receiver,
+            timeout_timestamp: packet.timeout_timestamp,
+            status: match packet.status {
+                IbcPacketStatusDb::Pending => IbcPacketStatus::Pending,
+                IbcPacketStatusDb::Received => IbcPacketStatus::Received,
+                IbcPacketStatusDb::Acknowledged => {
+                    IbcPacketStatus::Acknowledged
+                }
+                IbcPacketStatusDb::Failed => IbcPacketStatus::Failed,
+                IbcPacketStatusDb::TimedOut => IbcPacketStatus::TimedOut,
+            },
+            refunded: packet.refunded,
+            send_tx_id: packet.send_tx_id,
+            recv_tx_id: packet.recv_tx_id,
+            ack_tx_id: packet.ack_tx_id,
+            timeout_tx_id: packet.timeout_tx_id,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -21,6 +100,7 @@ pub struct IbcAck {
 pub struct IbcRateLimit {
     pub token_address: String,
     pub throughput_limit: String,
+    pub epoch: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -29,6 +109,7 @@ pub struct IbcTokenFlow {
     pub token_address: String,
     pub withdraw: String,
     pub deposit: String,
+    pub epoch: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -37,3 +118,78 @@ pub struct IbcTokenThroughput {
     pub throughput: String,
     pub limit: String,
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IbcThroughputUtilization {
+    pub token_address: String,
+    pub epoch: String,
+    pub throughput: String,
+    pub limit: String,
+    /// Throughput over limit, missing when the limit is zero
+    pub utilization: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IbcChannelState {
+    Uninitialized,
+    Init,
+    TryOpen,
+    Open,
+    Closed,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IbcChannel {
+    pub port_id: String,
+    pub channel_id: String,
+    pub state: IbcChannelState,
+    pub counterparty_port_id: String,
+    pub counterparty_channel_id: Option<String>,
+    pub connection_id: String,
+    pub counterparty_connection_id: Option<String>,
+    pub client_id: String,
+    pub counterparty_client_id: String,
+    pub counterparty_chain_id: Option<String>,
+    pub volumes: Vec<IbcChannelVolume>,
+}
+
+/// Amounts of a token that went through a channel, in its base unit
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IbcChannelVolume {
+    pub token_address: String,
+    pub trace: String,
+    /// Amount received through the channel
+    pub deposit: String,
+    /// Amount sent through the channel
+    pub withdraw: String,
+}
+
+impl IbcChannel {
+    pub fn from(channel: IbcChannelDb, volumes: Vec<IbcChannelVolume>) -> Self {
+        Self {
+            port_id: channel.port_id,
+            channel_id: channel.channel_id,
+            state: match channel.state {
+                IbcChannelStateDb::Uninitialized => {
+                    IbcChannelState::Uninitialized
+                }
+                IbcChannelStateDb::Init => IbcChannelState::Init,
+                IbcChannelStateDb::TryOpen => IbcChannelState::TryOpen,
+                IbcChannelStateDb::Open => IbcChannelState::Open,
+                IbcChannelStateDb::Closed => IbcChannelState::Closed,
+            },
+            counterparty_port_id: channel.counterparty_port_id,
+            counterparty_channel_id: channel.counterparty_channel_id,
+            connection_id: channel.connection_id,
+            counterparty_connection_id: channel.counterparty_connection_id,
+            client_id: channel.client_id,
+            counterparty_client_id: channel.counterparty_client_id,
+            counterparty_chain_id: channel.counterparty_chain_id,
+            volumes,
+        }
+    }
+}
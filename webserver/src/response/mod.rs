@@ -1,3 +1,4 @@
+pub mod account;
 pub mod api;
 pub mod balance;
 pub mod block;
@@ -10,5 +11,7 @@ pub mod masp;
 pub mod pgf;
 pub mod pos;
 pub mod revealed_pk;
+pub mod search;
+pub mod subscription;
 pub mod transaction;
 pub mod utils;
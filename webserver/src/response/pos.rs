@@ -1,9 +1,11 @@
 use bigdecimal::BigDecimal;
 use orm::bond::BondDb;
 use orm::crawler_state::{ChainCrawlerStateDb, EpochCrawlerStateDb};
-use orm::pos_rewards::PoSRewardDb;
+use orm::pos_rewards::{PoSRewardDb, PosRewardLedgerDb, PosRewardLedgerKindDb};
 use orm::unbond::UnbondDb;
-use orm::validators::{ValidatorDb, ValidatorStateDb};
+use orm::validators::{
+    ValidatorDb, ValidatorEpochSnapshotDb, ValidatorStateDb,
+};
 use serde::{Deserialize, Serialize};
 
 use super::utils::{epoch_progress, time_between_epochs};
@@ -102,6 +104,51 @@ pub struct Reward {
     pub validator: ValidatorWithId,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RewardLedgerKind {
+    Accrued,
+    Claimed,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardLedgerEntry {
+    pub epoch: String,
+    pub kind: RewardLedgerKind,
+    pub min_denom_amount: String,
+    pub bonded_amount: Option<String>,
+    pub tx_id: Option<String>,
+    pub validator: ValidatorWithId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationApr {
+    pub validator: ValidatorWithId,
+    pub min_denom_amount: String,
+    pub epochs: u64,
+    pub apr: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorApr {
+    pub min_denom_amount: String,
+    pub apr: Option<String>,
+    pub delegations: Vec<DelegationApr>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorApr {
+    pub validator: ValidatorWithId,
+    pub min_denom_amount: String,
+    pub epochs: u64,
+    pub delegators: u64,
+    pub apr: Option<String>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TotalVotingPower {
@@ -135,6 +182,36 @@ pub struct ValidatorWithId {
     pub rank: Option<i32>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorEpochSnapshot {
+    pub epoch: String,
+    pub voting_power: String,
+    pub max_commission: String,
+    pub commission: String,
+    pub state: ValidatorState,
+    pub proposed_blocks: u64,
+    pub total_blocks: u64,
+}
+
+impl ValidatorEpochSnapshot {
+    pub fn from(
+        db_snapshot: ValidatorEpochSnapshotDb,
+        proposed_blocks: u64,
+        total_blocks: u64,
+    ) -> Self {
+        Self {
+            epoch: db_snapshot.epoch.to_string(),
+            voting_power: db_snapshot.voting_power.to_string(),
+            max_commission: db_snapshot.max_commission,
+            commission: db_snapshot.commission,
+            state: db_snapshot.state.into(),
+            proposed_blocks,
+            total_blocks,
+        }
+    }
+}
+
 impl ValidatorWithId {
     pub fn from(db_validator: ValidatorDb, rank: Option<i32>) -> Self {
         Self {
@@ -227,6 +304,33 @@ impl Withdraw {
     }
 }
 
+impl From<PosRewardLedgerKindDb> for RewardLedgerKind {
+    fn from(value: PosRewardLedgerKindDb) -> Self {
+        match value {
+            PosRewardLedgerKindDb::Accrued => Self::Accrued,
+            PosRewardLedgerKindDb::Claimed => Self::Claimed,
+        }
+    }
+}
+
+impl RewardLedgerEntry {
+    pub fn from(
+        db_entry: PosRewardLedgerDb,
+        db_validator: ValidatorDb,
+    ) -> Self {
+        Self {
+            epoch: db_entry.epoch.to_string(),
+            kind: db_entry.kind.into(),
+            min_denom_amount: db_entry.raw_amount.to_string(),
+            bonded_amount: db_entry
+                .bonded_amount
+                .map(|amount| amount.to_string()),
+            tx_id: db_entry.inner_tx_id,
+            validator: ValidatorWithId::from(db_validator, None),
+        }
+    }
+}
+
 impl Reward {
     pub fn from(db_reward: PoSRewardDb, db_validator: ValidatorDb) -> Self {
         Self {
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AddressKind {
+    Transparent,
+    Shielded,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WrapperTransactionMatch {
+    pub id: String,
+    pub block_height: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InnerTransactionMatch {
+    pub id: String,
+    pub wrapper_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockMatch {
+    pub height: u64,
+    pub hash: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressMatch {
+    pub address: String,
+    pub kind: AddressKind,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorMatch {
+    pub address: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalMatch {
+    pub id: String,
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum SearchResultItem {
+    WrapperTransaction(WrapperTransactionMatch),
+    InnerTransaction(InnerTransactionMatch),
+    Block(BlockMatch),
+    Address(AddressMatch),
+    Validator(ValidatorMatch),
+    Proposal(ProposalMatch),
+}
+
+/// A search hit, `score` goes from 0 to 1 with exact matches scoring 1
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    #[serde(flatten)]
+    pub item: SearchResultItem,
+    pub score: f32,
+}
+
+impl SearchResult {
+    pub fn exact(item: SearchResultItem) -> Self {
+        Self { item, score: 1.0 }
+    }
+}
+
+impl ProposalMatch {
+    pub fn from(id: i32, content: &str) -> Self {
+        // Proposal content is a json object, the title is optional
+        let title = serde_json::from_str::<serde_json::Value>(content)
+            .ok()
+            .and_then(|content| {
+                content
+                    .get("title")
+                    .and_then(|title| title.as_str())
+                    .map(|title| title.to_string())
+            });
+
+        Self {
+            id: id.to_string(),
+            title,
+        }
+    }
+}
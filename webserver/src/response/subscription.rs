@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use super::block::Block;
+use super::governance::ProposalStatus;
+use super::transaction::{
+    InnerTransaction, TransactionHistory, WrapperTransaction,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalStatusChange {
+    pub proposal_id: u64,
+    pub status: ProposalStatus,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "topic", content = "data", rename_all = "camelCase")]
+pub enum SubscriptionEvent {
+    Block(Block),
+    WrapperTransaction(WrapperTransaction),
+    InnerTransaction(InnerTransaction),
+    AddressActivity(TransactionHistory),
+    ProposalStatus(ProposalStatusChange),
+}
+
+impl SubscriptionEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SubscriptionEvent::Block(_) => "block",
+            SubscriptionEvent::WrapperTransaction(_) => "wrapperTransaction",
+            SubscriptionEvent::InnerTransaction(_) => "innerTransaction",
+            SubscriptionEvent::AddressActivity(_) => "addressActivity",
+            SubscriptionEvent::ProposalStatus(_) => "proposalStatus",
+        }
+    }
+}
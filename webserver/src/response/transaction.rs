@@ -1,3 +1,8 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDateTime, SecondsFormat};
 use orm::transactions::{
     InnerTransactionDb, TransactionHistoryDb, TransactionHistoryKindDb,
     TransactionKindDb, TransactionResultDb, WrapperTransactionDb,
@@ -37,6 +42,12 @@ pub enum TransactionKind {
     DeactivateValidator,
     ReactivateValidator,
     UnjailValidator,
+    InitAccount,
+    UpdateAccount,
+    ChangeConsensusKey,
+    ResignSteward,
+    UpdateStewardCommission,
+    BridgePoolTransfer,
     Unknown,
 }
 
@@ -97,6 +108,49 @@ impl From<TransactionResultDb> for TransactionResult {
     }
 }
 
+impl From<TransactionKind> for TransactionKindDb {
+    fn from(value: TransactionKind) -> Self {
+        match value {
+            TransactionKind::TransparentTransfer => Self::TransparentTransfer,
+            TransactionKind::ShieldedTransfer => Self::ShieldedTransfer,
+            TransactionKind::ShieldingTransfer => Self::ShieldingTransfer,
+            TransactionKind::UnshieldingTransfer => Self::UnshieldingTransfer,
+            TransactionKind::MixedTransfer => Self::MixedTransfer,
+            TransactionKind::Bond => Self::Bond,
+            TransactionKind::Redelegation => Self::Redelegation,
+            TransactionKind::Unbond => Self::Unbond,
+            TransactionKind::Withdraw => Self::Withdraw,
+            TransactionKind::ClaimRewards => Self::ClaimRewards,
+            TransactionKind::VoteProposal => Self::VoteProposal,
+            TransactionKind::InitProposal => Self::InitProposal,
+            TransactionKind::ChangeMetadata => Self::ChangeMetadata,
+            TransactionKind::ChangeCommission => Self::ChangeCommission,
+            TransactionKind::RevealPk => Self::RevealPk,
+            TransactionKind::IbcMsgTransfer => Self::IbcMsgTransfer,
+            TransactionKind::IbcTransparentTransfer => {
+                Self::IbcTransparentTransfer
+            }
+            TransactionKind::IbcShieldingTransfer => Self::IbcShieldingTransfer,
+            TransactionKind::IbcUnshieldingTransfer => {
+                Self::IbcUnshieldingTransfer
+            }
+            TransactionKind::BecomeValidator => Self::BecomeValidator,
+            TransactionKind::DeactivateValidator => Self::DeactivateValidator,
+            TransactionKind::ReactivateValidator => Self::ReactivateValidator,
+            TransactionKind::UnjailValidator => Self::UnjailValidator,
+            TransactionKind::InitAccount => Self::InitAccount,
+            TransactionKind::UpdateAccount => Self::UpdateAccount,
+            TransactionKind::ChangeConsensusKey => Self::ChangeConsensusKey,
+            TransactionKind::ResignSteward => Self::ResignSteward,
+            TransactionKind::UpdateStewardCommission => {
+                Self::UpdateStewardCommission
+            }
+            TransactionKind::BridgePoolTransfer => Self::BridgePoolTransfer,
+            TransactionKind::Unknown => Self::Unknown,
+        }
+    }
+}
+
 impl From<TransactionKindDb> for TransactionKind {
     fn from(value: TransactionKindDb) -> Self {
         match value {
@@ -130,6 +184,14 @@ impl From<TransactionKindDb> for TransactionKind {
             TransactionKindDb::ReactivateValidator => Self::ReactivateValidator,
             TransactionKindDb::DeactivateValidator => Self::DeactivateValidator,
             TransactionKindDb::UnjailValidator => Self::UnjailValidator,
+            TransactionKindDb::InitAccount => Self::InitAccount,
+            TransactionKindDb::UpdateAccount => Self::UpdateAccount,
+            TransactionKindDb::ChangeConsensusKey => Self::ChangeConsensusKey,
+            TransactionKindDb::ResignSteward => Self::ResignSteward,
+            TransactionKindDb::UpdateStewardCommission => {
+                Self::UpdateStewardCommission
+            }
+            TransactionKindDb::BridgePoolTransfer => Self::BridgePoolTransfer,
         }
     }
 }
@@ -202,3 +264,460 @@ impl TransactionHistory {
         }
     }
 }
+
+/// A line of the history export. Transfers moving several tokens for the
+/// same address are exported as one line per token, the wrapper fee being
+/// only reported on the first line of the fee payer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionHistoryExportRow {
+    pub timestamp: Option<String>,
+    pub block_height: i32,
+    pub tx_id: String,
+    pub wrapper_id: String,
+    pub kind: TransactionKind,
+    pub direction: TrasactionHistoryKind,
+    pub address: String,
+    pub counterparty: Option<String>,
+    pub token: Option<String>,
+    pub amount: Option<String>,
+    /// Fee of the wrapper, only set on the first line of the fee payer
+    pub fee: Option<String>,
+    pub fee_token: Option<String>,
+    pub exit_code: TransactionResult,
+}
+
+impl TransactionHistoryExportRow {
+    pub const CSV_HEADER: &'static str =
+        "timestamp,blockHeight,txId,wrapperId,kind,direction,address,\
+         counterparty,token,amount,fee,feeToken,exitCode\n";
+
+    pub fn from(
+        history: TransactionHistoryDb,
+        inner_tx: InnerTransactionDb,
+        wrapper_tx: WrapperTransactionDb,
+        timestamp: Option<NaiveDateTime>,
+    ) -> Vec<Self> {
+        let direction = match history.kind {
+            TransactionHistoryKindDb::Received => {
+                TrasactionHistoryKind::Received
+            }
+            TransactionHistoryKindDb::Sent => TrasactionHistoryKind::Sent,
+        };
+
+        let is_fee_payer = wrapper_tx.fee_payer == history.target;
+        let fee = is_fee_payer
+            .then(|| {
+                let gas_used = BigDecimal::from(wrapper_tx.gas_used?);
+                let amount_per_gas_unit = BigDecimal::from_str(
+                    wrapper_tx.amount_per_gas_unit.as_deref()?,
+                )
+                .ok()?;
+                Some((gas_used * amount_per_gas_unit).normalized().to_string())
+            })
+            .flatten();
+        let fee_token = fee.as_ref().map(|_| wrapper_tx.fee_token.clone());
+
+        let row = Self {
+            timestamp: timestamp.map(|timestamp| {
+                timestamp
+                    .and_utc()
+                    .to_rfc3339_opts(SecondsFormat::Secs, true)
+            }),
+            block_height: wrapper_tx.block_height,
+            tx_id: inner_tx.id,
+            wrapper_id: wrapper_tx.id,
+            kind: TransactionKind::from(inner_tx.kind),
+            direction: direction.clone(),
+            address: history.target.clone(),
+            counterparty: None,
+            token: None,
+            amount: None,
+            fee,
+            fee_token,
+            exit_code: TransactionResult::from(inner_tx.exit_code),
+        };
+
+        let Some(transfer) = inner_tx
+            .data
+            .as_deref()
+            .and_then(|data| serde_json::from_str(data).ok())
+            .and_then(transfer_data)
+        else {
+            return vec![row];
+        };
+
+        let entries = |side: &str| {
+            transfer
+                .get(side)
+                .and_then(|entries| entries.as_array())
+                .cloned()
+                .unwrap_or_default()
+        };
+        let (own_side, other_side) = match direction {
+            TrasactionHistoryKind::Sent => {
+                (entries("sources"), entries("targets"))
+            }
+            TrasactionHistoryKind::Received => {
+                (entries("targets"), entries("sources"))
+            }
+        };
+
+        let field = |entry: &serde_json::Value, name: &str| {
+            entry
+                .get(name)
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string())
+        };
+
+        let mut counterparties = other_side
+            .iter()
+            .filter_map(|entry| field(entry, "owner"))
+            .collect::<Vec<_>>();
+        counterparties.sort();
+        counterparties.dedup();
+        let counterparty =
+            (!counterparties.is_empty()).then(|| counterparties.join(" "));
+
+        let rows = own_side
+            .iter()
+            .filter(|entry| {
+                field(entry, "owner").as_ref() == Some(&history.target)
+            })
+            .enumerate()
+            .map(|(index, entry)| Self {
+                counterparty: counterparty.clone(),
+                token: field(entry, "token"),
+                amount: field(entry, "amount"),
+                fee: row.fee.clone().filter(|_| index == 0),
+                fee_token: row.fee_token.clone().filter(|_| index == 0),
+                ..row.clone()
+            })
+            .collect::<Vec<_>>();
+
+        if rows.is_empty() {
+            vec![Self {
+                counterparty,
+                ..row
+            }]
+        } else {
+            rows
+        }
+    }
+
+    pub fn to_csv_record(&self) -> String {
+        let kind = serde_json::to_value(&self.kind)
+            .ok()
+            .and_then(|kind| kind.as_str().map(|kind| kind.to_string()))
+            .unwrap_or_default();
+        let direction = match self.direction {
+            TrasactionHistoryKind::Received => "received",
+            TrasactionHistoryKind::Sent => "sent",
+        };
+        let exit_code = match self.exit_code {
+            TransactionResult::Applied => "applied",
+            TransactionResult::Rejected => "rejected",
+        };
+
+        let fields = [
+            self.timestamp.clone().unwrap_or_default(),
+            self.block_height.to_string(),
+            self.tx_id.clone(),
+            self.wrapper_id.clone(),
+            kind,
+            direction.to_string(),
+            self.address.clone(),
+            self.counterparty.clone().unwrap_or_default(),
+            self.token.clone().unwrap_or_default(),
+            self.amount.clone().unwrap_or_default(),
+            self.fee.clone().unwrap_or_default(),
+            self.fee_token.clone().unwrap_or_default(),
+            exit_code.to_string(),
+        ];
+
+        let mut record = fields
+            .iter()
+            .map(|field| csv_escape(field))
+            .collect::<Vec<_>>()
+            .join(",");
+        record.push('\n');
+        record
+    }
+}
+
+/// Wrappers whose fee was already exported, so that a wrapper with several
+/// inner transactions only reports its fee once. Export rows are ordered by
+/// block height, only the wrappers of the current block are remembered.
+#[derive(Debug, Default)]
+pub struct ExportedFees {
+    block_height: i32,
+    wrappers: HashSet<(String, String)>,
+}
+
+impl ExportedFees {
+    pub fn dedup(&mut self, row: &mut TransactionHistoryExportRow) {
+        if row.fee.is_none() {
+            return;
+        }
+
+        if row.block_height != self.block_height {
+            self.block_height = row.block_height;
+            self.wrappers.clear();
+        }
+
+        if !self
+            .wrappers
+            .insert((row.wrapper_id.clone(), row.address.clone()))
+        {
+            row.fee = None;
+            row.fee_token = None;
+        }
+    }
+}
+
+/// Transfers are stored as the transfer data itself, ibc transfers as a
+/// (token, transfer data) tuple
+fn transfer_data(data: serde_json::Value) -> Option<serde_json::Value> {
+    if data.get("sources").is_some() {
+        return Some(data);
+    }
+
+    data.as_array()
+        .and_then(|tuple| tuple.get(1))
+        .filter(|transfer| transfer.get("sources").is_some())
+        .cloned()
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(
+        target: &str,
+        kind: TransactionHistoryKindDb,
+    ) -> TransactionHistoryDb {
+        TransactionHistoryDb {
+            id: 1,
+            inner_tx_id: "inner".to_string(),
+            target: target.to_string(),
+            kind,
+        }
+    }
+
+    fn inner_tx(data: Option<&str>) -> InnerTransactionDb {
+        InnerTransactionDb {
+            id: "inner".to_string(),
+            wrapper_id: "wrapper".to_string(),
+            kind: TransactionKindDb::TransparentTransfer,
+            data: data.map(String::from),
+            memo: None,
+            exit_code: TransactionResultDb::Applied,
+        }
+    }
+
+    fn wrapper_tx(fee_payer: &str) -> WrapperTransactionDb {
+        WrapperTransactionDb {
+            id: "wrapper".to_string(),
+            fee_payer: fee_payer.to_string(),
+            fee_token: "tnam1nam".to_string(),
+            gas_limit: "50000".to_string(),
+            gas_used: Some(20_000),
+            amount_per_gas_unit: Some("0.000001".to_string()),
+            block_height: 42,
+            exit_code: TransactionResultDb::Applied,
+            atomic: true,
+        }
+    }
+
+    const TRANSFER: &str = r#"{
+        "sources": [
+            {"owner": "tnam1alice", "token": "tnam1nam", "amount": "10"},
+            {"owner": "tnam1alice", "token": "tnam1usdc", "amount": "5"}
+        ],
+        "targets": [
+            {"owner": "tnam1bob", "token": "tnam1nam", "amount": "10"},
+            {"owner": "tnam1carol", "token": "tnam1usdc", "amount": "5"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_export_row_per_token() {
+        let timestamp = chrono::DateTime::from_timestamp(1_700_000_000, 0)
+            .unwrap()
+            .naive_utc();
+
+        let rows = TransactionHistoryExportRow::from(
+            history("tnam1alice", TransactionHistoryKindDb::Sent),
+            inner_tx(Some(TRANSFER)),
+            wrapper_tx("tnam1alice"),
+            Some(timestamp),
+        );
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].timestamp.as_deref(), Some("2023-11-14T22:13:20Z"));
+        assert_eq!(rows[0].block_height, 42);
+        assert_eq!(rows[0].token.as_deref(), Some("tnam1nam"));
+        assert_eq!(rows[0].amount.as_deref(), Some("10"));
+        assert_eq!(rows[1].token.as_deref(), Some("tnam1usdc"));
+        assert_eq!(rows[1].amount.as_deref(), Some("5"));
+        for row in &rows {
+            assert_eq!(
+                row.counterparty.as_deref(),
+                Some("tnam1bob tnam1carol")
+            );
+        }
+        // The fee is reported once, not once per token
+        assert_eq!(rows[0].fee.as_deref(), Some("0.02"));
+        assert_eq!(rows[0].fee_token.as_deref(), Some("tnam1nam"));
+        assert!(rows[1].fee.is_none());
+        assert!(rows[1].fee_token.is_none());
+    }
+
+    #[test]
+    fn test_export_fee_once_per_wrapper() {
+        let second_inner_tx = InnerTransactionDb {
+            id: "inner2".to_string(),
+            ..inner_tx(Some(TRANSFER))
+        };
+        let next_block_wrapper = WrapperTransactionDb {
+            id: "wrapper2".to_string(),
+            block_height: 43,
+            ..wrapper_tx("tnam1alice")
+        };
+
+        let mut exported_fees = ExportedFees::default();
+        let rows = [
+            (inner_tx(Some(TRANSFER)), wrapper_tx("tnam1alice")),
+            (second_inner_tx, wrapper_tx("tnam1alice")),
+            (inner_tx(Some(TRANSFER)), next_block_wrapper),
+        ]
+        .into_iter()
+        .flat_map(|(inner_tx, wrapper_tx)| {
+            TransactionHistoryExportRow::from(
+                history("tnam1alice", TransactionHistoryKindDb::Sent),
+                inner_tx,
+                wrapper_tx,
+                None,
+            )
+        })
+        .map(|mut row| {
+            exported_fees.dedup(&mut row);
+            row
+        })
+        .collect::<Vec<_>>();
+
+        // Two tokens for each of the three inner transactions
+        assert_eq!(rows.len(), 6);
+        let fees = rows
+            .iter()
+            .map(|row| (row.wrapper_id.as_str(), row.fee.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fees,
+            [
+                ("wrapper", Some("0.02")),
+                ("wrapper", None),
+                ("wrapper", None),
+                ("wrapper", None),
+                ("wrapper2", Some("0.02")),
+                ("wrapper2", None),
+            ]
+        );
+        assert!(
+            rows.iter()
+                .all(|row| row.fee.is_some() == row.fee_token.is_some())
+        );
+    }
+
+    #[test]
+    fn test_export_row_received() {
+        let rows = TransactionHistoryExportRow::from(
+            history("tnam1bob", TransactionHistoryKindDb::Received),
+            inner_tx(Some(TRANSFER)),
+            wrapper_tx("tnam1alice"),
+            None,
+        );
+
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].timestamp.is_none());
+        assert_eq!(rows[0].counterparty.as_deref(), Some("tnam1alice"));
+        assert_eq!(rows[0].token.as_deref(), Some("tnam1nam"));
+        assert_eq!(rows[0].amount.as_deref(), Some("10"));
+        // Only the fee payer pays the fee
+        assert!(rows[0].fee.is_none());
+        assert!(rows[0].fee_token.is_none());
+    }
+
+    #[test]
+    fn test_export_row_ibc_transfer() {
+        let data = format!(r#"["tnam1nam", {TRANSFER}]"#);
+
+        let rows = TransactionHistoryExportRow::from(
+            history("tnam1carol", TransactionHistoryKindDb::Received),
+            inner_tx(Some(&data)),
+            wrapper_tx("tnam1alice"),
+            None,
+        );
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].token.as_deref(), Some("tnam1usdc"));
+        assert_eq!(rows[0].amount.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn test_export_row_without_transfer() {
+        let rows = TransactionHistoryExportRow::from(
+            history("tnam1alice", TransactionHistoryKindDb::Sent),
+            inner_tx(Some(r#"{"validator":"tnam1validator"}"#)),
+            wrapper_tx("tnam1alice"),
+            None,
+        );
+
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].counterparty.is_none());
+        assert!(rows[0].token.is_none());
+        assert!(rows[0].amount.is_none());
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape(""), "");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_escape("line\r\nbreak"), "\"line\r\nbreak\"");
+    }
+
+    #[test]
+    fn test_to_csv_record() {
+        let rows = TransactionHistoryExportRow::from(
+            history("tnam1alice", TransactionHistoryKindDb::Sent),
+            inner_tx(Some(TRANSFER)),
+            wrapper_tx("tnam1alice"),
+            None,
+        );
+        let row = TransactionHistoryExportRow {
+            counterparty: Some("tnam1bob, \"the builder\"\n".to_string()),
+            ..rows[0].clone()
+        };
+
+        assert_eq!(
+            row.to_csv_record(),
+            ",42,inner,wrapper,transparentTransfer,sent,tnam1alice,\"tnam1bob, \
+             \"\"the builder\"\"\n\",tnam1nam,10,0.02,tnam1nam,applied\n"
+        );
+        assert_eq!(
+            TransactionHistoryExportRow::CSV_HEADER.split(',').count(),
+            rows[1].to_csv_record().split(',').count()
+        );
+    }
+}
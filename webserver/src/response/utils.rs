@@ -4,9 +4,15 @@ use serde::Serialize;
 use crate::constant::ITEM_PER_PAGE;
 
 #[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PaginatedResponse<T: Serialize> {
     pub results: T,
-    pub pagination: Pagination,
+    /// Omitted for cursor requests: keyset pages skip the count query, so
+    /// there is no page number or total to report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -30,14 +36,28 @@ where
     ) -> Self {
         Self {
             results,
-            pagination: Pagination {
+            pagination: Some(Pagination {
                 page,
                 per_page: ITEM_PER_PAGE,
                 total_pages,
                 total_items,
-            },
+            }),
+            next_cursor: None,
+        }
+    }
+
+    pub fn with_cursor(results: T, next_cursor: Option<String>) -> Self {
+        Self {
+            results,
+            pagination: None,
+            next_cursor,
         }
     }
+
+    pub fn next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self
+    }
 }
 
 pub fn epoch_progress(
@@ -0,0 +1,32 @@
+use crate::appstate::AppState;
+use crate::error::account::AccountError;
+use crate::repository::account::{AccountRepository, AccountRepositoryTrait};
+use crate::response::account::AccountInfo;
+
+#[derive(Clone)]
+pub struct AccountService {
+    account_repo: AccountRepository,
+}
+
+impl AccountService {
+    pub fn new(app_state: AppState) -> Self {
+        Self {
+            account_repo: AccountRepository::new(app_state),
+        }
+    }
+
+    pub async fn get_account_info(
+        &self,
+        address: String,
+    ) -> Result<AccountInfo, AccountError> {
+        let account = self
+            .account_repo
+            .find_account_by_address(address.clone())
+            .await
+            .map_err(AccountError::Database)?;
+
+        account
+            .map(AccountInfo::from)
+            .ok_or(AccountError::NotFound(address))
+    }
+}
@@ -1,31 +1,60 @@
-use shared::balance::Amount;
-
 use crate::appstate::AppState;
 use crate::error::balance::BalanceError;
 use crate::repository::balance::{BalanceRepo, BalanceRepoTrait};
-use crate::response::balance::AddressBalance;
+use crate::repository::block::{BlockRepository, BlockRepositoryTrait};
+use crate::response::balance::{AddressBalance, AddressBalanceChange};
 
 #[derive(Clone)]
 pub struct BalanceService {
     pub balance_repo: BalanceRepo,
+    pub block_repo: BlockRepository,
 }
 
 impl BalanceService {
     pub fn new(app_state: AppState) -> Self {
         Self {
-            balance_repo: BalanceRepo::new(app_state),
+            balance_repo: BalanceRepo::new(app_state.clone()),
+            block_repo: BlockRepository::new(app_state),
         }
     }
 
     pub async fn get_address_balances(
         &self,
         address: String,
+        height: Option<i32>,
+        timestamp: Option<i64>,
+        omit_zero: bool,
     ) -> Result<Vec<AddressBalance>, BalanceError> {
-        let balances = self
-            .balance_repo
-            .get_address_balances(address)
-            .await
-            .map_err(BalanceError::Database)?;
+        let height = match (height, timestamp) {
+            (Some(_), Some(_)) => return Err(BalanceError::HeightAndTimestamp),
+            (Some(height), None) => Some(height),
+            (None, Some(timestamp)) => {
+                let timestamp = chrono::DateTime::from_timestamp(timestamp, 0)
+                    .ok_or(BalanceError::InvalidTimestamp(timestamp))?
+                    .naive_utc();
+                // Before the first indexed block every balance is zero
+                let block = self
+                    .block_repo
+                    .find_block_by_timestamp(timestamp)
+                    .await
+                    .map_err(BalanceError::Database)?;
+                Some(block.map(|block| block.height).unwrap_or_default())
+            }
+            (None, None) => None,
+        };
+
+        let balances = match height {
+            Some(height) => self
+                .balance_repo
+                .get_address_balances_at_height(address, height)
+                .await
+                .map_err(BalanceError::Database)?,
+            None => self
+                .balance_repo
+                .get_address_balances(address)
+                .await
+                .map_err(BalanceError::Database)?,
+        };
 
         let tokens = self
             .balance_repo
@@ -33,20 +62,39 @@ impl BalanceService {
             .await
             .map_err(BalanceError::Database)?;
 
-        // TODO: temporary solution as we only store NAM balances
         let denominated_balances = tokens
             .into_iter()
-            .map(|token| AddressBalance {
-                token_address: token.address.clone(),
-                min_denom_amount: balances
+            .map(|(token, ibc_token)| {
+                let balance = balances
                     .iter()
-                    .find(|&balance| balance.token.eq(&token.address))
-                    .cloned()
-                    .map(|balance| Amount::from(balance.raw_amount).to_string())
-                    .unwrap_or_else(|| Amount::zero().to_string()),
+                    .find(|&balance| balance.token.eq(&token.address));
+                AddressBalance::from(token, ibc_token, balance)
             })
+            .filter(|balance| !omit_zero || !balance.is_zero())
             .collect();
 
         Ok(denominated_balances)
     }
+
+    pub async fn get_address_balance_history(
+        &self,
+        address: String,
+        token: Option<String>,
+        page: u64,
+    ) -> Result<(Vec<AddressBalanceChange>, u64, u64), BalanceError> {
+        let (balance_changes, total_pages, total_items) = self
+            .balance_repo
+            .get_address_balance_history(address, token, page as i64)
+            .await
+            .map_err(BalanceError::Database)?;
+
+        Ok((
+            balance_changes
+                .into_iter()
+                .map(AddressBalanceChange::from)
+                .collect(),
+            total_pages as u64,
+            total_items as u64,
+        ))
+    }
 }
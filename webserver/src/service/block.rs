@@ -4,7 +4,7 @@ use crate::repository::block::{BlockRepository, BlockRepositoryTrait};
 use crate::repository::tranasaction::{
     TransactionRepository, TransactionRepositoryTrait,
 };
-use crate::response::block::Block;
+use crate::response::block::{Block, BlockMismatch};
 
 #[derive(Clone)]
 pub struct BlockService {
@@ -56,9 +56,12 @@ impl BlockService {
         &self,
         timestamp: i64,
     ) -> Result<Block, BlockError> {
+        let datetime = chrono::DateTime::from_timestamp(timestamp, 0)
+            .ok_or(BlockError::InvalidTimestamp(timestamp))?
+            .naive_utc();
         let block = self
             .block_repo
-            .find_block_by_timestamp(timestamp)
+            .find_block_by_timestamp(datetime)
             .await
             .map_err(BlockError::Database)?;
 
@@ -84,4 +87,21 @@ impl BlockService {
 
         Ok(Block::from(block, prev_block, transactions))
     }
+
+    pub async fn get_block_mismatches(
+        &self,
+        page: u64,
+    ) -> Result<(Vec<BlockMismatch>, u64, u64), BlockError> {
+        let (mismatches, total_pages, total_items) = self
+            .block_repo
+            .find_block_mismatches(page as i64)
+            .await
+            .map_err(BlockError::Database)?;
+
+        Ok((
+            mismatches.into_iter().map(BlockMismatch::from).collect(),
+            total_pages as u64,
+            total_items as u64,
+        ))
+    }
 }
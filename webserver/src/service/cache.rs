@@ -0,0 +1,140 @@
+use std::future::Future;
+
+use deadpool_redis::redis::AsyncCommands;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use orm::crawler_state::{CrawlerNameDb, CrawlerStateDb};
+use orm::schema::crawler_state;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::appstate::AppState;
+
+const CACHE_KEY_PREFIX: &str = "namada-indexer";
+
+#[derive(Clone)]
+pub struct CacheService {
+    app_state: AppState,
+    ttl: usize,
+}
+
+impl CacheService {
+    pub fn new(app_state: AppState, ttl: usize) -> Self {
+        Self { app_state, ttl }
+    }
+
+    /// Read-through cache. The key is versioned by the last processed block
+    /// and epoch of the given crawlers, so entries are invalidated as soon as
+    /// one of them advances. Interval crawlers only record a timestamp, so
+    /// their entries are bounded by the ttl alone. Any cache failure falls
+    /// back to running `query` against the database.
+    pub async fn get_or_insert<P, T, E, F, Fut>(
+        &self,
+        name: &str,
+        params: &P,
+        crawlers: &[CrawlerNameDb],
+        query: F,
+    ) -> Result<T, E>
+    where
+        P: Serialize,
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if !self.app_state.is_cache_enabled() {
+            return query().await;
+        }
+
+        let Some(key) = self.cache_key(name, params, crawlers).await else {
+            return query().await;
+        };
+
+        if let Some(value) = self.get(&key).await {
+            return Ok(value);
+        }
+
+        let value = query().await?;
+        self.set(&key, &value).await;
+
+        Ok(value)
+    }
+
+    async fn cache_key<P: Serialize>(
+        &self,
+        name: &str,
+        params: &P,
+        crawlers: &[CrawlerNameDb],
+    ) -> Option<String> {
+        let params = serde_json::to_string(params)
+            .inspect_err(|e| {
+                tracing::warn!("Error serializing cache key params: {}", e)
+            })
+            .ok()?;
+        let version = self
+            .crawlers_version(crawlers.to_vec())
+            .await
+            .inspect_err(|e| {
+                tracing::warn!("Error reading crawlers state for cache: {}", e)
+            })
+            .ok()?;
+
+        Some(format!("{CACHE_KEY_PREFIX}:{name}:{params}:{version}"))
+    }
+
+    async fn crawlers_version(
+        &self,
+        crawlers: Vec<CrawlerNameDb>,
+    ) -> Result<String, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        let states: Vec<CrawlerStateDb> = conn
+            .interact(move |conn| {
+                crawler_state::table
+                    .filter(crawler_state::name.eq_any(crawlers))
+                    .order(crawler_state::name.asc())
+                    .select(crawler_state::all_columns)
+                    .get_results(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+
+        Ok(states
+            .into_iter()
+            .map(|state| {
+                format!(
+                    "{}-{}-{}",
+                    state.name,
+                    state.last_processed_block.unwrap_or_default(),
+                    state.last_processed_epoch.unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(":"))
+    }
+
+    async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let mut conn = self.app_state.get_cache_connection().await?;
+
+        let value: Option<String> = conn
+            .get(key)
+            .await
+            .inspect_err(|e| tracing::warn!("Error reading from cache: {}", e))
+            .ok()?;
+
+        value.and_then(|value| serde_json::from_str(&value).ok())
+    }
+
+    async fn set<T: Serialize>(&self, key: &str, value: &T) {
+        let Some(mut conn) = self.app_state.get_cache_connection().await else {
+            return;
+        };
+        let Ok(value) = serde_json::to_string(value) else {
+            return;
+        };
+
+        let _: Result<(), _> = conn
+            .set_ex(key, value, self.ttl)
+            .await
+            .inspect_err(|e| tracing::warn!("Error writing to cache: {}", e));
+    }
+}
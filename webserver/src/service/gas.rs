@@ -1,9 +1,16 @@
+use std::collections::HashMap;
+
 use bigdecimal::ToPrimitive;
 
 use crate::appstate::AppState;
+use crate::constant::GAS_ESTIMATE_SAFETY_MARGIN_PCT;
+use crate::dto::gas::GasStatsInterval;
 use crate::error::gas::GasError;
-use crate::repository::gas::{GasRepository, GasRepositoryTrait};
-use crate::response::gas::{GasEstimate, GasPrice};
+use crate::repository::gas::{
+    GasEstimateWindow, GasRepository, GasRepositoryTrait, GasStatsDb,
+    GasStatsPeriod,
+};
+use crate::response::gas::{GasEstimate, GasPrice, GasStats};
 use crate::response::transaction::TransactionKind;
 
 #[derive(Clone)]
@@ -58,8 +65,21 @@ impl GasService {
         reveal_pk: u64,
         signatures: u64,
         tx_size: u64,
+        last_blocks: Option<u64>,
+        last_days: Option<u64>,
+        safety_margin: Option<u64>,
     ) -> Result<GasEstimate, GasError> {
-        let (min, max, avg, count) = self
+        let window = match (last_blocks, last_days) {
+            (Some(last_blocks), _) => {
+                GasEstimateWindow::LastBlocks(last_blocks)
+            }
+            (None, Some(last_days)) => GasEstimateWindow::LastDays(last_days),
+            (None, None) => GasEstimateWindow::All,
+        };
+        let safety_margin =
+            safety_margin.unwrap_or(GAS_ESTIMATE_SAFETY_MARGIN_PCT);
+
+        let (min, max, avg, p50, p90, p99, count) = self
             .gas_repo
             .find_gas_estimates(
                 bond,
@@ -78,23 +98,42 @@ impl GasService {
                 reveal_pk,
                 signatures,
                 tx_size,
+                window,
             )
             .await
             .map_err(GasError::Database)
-            .map(|(min, max, avg, count)| {
+            .map(|(min, max, avg, p50, p90, p99, count)| {
                 let min = min.map(|gas| gas as u64);
                 let max = max.map(|gas| gas as u64);
                 let avg = avg.map(|gas| gas.to_f64().unwrap() as u64);
+                let p50 = p50.map(|gas| gas as u64);
+                let p90 = p90.map(|gas| gas as u64);
+                let p99 = p99.map(|gas| gas as u64);
                 let count = count as u64;
-                (min, max, avg, count)
+                (min, max, avg, p50, p90, p99, count)
             })?;
 
-        if let (Some(min), Some(max), Some(avg), count) = (min, max, avg, count)
+        if let (
+            Some(min),
+            Some(max),
+            Some(avg),
+            Some(p50),
+            Some(p90),
+            Some(p99),
+            count,
+        ) = (min, max, avg, p50, p90, p99, count)
         {
             Ok(GasEstimate {
                 min,
                 max,
                 avg,
+                p50,
+                p90,
+                p99,
+                suggested_gas_limit: Self::with_safety_margin(
+                    p99,
+                    safety_margin,
+                ),
                 total_estimates: count,
             })
         } else {
@@ -152,10 +191,107 @@ impl GasService {
                 min: estimate,
                 max: estimate,
                 avg: estimate,
+                p50: estimate,
+                p90: estimate,
+                p99: estimate,
+                suggested_gas_limit: Self::with_safety_margin(
+                    estimate,
+                    safety_margin,
+                ),
                 total_estimates: 0,
             })
         }
     }
+
+    pub async fn get_gas_stats(
+        &self,
+        interval: GasStatsInterval,
+        token: Option<String>,
+        from_epoch: Option<u32>,
+        to_epoch: Option<u32>,
+        page: u64,
+    ) -> Result<(Vec<GasStats>, u64, u64), GasError> {
+        let from_epoch = from_epoch.map(|epoch| epoch as i32);
+        let to_epoch = to_epoch.map(|epoch| epoch as i32);
+
+        let period = match interval {
+            GasStatsInterval::Day => GasStatsPeriod::Day,
+            GasStatsInterval::Epoch => GasStatsPeriod::Epoch,
+        };
+
+        let (stats, total_pages, total_items) = self
+            .gas_repo
+            .find_gas_stats(
+                period,
+                token.clone(),
+                from_epoch,
+                to_epoch,
+                page as i64,
+            )
+            .await
+            .map_err(GasError::Database)?;
+
+        // Gas price snapshots are taken per epoch, so they can't be matched
+        // to a day
+        let gas_prices: HashMap<(String, String), String> = match period {
+            GasStatsPeriod::Epoch => self
+                .gas_repo
+                .find_gas_price_history(token, from_epoch, to_epoch)
+                .await
+                .map_err(GasError::Database)?
+                .into_iter()
+                .map(|gas_price| {
+                    (
+                        (gas_price.token, gas_price.epoch.to_string()),
+                        gas_price.amount.to_string(),
+                    )
+                })
+                .collect(),
+            GasStatsPeriod::Day => HashMap::new(),
+        };
+
+        let stats = stats
+            .into_iter()
+            .map(|stats| Self::to_gas_stats(stats, &gas_prices))
+            .collect();
+
+        Ok((stats, total_pages as u64, total_items as u64))
+    }
+
+    fn to_gas_stats(
+        (
+            token,
+            period,
+            total_transactions,
+            fees_paid,
+            median_amount_per_gas_unit,
+            gas_used,
+            gas_limit,
+            gas_utilization,
+        ): GasStatsDb,
+        gas_prices: &HashMap<(String, String), String>,
+    ) -> GasStats {
+        let min_gas_price = period.as_ref().and_then(|period| {
+            gas_prices.get(&(token.clone(), period.clone())).cloned()
+        });
+
+        GasStats {
+            token,
+            period,
+            total_transactions: total_transactions.parse::<u64>().unwrap_or(0),
+            fees_paid,
+            median_amount_per_gas_unit,
+            gas_used,
+            gas_limit,
+            gas_utilization,
+            min_gas_price,
+        }
+    }
+
+    fn with_safety_margin(gas: u64, safety_margin: u64) -> u64 {
+        let gas = (gas as u128 * (100 + safety_margin as u128)).div_ceil(100);
+        u64::try_from(gas).unwrap_or(u64::MAX)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -185,3 +321,69 @@ impl DefaultGasTable {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_row(period: Option<&str>) -> GasStatsDb {
+        (
+            "tnam1native".to_string(),
+            period.map(str::to_string),
+            "12".to_string(),
+            "3000".to_string(),
+            Some("0.000001".to_string()),
+            "900".to_string(),
+            "1000".to_string(),
+            Some("0.9".to_string()),
+        )
+    }
+
+    #[test]
+    fn to_gas_stats_attaches_min_gas_price_of_the_epoch() {
+        let gas_prices = HashMap::from([(
+            ("tnam1native".to_string(), "5".to_string()),
+            "0.000001".to_string(),
+        )]);
+
+        let stats = GasService::to_gas_stats(stats_row(Some("5")), &gas_prices);
+
+        assert_eq!(stats.token, "tnam1native");
+        assert_eq!(stats.period.as_deref(), Some("5"));
+        assert_eq!(stats.total_transactions, 12);
+        assert_eq!(stats.fees_paid, "3000");
+        assert_eq!(stats.gas_used, "900");
+        assert_eq!(stats.gas_limit, "1000");
+        assert_eq!(stats.min_gas_price.as_deref(), Some("0.000001"));
+    }
+
+    #[test]
+    fn to_gas_stats_without_matching_gas_price() {
+        let gas_prices = HashMap::from([(
+            ("tnam1native".to_string(), "5".to_string()),
+            "0.000001".to_string(),
+        )]);
+
+        let other_epoch =
+            GasService::to_gas_stats(stats_row(Some("6")), &gas_prices);
+        assert_eq!(other_epoch.min_gas_price, None);
+
+        let no_period = GasService::to_gas_stats(stats_row(None), &gas_prices);
+        assert_eq!(no_period.period, None);
+        assert_eq!(no_period.min_gas_price, None);
+    }
+
+    #[test]
+    fn with_safety_margin_rounds_up() {
+        assert_eq!(GasService::with_safety_margin(1000, 0), 1000);
+        assert_eq!(GasService::with_safety_margin(1000, 20), 1200);
+        assert_eq!(GasService::with_safety_margin(1001, 20), 1202);
+        assert_eq!(GasService::with_safety_margin(0, 100), 0);
+    }
+
+    #[test]
+    fn with_safety_margin_saturates() {
+        assert_eq!(GasService::with_safety_margin(u64::MAX, 0), u64::MAX);
+        assert_eq!(GasService::with_safety_margin(u64::MAX, 100), u64::MAX);
+    }
+}
@@ -1,13 +1,22 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
 use orm::governance_proposal::{
     GovernanceProposalKindDb, GovernanceProposalResultDb,
+    GovernanceProposalTallyTypeDb,
 };
+use orm::governance_votes::GovernanceVoteKindDb;
+use subtle_encoding::hex;
 
 use crate::appstate::AppState;
+use crate::constant::ITEM_PER_PAGE;
 use crate::dto::governance::{ProposalKind, ProposalStatus};
 use crate::error::governance::GovernanceError;
 use crate::repository::chain::{ChainRepository, ChainRepositoryTrait};
 use crate::repository::governance::{GovernanceRepo, GovernanceRepoTrait};
-use crate::response::governance::{Proposal, ProposalVote};
+use crate::response::governance::{
+    Proposal, ProposalEvent, ProposalVote, ProposalVoteBreakdown,
+    ProposalWasmCode, TallyType, VotePowerBreakdown,
+};
+use crate::service::utils::{decode_cursor, encode_cursor, raw_amount_to_nam};
 
 #[derive(Clone)]
 pub struct GovernanceService {
@@ -81,6 +90,52 @@ impl GovernanceService {
         Ok(db_proposal.map(|proposal| proposal.data))
     }
 
+    pub async fn find_proposal_wasm_code(
+        &self,
+        proposal_id: u64,
+    ) -> Result<ProposalWasmCode, GovernanceError> {
+        let db_proposal = self
+            .governance_repo
+            .find_governance_proposals_by_id(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?
+            .ok_or(GovernanceError::NotFound(proposal_id))?;
+
+        ProposalWasmCode::from_proposal_db(
+            db_proposal.wasm_code_hash,
+            db_proposal.wasm_code_size,
+            db_proposal.wasm_code_name,
+        )
+        .ok_or(GovernanceError::WasmCodeNotFound(proposal_id))
+    }
+
+    pub async fn find_proposal_wasm_code_bytes(
+        &self,
+        proposal_id: u64,
+    ) -> Result<(ProposalWasmCode, Vec<u8>), GovernanceError> {
+        let db_proposal = self
+            .governance_repo
+            .find_governance_proposals_by_id(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?
+            .ok_or(GovernanceError::NotFound(proposal_id))?;
+
+        let wasm_code = ProposalWasmCode::from_proposal_db(
+            db_proposal.wasm_code_hash,
+            db_proposal.wasm_code_size,
+            db_proposal.wasm_code_name,
+        )
+        .ok_or(GovernanceError::WasmCodeNotFound(proposal_id))?;
+
+        // The data of default with wasm proposals is the hex encoded wasm code
+        let bytes = db_proposal
+            .data
+            .and_then(|data| hex::decode(data).ok())
+            .ok_or(GovernanceError::WasmCodeNotFound(proposal_id))?;
+
+        Ok((wasm_code, bytes))
+    }
+
     pub async fn find_all_governance_proposals(
         &self,
         status: Option<ProposalStatus>,
@@ -157,7 +212,8 @@ impl GovernanceService {
         &self,
         proposal_id: u64,
         page: u64,
-    ) -> Result<(Vec<ProposalVote>, u64, u64), GovernanceError> {
+    ) -> Result<(Vec<ProposalVote>, u64, u64, Option<String>), GovernanceError>
+    {
         let db_proposal = self
             .governance_repo
             .find_governance_proposals_by_id(proposal_id as i32)
@@ -174,6 +230,14 @@ impl GovernanceService {
             .await
             .map_err(GovernanceError::Database)?;
 
+        let next_cursor = if (page as i64) < total_pages {
+            db_proposal_votes
+                .last()
+                .map(|vote| encode_cursor(&[vote.id.to_string()]))
+        } else {
+            None
+        };
+
         Ok((
             db_proposal_votes
                 .into_iter()
@@ -181,6 +245,54 @@ impl GovernanceService {
                 .collect(),
             total_pages as u64,
             total_items as u64,
+            next_cursor,
+        ))
+    }
+
+    pub async fn find_governance_proposal_votes_by_cursor(
+        &self,
+        proposal_id: u64,
+        cursor: String,
+    ) -> Result<(Vec<ProposalVote>, Option<String>), GovernanceError> {
+        let after = decode_cursor(&cursor, 1)
+            .and_then(|parts| parts.first()?.parse::<i32>().ok())
+            .ok_or(GovernanceError::InvalidCursor(cursor))?;
+
+        let db_proposal = self
+            .governance_repo
+            .find_governance_proposals_by_id(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?;
+
+        if db_proposal.is_none() {
+            return Err(GovernanceError::NotFound(proposal_id));
+        }
+
+        let mut db_proposal_votes = self
+            .governance_repo
+            .find_governance_proposal_votes_by_cursor(
+                proposal_id as i32,
+                Some(after),
+                ITEM_PER_PAGE as i64 + 1,
+            )
+            .await
+            .map_err(GovernanceError::Database)?;
+
+        let next_cursor = if db_proposal_votes.len() > ITEM_PER_PAGE as usize {
+            db_proposal_votes.truncate(ITEM_PER_PAGE as usize);
+            db_proposal_votes
+                .last()
+                .map(|vote| encode_cursor(&[vote.id.to_string()]))
+        } else {
+            None
+        };
+
+        Ok((
+            db_proposal_votes
+                .into_iter()
+                .map(ProposalVote::from)
+                .collect(),
+            next_cursor,
         ))
     }
 
@@ -230,6 +342,99 @@ impl GovernanceService {
             .collect())
     }
 
+    pub async fn find_governance_proposal_vote_breakdown(
+        &self,
+        proposal_id: u64,
+    ) -> Result<ProposalVoteBreakdown, GovernanceError> {
+        let db_proposal = self
+            .governance_repo
+            .find_governance_proposals_by_id(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?
+            .ok_or(GovernanceError::NotFound(proposal_id))?;
+
+        let total_voting_power = db_proposal
+            .total_voting_power
+            .ok_or(GovernanceError::VotingPowerNotFound(proposal_id))?;
+
+        let db_vote_power = self
+            .governance_repo
+            .find_governance_proposal_vote_power(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?;
+
+        let mut validators = VotePowerTally::default();
+        let mut delegators = VotePowerTally::default();
+        let mut overriding_delegators = 0;
+
+        for (is_validator, kind, voting_power, voters, overriding) in
+            db_vote_power
+        {
+            let tally = if is_validator.unwrap_or_default() {
+                &mut validators
+            } else {
+                overriding_delegators += overriding as u64;
+                &mut delegators
+            };
+            tally.add(kind, voting_power.unwrap_or_default(), voters as u64);
+        }
+
+        let turnout = validators.total() + delegators.total();
+
+        // The ledger requires 2/5 of the voting power to vote on proposals
+        // tallied by two fifths, and 1/3 otherwise
+        let quorum = match db_proposal.tally_type {
+            GovernanceProposalTallyTypeDb::TwoFifths => {
+                &total_voting_power * BigDecimal::from(2) / BigDecimal::from(5)
+            }
+            GovernanceProposalTallyTypeDb::OneHalfOverOneThird
+            | GovernanceProposalTallyTypeDb::LessOneHalfOverOneThirdNay => {
+                &total_voting_power / BigDecimal::from(3)
+            }
+        }
+        .with_scale_round(0, RoundingMode::Ceiling);
+
+        Ok(ProposalVoteBreakdown {
+            proposal_id,
+            tally_type: TallyType::from(db_proposal.tally_type),
+            status: db_proposal.result.into(),
+            total_voting_power: raw_amount_to_nam(
+                total_voting_power.to_string(),
+            ),
+            validators: validators.into(),
+            delegators: delegators.into(),
+            overriding_delegators,
+            turnout: raw_amount_to_nam(turnout.to_string()),
+            turnout_percentage: percentage(&turnout, &total_voting_power),
+            quorum: raw_amount_to_nam(quorum.to_string()),
+            quorum_progress: percentage(&turnout, &quorum),
+            quorum_reached: turnout >= quorum,
+        })
+    }
+
+    pub async fn find_governance_proposal_timeline(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Vec<ProposalEvent>, GovernanceError> {
+        let db_proposal = self
+            .governance_repo
+            .find_governance_proposals_by_id(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?;
+
+        if db_proposal.is_none() {
+            return Err(GovernanceError::NotFound(proposal_id));
+        }
+
+        let db_events = self
+            .governance_repo
+            .find_proposal_events(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?;
+
+        Ok(db_events.into_iter().map(ProposalEvent::from).collect())
+    }
+
     fn map_status(
         &self,
         status: Option<ProposalStatus>,
@@ -258,3 +463,54 @@ impl GovernanceService {
         })
     }
 }
+
+#[derive(Default)]
+struct VotePowerTally {
+    yay: BigDecimal,
+    nay: BigDecimal,
+    abstain: BigDecimal,
+    voters: u64,
+}
+
+impl VotePowerTally {
+    fn add(
+        &mut self,
+        kind: GovernanceVoteKindDb,
+        voting_power: BigDecimal,
+        voters: u64,
+    ) {
+        match kind {
+            GovernanceVoteKindDb::Yay => self.yay += voting_power,
+            GovernanceVoteKindDb::Nay => self.nay += voting_power,
+            GovernanceVoteKindDb::Abstain => self.abstain += voting_power,
+            GovernanceVoteKindDb::Unknown => (),
+        }
+        self.voters += voters;
+    }
+
+    fn total(&self) -> BigDecimal {
+        &self.yay + &self.nay + &self.abstain
+    }
+}
+
+impl From<VotePowerTally> for VotePowerBreakdown {
+    fn from(value: VotePowerTally) -> Self {
+        Self {
+            yay: raw_amount_to_nam(value.yay.to_string()),
+            nay: raw_amount_to_nam(value.nay.to_string()),
+            abstain: raw_amount_to_nam(value.abstain.to_string()),
+            voters: value.voters,
+        }
+    }
+}
+
+fn percentage(part: &BigDecimal, total: &BigDecimal) -> String {
+    if total.is_zero() {
+        return BigDecimal::zero().to_string();
+    }
+
+    (part * BigDecimal::from(100) / total)
+        .round(2)
+        .normalized()
+        .to_string()
+}
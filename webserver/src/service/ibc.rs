@@ -1,11 +1,15 @@
 use bigdecimal::BigDecimal;
-use orm::ibc::IbcAckStatusDb;
+use chrono::Utc;
+use orm::ibc::IbcPacketStatusDb;
 
 use crate::appstate::AppState;
 use crate::error::ibc::IbcError;
-use crate::repository::ibc::{IbcRepository, IbcRepositoryTrait};
+use crate::repository::ibc::{
+    EpochRange, IbcRepository, IbcRepositoryTrait, TraceFlow,
+};
 use crate::response::ibc::{
-    IbcAck, IbcAckStatus, IbcRateLimit, IbcTokenFlow, IbcTokenThroughput,
+    IbcAck, IbcAckStatus, IbcChannel, IbcChannelVolume, IbcPacket,
+    IbcRateLimit, IbcThroughputUtilization, IbcTokenFlow, IbcTokenThroughput,
 };
 
 #[derive(Clone)]
@@ -25,33 +29,84 @@ impl IbcService {
         tx_id: String,
     ) -> Result<IbcAck, IbcError> {
         self.ibc_repo
-            .find_ibc_ack(tx_id)
+            .find_ibc_packet_by_tx_id(tx_id)
             .await
             .map_err(IbcError::Database)
-            .map(|ack| match ack {
-                Some(ack) => IbcAck {
-                    status: match ack.status {
-                        IbcAckStatusDb::Unknown => IbcAckStatus::Unknown,
-                        IbcAckStatusDb::Timeout => IbcAckStatus::Timeout,
-                        IbcAckStatusDb::Fail => IbcAckStatus::Fail,
-                        IbcAckStatusDb::Success => IbcAckStatus::Success,
+            .map(|packet| match packet {
+                Some(packet) => IbcAck {
+                    status: match packet.status {
+                        IbcPacketStatusDb::Pending => IbcAckStatus::Unknown,
+                        IbcPacketStatusDb::TimedOut => IbcAckStatus::Timeout,
+                        IbcPacketStatusDb::Failed => IbcAckStatus::Fail,
+                        IbcPacketStatusDb::Received
+                        | IbcPacketStatusDb::Acknowledged => {
+                            IbcAckStatus::Success
+                        }
                     },
-                    timeout: Some(ack.timeout),
+                    timeout: Some(packet.timeout_timestamp),
+                    packet: Some(IbcPacket::from(packet)),
                 },
                 None => IbcAck {
                     status: IbcAckStatus::Unknown,
                     timeout: None,
+                    packet: None,
                 },
             })
     }
 
+    pub async fn get_in_flight_packets(
+        &self,
+        channel: String,
+        page: u64,
+    ) -> Result<(Vec<IbcPacket>, u64, u64), IbcError> {
+        let (packets, total_pages, total_items) = self
+            .ibc_repo
+            .find_in_flight_packets(channel, Self::now(), page as i64)
+            .await
+            .map_err(IbcError::Database)?;
+
+        Ok((
+            packets.into_iter().map(IbcPacket::from).collect(),
+            total_pages as u64,
+            total_items as u64,
+        ))
+    }
+
+    pub async fn get_timed_out_packets(
+        &self,
+        channel: String,
+        page: u64,
+    ) -> Result<(Vec<IbcPacket>, u64, u64), IbcError> {
+        let (packets, total_pages, total_items) = self
+            .ibc_repo
+            .find_timed_out_packets(channel, Self::now(), page as i64)
+            .await
+            .map_err(IbcError::Database)?;
+
+        Ok((
+            packets.into_iter().map(IbcPacket::from).collect(),
+            total_pages as u64,
+            total_items as u64,
+        ))
+    }
+
+    /// Packet timeouts are unix timestamps in nanoseconds
+    fn now() -> i64 {
+        Utc::now().timestamp_nanos_opt().unwrap_or(i64::MAX)
+    }
+
     pub async fn get_throughput_limits(
         &self,
         matching_token_address: Option<String>,
         matching_rate_limit: Option<BigDecimal>,
+        epochs: EpochRange,
     ) -> Result<Vec<IbcRateLimit>, IbcError> {
         self.ibc_repo
-            .get_throughput_limits(matching_token_address, matching_rate_limit)
+            .get_throughput_limits(
+                matching_token_address,
+                matching_rate_limit,
+                epochs,
+            )
             .await
             .map_err(IbcError::Database)
             .map(|limits| unsafe {
@@ -59,9 +114,10 @@ impl IbcService {
                 // the vec and creating a new one, just to convert between
                 // types.
 
-                const _: () =
-                    assert_conversion_safety::<(String, String), IbcRateLimit>(
-                    );
+                const _: () = assert_conversion_safety::<
+                    (String, String, String),
+                    IbcRateLimit,
+                >();
 
                 // SAFETY: We have asserted the safety of the conversion above
                 std::mem::transmute(limits)
@@ -71,9 +127,10 @@ impl IbcService {
     pub async fn get_token_flows(
         &self,
         matching_token_address: Option<String>,
+        epochs: EpochRange,
     ) -> Result<Vec<IbcTokenFlow>, IbcError> {
         self.ibc_repo
-            .get_token_flows(matching_token_address)
+            .get_token_flows(matching_token_address, epochs)
             .await
             .map_err(IbcError::Database)
             .map(|flows| unsafe {
@@ -82,7 +139,7 @@ impl IbcService {
                 // types.
 
                 const _: () = assert_conversion_safety::<
-                    (String, String, String),
+                    (String, String, String, String),
                     IbcTokenFlow,
                 >();
 
@@ -94,7 +151,25 @@ impl IbcService {
     pub async fn get_token_throughput(
         &self,
         token: String,
+        epoch: Option<u32>,
     ) -> Result<IbcTokenThroughput, IbcError> {
+        if let Some(epoch) = epoch {
+            return self
+                .get_throughput_utilization(
+                    Some(token.clone()),
+                    Some(epoch),
+                    Some(epoch),
+                )
+                .await?
+                .into_iter()
+                .next()
+                .map(|utilization| IbcTokenThroughput {
+                    throughput: utilization.throughput,
+                    limit: utilization.limit,
+                })
+                .ok_or(IbcError::TokenThroughputNotFound(token, epoch));
+        }
+
         self.ibc_repo
             .get_token_throughput(token)
             .await
@@ -113,6 +188,123 @@ impl IbcService {
                 std::mem::transmute(throughput)
             })
     }
+
+    pub async fn get_throughput_utilization(
+        &self,
+        matching_token_address: Option<String>,
+        from_epoch: Option<u32>,
+        to_epoch: Option<u32>,
+    ) -> Result<Vec<IbcThroughputUtilization>, IbcError> {
+        self.ibc_repo
+            .get_throughput_utilization(
+                matching_token_address,
+                from_epoch.map(|epoch| epoch as i32),
+                to_epoch.map(|epoch| epoch as i32),
+            )
+            .await
+            .map_err(IbcError::Database)
+            .map(|rows| {
+                rows.into_iter()
+                    .map(
+                        |(
+                            token_address,
+                            epoch,
+                            throughput,
+                            limit,
+                            utilization,
+                        )| {
+                            IbcThroughputUtilization {
+                                token_address,
+                                epoch,
+                                throughput,
+                                limit,
+                                utilization,
+                            }
+                        },
+                    )
+                    .collect()
+            })
+    }
+
+    /// The volume of a channel is the one of the tokens whose trace starts
+    /// with its port and channel, i.e. the tokens that entered namada through
+    /// it. Native tokens sent through a channel aren't counted.
+    pub async fn get_channels(
+        &self,
+        counterparty_chain_id: Option<String>,
+        from_epoch: Option<u32>,
+        to_epoch: Option<u32>,
+    ) -> Result<Vec<IbcChannel>, IbcError> {
+        let channels = self
+            .ibc_repo
+            .find_ibc_channels(counterparty_chain_id)
+            .await
+            .map_err(IbcError::Database)?;
+
+        let trace_flows = self
+            .ibc_repo
+            .get_trace_flows(
+                from_epoch.map(|epoch| epoch as i32),
+                to_epoch.map(|epoch| epoch as i32),
+            )
+            .await
+            .map_err(IbcError::Database)?;
+
+        Ok(channels
+            .into_iter()
+            .map(|channel| {
+                let volumes = channel_volumes(
+                    &channel.port_id,
+                    &channel.channel_id,
+                    &trace_flows,
+                );
+                IbcChannel::from(channel, volumes)
+            })
+            .collect())
+    }
+
+    /// Without any epoch only the latest one is returned, to keep the
+    /// original behaviour of the endpoints. A single `epoch` takes
+    /// precedence over `from_epoch` and `to_epoch`.
+    pub fn epoch_range(
+        epoch: Option<u32>,
+        from_epoch: Option<u32>,
+        to_epoch: Option<u32>,
+    ) -> EpochRange {
+        match (epoch, from_epoch, to_epoch) {
+            (None, None, None) => EpochRange::Latest,
+            (Some(epoch), _, _) => {
+                EpochRange::Between(Some(epoch as i32), Some(epoch as i32))
+            }
+            (None, from_epoch, to_epoch) => EpochRange::Between(
+                from_epoch.map(|epoch| epoch as i32),
+                to_epoch.map(|epoch| epoch as i32),
+            ),
+        }
+    }
+}
+
+/// Volumes of the tokens whose trace starts with the port and channel. Each
+/// token is kept apart, their amounts are in different base units.
+fn channel_volumes(
+    port_id: &str,
+    channel_id: &str,
+    trace_flows: &[TraceFlow],
+) -> Vec<IbcChannelVolume> {
+    let prefix = format!("{port_id}/{channel_id}/");
+
+    trace_flows
+        .iter()
+        .filter(|(_, trace, _, _)| trace.starts_with(&prefix))
+        .map(
+            |(token_address, trace, deposit, withdraw)| IbcChannelVolume {
+                token_address: token_address.clone(),
+                trace: trace.clone(),
+                deposit: deposit.clone().unwrap_or_default().to_string(),
+                withdraw: withdraw.clone().unwrap_or_default().to_string(),
+            },
+        )
+        .collect()
 }
 
 #[allow(dead_code)]
@@ -125,3 +317,123 @@ const fn assert_conversion_safety<From, To>() {
         panic!("alignment is invalid");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace_flow(
+        token_address: &str,
+        trace: &str,
+        deposit: Option<u64>,
+        withdraw: Option<u64>,
+    ) -> TraceFlow {
+        (
+            token_address.to_string(),
+            trace.to_string(),
+            deposit.map(BigDecimal::from),
+            withdraw.map(BigDecimal::from),
+        )
+    }
+
+    #[test]
+    fn test_channel_volumes_per_token() {
+        let trace_flows = vec![
+            trace_flow(
+                "tnam1atom",
+                "transfer/channel-1/uatom",
+                Some(10),
+                Some(4),
+            ),
+            trace_flow("tnam1osmo", "transfer/channel-1/uosmo", Some(7), None),
+            trace_flow("tnam1tia", "transfer/channel-2/utia", Some(3), Some(1)),
+        ];
+
+        let volumes = channel_volumes("transfer", "channel-1", &trace_flows);
+
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(volumes[0].token_address, "tnam1atom");
+        assert_eq!(volumes[0].trace, "transfer/channel-1/uatom");
+        assert_eq!(volumes[0].deposit, "10");
+        assert_eq!(volumes[0].withdraw, "4");
+        assert_eq!(volumes[1].token_address, "tnam1osmo");
+        assert_eq!(volumes[1].deposit, "7");
+        assert_eq!(volumes[1].withdraw, "0");
+    }
+
+    #[test]
+    fn test_channel_volumes_prefix() {
+        let trace_flows = vec![
+            // A longer channel id sharing the same digits
+            trace_flow("tnam1a", "transfer/channel-10/uatom", Some(1), None),
+            // Multi-hop token that only went through the channel later on
+            trace_flow(
+                "tnam1b",
+                "transfer/channel-2/transfer/channel-1/uatom",
+                Some(1),
+                None,
+            ),
+            trace_flow("tnam1c", "other/channel-1/uatom", Some(1), None),
+            trace_flow("tnam1d", "transfer/channel-1", Some(1), None),
+            trace_flow(
+                "tnam1e",
+                "transfer/channel-1/transfer/channel-5/uatom",
+                Some(2),
+                None,
+            ),
+        ];
+
+        let volumes = channel_volumes("transfer", "channel-1", &trace_flows);
+
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].token_address, "tnam1e");
+    }
+
+    #[test]
+    fn test_epoch_range_latest() {
+        assert_eq!(
+            IbcService::epoch_range(None, None, None),
+            EpochRange::Latest
+        );
+    }
+
+    #[test]
+    fn test_epoch_range_single_epoch() {
+        assert_eq!(
+            IbcService::epoch_range(Some(5), None, None),
+            EpochRange::Between(Some(5), Some(5))
+        );
+    }
+
+    #[test]
+    fn test_epoch_range_epoch_takes_precedence() {
+        assert_eq!(
+            IbcService::epoch_range(Some(5), Some(1), Some(10)),
+            EpochRange::Between(Some(5), Some(5))
+        );
+        assert_eq!(
+            IbcService::epoch_range(Some(5), Some(1), None),
+            EpochRange::Between(Some(5), Some(5))
+        );
+        assert_eq!(
+            IbcService::epoch_range(Some(5), None, Some(10)),
+            EpochRange::Between(Some(5), Some(5))
+        );
+    }
+
+    #[test]
+    fn test_epoch_range_bounds() {
+        assert_eq!(
+            IbcService::epoch_range(None, Some(1), Some(10)),
+            EpochRange::Between(Some(1), Some(10))
+        );
+        assert_eq!(
+            IbcService::epoch_range(None, Some(1), None),
+            EpochRange::Between(Some(1), None)
+        );
+        assert_eq!(
+            IbcService::epoch_range(None, None, Some(10)),
+            EpochRange::Between(None, Some(10))
+        );
+    }
+}
@@ -1,5 +1,7 @@
+pub mod account;
 pub mod balance;
 pub mod block;
+pub mod cache;
 pub mod chain;
 pub mod crawler_state;
 pub mod gas;
@@ -9,5 +11,7 @@ pub mod masp;
 pub mod pgf;
 pub mod pos;
 pub mod revealed_pk;
+pub mod search;
+pub mod subscription;
 pub mod transaction;
 pub mod utils;
@@ -1,14 +1,23 @@
+use std::collections::HashMap;
+
 use bigdecimal::{BigDecimal, Zero};
 use orm::helpers::OrderByDb;
-use orm::validators::{ValidatorSortByDb, ValidatorStateDb};
+use orm::pos_rewards::PosRewardLedgerKindDb;
+use orm::validators::{
+    ValidatorEpochSnapshotDb, ValidatorSortByDb, ValidatorStateDb,
+};
 
 use crate::appstate::AppState;
-use crate::dto::pos::{OrderByDto, ValidatorSortFieldDto, ValidatorStateDto};
+use crate::dto::pos::{
+    OrderByDto, RewardLedgerKindDto, ValidatorSortFieldDto, ValidatorStateDto,
+};
 use crate::error::pos::PoSError;
 use crate::repository::chain::{ChainRepository, ChainRepositoryTrait};
 use crate::repository::pos::{PosRepository, PosRepositoryTrait};
 use crate::response::pos::{
-    Bond, BondStatus, MergedBond, Reward, Unbond, ValidatorWithId, Withdraw,
+    Bond, BondStatus, DelegationApr, DelegatorApr, MergedBond, Reward,
+    RewardLedgerEntry, Unbond, ValidatorApr, ValidatorEpochSnapshot,
+    ValidatorWithId, Withdraw,
 };
 
 #[derive(Clone)]
@@ -272,6 +281,52 @@ impl PosService {
         Ok((withdraws, total_pages as u64, total_items as u64))
     }
 
+    /// Returns the stake, commission and state of a validator at each
+    /// indexed epoch, together with the blocks it proposed in that epoch
+    pub async fn get_validator_history(
+        &self,
+        address: String,
+        page: u64,
+    ) -> Result<(Vec<ValidatorEpochSnapshot>, u64, u64), PoSError> {
+        let db_validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address.clone()))?;
+
+        let (db_snapshots, total_pages, total_items) = self
+            .pos_repo
+            .find_validator_snapshots(db_validator.id, page as i64)
+            .await
+            .map_err(PoSError::Database)?;
+
+        let epochs = db_snapshots
+            .iter()
+            .map(|snapshot| snapshot.epoch)
+            .collect::<Vec<_>>();
+
+        let total_blocks: HashMap<Option<i32>, i64> = self
+            .pos_repo
+            .count_blocks_per_epoch(epochs.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .into_iter()
+            .collect();
+        let proposed_blocks: HashMap<Option<i32>, i64> = self
+            .pos_repo
+            .count_proposed_blocks_per_epoch(address, epochs)
+            .await
+            .map_err(PoSError::Database)?
+            .into_iter()
+            .collect();
+
+        let snapshots =
+            with_block_counts(db_snapshots, &total_blocks, &proposed_blocks);
+
+        Ok((snapshots, total_pages as u64, total_items as u64))
+    }
+
     pub async fn get_rewards_by_address(
         &self,
         address: String,
@@ -302,6 +357,175 @@ impl PosService {
         Ok(rewards)
     }
 
+    pub async fn get_reward_history(
+        &self,
+        address: String,
+        validator: Option<String>,
+        kind: Option<RewardLedgerKindDto>,
+        from_epoch: Option<u32>,
+        to_epoch: Option<u32>,
+        page: u64,
+    ) -> Result<(Vec<RewardLedgerEntry>, u64, u64), PoSError> {
+        let kind = kind.map(|kind| match kind {
+            RewardLedgerKindDto::Accrued => PosRewardLedgerKindDb::Accrued,
+            RewardLedgerKindDto::Claimed => PosRewardLedgerKindDb::Claimed,
+        });
+
+        let (db_entries, total_pages, total_items) = self
+            .pos_repo
+            .find_reward_ledger(
+                address,
+                validator,
+                kind,
+                from_epoch.map(|epoch| epoch as i32),
+                to_epoch.map(|epoch| epoch as i32),
+                page as i64,
+            )
+            .await
+            .map_err(PoSError::Database)?;
+
+        let entries = db_entries
+            .into_iter()
+            .map(|(db_entry, db_validator)| {
+                RewardLedgerEntry::from(db_entry, db_validator)
+            })
+            .collect();
+
+        Ok((entries, total_pages as u64, total_items as u64))
+    }
+
+    pub async fn get_delegator_apr(
+        &self,
+        address: String,
+        from_epoch: Option<u32>,
+        to_epoch: Option<u32>,
+    ) -> Result<DelegatorApr, PoSError> {
+        let epochs_per_year = self.get_epochs_per_year().await?;
+
+        let db_accrued_rewards = self
+            .pos_repo
+            .get_accrued_rewards(
+                Some(address),
+                None,
+                from_epoch.map(|epoch| epoch as i32),
+                to_epoch.map(|epoch| epoch as i32),
+            )
+            .await
+            .map_err(PoSError::Database)?;
+
+        let mut total_rewards = BigDecimal::zero();
+        let mut total_bonded = BigDecimal::zero();
+        let mut delegations = vec![];
+
+        for (validator_id, rewards, bonded, epochs, _) in db_accrued_rewards {
+            let rewards = rewards.unwrap_or_default();
+            let bonded = bonded.unwrap_or_default();
+
+            let db_validator = self
+                .pos_repo
+                .find_validator_by_id(validator_id)
+                .await
+                .map_err(PoSError::Database)?;
+            let Some(db_validator) = db_validator else {
+                tracing::error!(
+                    "Couldn't find validator with id {} in rewards query",
+                    validator_id
+                );
+                continue;
+            };
+
+            delegations.push(DelegationApr {
+                validator: ValidatorWithId::from(db_validator, None),
+                min_denom_amount: rewards.to_string(),
+                epochs: epochs as u64,
+                apr: Self::realized_apr(&rewards, &bonded, epochs_per_year),
+            });
+
+            total_rewards += rewards;
+            total_bonded += bonded;
+        }
+
+        Ok(DelegatorApr {
+            min_denom_amount: total_rewards.to_string(),
+            apr: Self::realized_apr(
+                &total_rewards,
+                &total_bonded,
+                epochs_per_year,
+            ),
+            delegations,
+        })
+    }
+
+    pub async fn get_validator_apr(
+        &self,
+        address: String,
+        from_epoch: Option<u32>,
+        to_epoch: Option<u32>,
+    ) -> Result<ValidatorApr, PoSError> {
+        let db_validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address))?;
+
+        let epochs_per_year = self.get_epochs_per_year().await?;
+
+        let (rewards, bonded, epochs, delegators) = self
+            .pos_repo
+            .get_accrued_rewards(
+                None,
+                Some(db_validator.id),
+                from_epoch.map(|epoch| epoch as i32),
+                to_epoch.map(|epoch| epoch as i32),
+            )
+            .await
+            .map_err(PoSError::Database)?
+            .into_iter()
+            .next()
+            .map(|(_, rewards, bonded, epochs, delegators)| {
+                (
+                    rewards.unwrap_or_default(),
+                    bonded.unwrap_or_default(),
+                    epochs as u64,
+                    delegators as u64,
+                )
+            })
+            .unwrap_or_default();
+
+        Ok(ValidatorApr {
+            validator: ValidatorWithId::from(db_validator, None),
+            min_denom_amount: rewards.to_string(),
+            epochs,
+            delegators,
+            apr: Self::realized_apr(&rewards, &bonded, epochs_per_year),
+        })
+    }
+
+    async fn get_epochs_per_year(&self) -> Result<i32, PoSError> {
+        self.chain_repo
+            .find_chain_parameters()
+            .await
+            .map(|parameters| parameters.epochs_per_year)
+            .map_err(PoSError::Database)
+    }
+
+    /// The bonded amount is summed over the same epochs as the rewards, so
+    /// their ratio is the average return per epoch
+    fn realized_apr(
+        rewards: &BigDecimal,
+        bonded: &BigDecimal,
+        epochs_per_year: i32,
+    ) -> Option<String> {
+        if bonded.is_zero() {
+            return None;
+        }
+
+        let apr = rewards * BigDecimal::from(epochs_per_year) / bonded;
+
+        Some(apr.round(6).normalized().to_string())
+    }
+
     // TODO: maybe return object(struct) instead
     pub async fn get_total_voting_power(&self) -> Result<u64, PoSError> {
         let total_voting_power_db = self
@@ -347,3 +571,107 @@ impl PosService {
         )
     }
 }
+
+/// Attaches the blocks proposed by the validator and the total blocks of each
+/// snapshot epoch, epochs without indexed blocks count as zero
+fn with_block_counts(
+    db_snapshots: Vec<ValidatorEpochSnapshotDb>,
+    total_blocks: &HashMap<Option<i32>, i64>,
+    proposed_blocks: &HashMap<Option<i32>, i64>,
+) -> Vec<ValidatorEpochSnapshot> {
+    db_snapshots
+        .into_iter()
+        .map(|snapshot| {
+            let epoch = Some(snapshot.epoch);
+            let proposed =
+                proposed_blocks.get(&epoch).copied().unwrap_or_default();
+            let total = total_blocks.get(&epoch).copied().unwrap_or_default();
+
+            ValidatorEpochSnapshot::from(
+                snapshot,
+                proposed as u64,
+                total as u64,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::pos::ValidatorState;
+
+    fn snapshot(
+        epoch: i32,
+        state: ValidatorStateDb,
+    ) -> ValidatorEpochSnapshotDb {
+        ValidatorEpochSnapshotDb {
+            id: epoch,
+            validator_id: 1,
+            epoch,
+            voting_power: 100 * epoch,
+            max_commission: "0.1".to_string(),
+            commission: "0.05".to_string(),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_with_block_counts() {
+        let total_blocks = HashMap::from([(Some(1), 10), (Some(2), 12)]);
+        let proposed_blocks = HashMap::from([(Some(2), 3)]);
+
+        let snapshots = with_block_counts(
+            vec![
+                snapshot(1, ValidatorStateDb::Consensus),
+                snapshot(2, ValidatorStateDb::Jailed),
+                snapshot(3, ValidatorStateDb::Inactive),
+            ],
+            &total_blocks,
+            &proposed_blocks,
+        );
+
+        let counts = snapshots
+            .iter()
+            .map(|snapshot| {
+                (
+                    snapshot.epoch.as_str(),
+                    snapshot.proposed_blocks,
+                    snapshot.total_blocks,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![("1", 0, 10), ("2", 3, 12), ("3", 0, 0)]);
+
+        assert_eq!(snapshots[1].voting_power, "200");
+        assert_eq!(snapshots[1].commission, "0.05");
+        assert!(matches!(snapshots[1].state, ValidatorState::Jailed));
+    }
+
+    #[test]
+    fn test_realized_apr() {
+        let apr = PosService::realized_apr(
+            &BigDecimal::from(5),
+            &BigDecimal::from(10_000),
+            730,
+        );
+        assert_eq!(apr.as_deref(), Some("0.365"));
+
+        let apr = PosService::realized_apr(
+            &BigDecimal::from(1),
+            &BigDecimal::from(3),
+            1,
+        );
+        assert_eq!(apr.as_deref(), Some("0.333333"));
+    }
+
+    #[test]
+    fn test_realized_apr_without_bonds() {
+        let apr = PosService::realized_apr(
+            &BigDecimal::from(5),
+            &BigDecimal::zero(),
+            730,
+        );
+        assert_eq!(apr, None);
+    }
+}
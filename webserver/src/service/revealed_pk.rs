@@ -2,8 +2,8 @@ use std::str::FromStr;
 
 use namada_sdk::address::Address as NamadaAddress;
 use namada_sdk::rpc;
-use namada_sdk::tendermint_rpc::HttpClient;
 use orm::revealed_pk::RevealedPkInsertDb;
+use shared::client::RpcClient;
 
 use crate::appstate::AppState;
 use crate::error::revealed_pk::RevealedPkError;
@@ -24,7 +24,7 @@ impl RevealedPkService {
 
     pub async fn get_revealed_pk_by_address(
         &self,
-        client: &HttpClient,
+        client: &RpcClient,
         address: String,
     ) -> Result<RevealedPk, RevealedPkError> {
         // We look for a revealed public key in the database
@@ -0,0 +1,288 @@
+use crate::appstate::AppState;
+use crate::constant::SEARCH_MAX_RESULTS;
+use crate::error::search::SearchError;
+use crate::repository::search::{SearchRepository, SearchRepositoryTrait};
+use crate::response::search::{
+    AddressKind, AddressMatch, BlockMatch, InnerTransactionMatch,
+    ProposalMatch, SearchResult, SearchResultItem, ValidatorMatch,
+    WrapperTransactionMatch,
+};
+
+const TRANSPARENT_ADDRESS_PREFIX: &str = "tnam1";
+const SHIELDED_ADDRESS_PREFIX: &str = "znam1";
+/// Shorter patterns match too many rows to be useful
+const MIN_TEXT_SEARCH_LENGTH: usize = 3;
+
+#[derive(Clone)]
+pub struct SearchService {
+    search_repo: SearchRepository,
+}
+
+impl SearchService {
+    pub fn new(app_state: AppState) -> Self {
+        Self {
+            search_repo: SearchRepository::new(app_state),
+        }
+    }
+
+    pub async fn search(
+        &self,
+        query: String,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Err(SearchError::InvalidQuery(
+                "Query can't be empty".to_string(),
+            ));
+        }
+
+        let results = if is_hash(&query) {
+            self.search_hash(query.to_lowercase()).await?
+        } else if let Ok(number) = query.parse::<i32>() {
+            self.search_number(number).await?
+        } else if query.starts_with(TRANSPARENT_ADDRESS_PREFIX) {
+            self.search_transparent_address(query).await?
+        } else if query.starts_with(SHIELDED_ADDRESS_PREFIX) {
+            vec![SearchResult::exact(SearchResultItem::Address(
+                AddressMatch {
+                    address: query,
+                    kind: AddressKind::Shielded,
+                },
+            ))]
+        } else if query.len() >= MIN_TEXT_SEARCH_LENGTH {
+            self.search_text(query).await?
+        } else {
+            vec![]
+        };
+
+        Ok(rank(results))
+    }
+
+    async fn search_hash(
+        &self,
+        hash: String,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let mut results = vec![];
+
+        if let Some((id, block_height)) = self
+            .search_repo
+            .find_wrapper_tx(hash.clone())
+            .await
+            .map_err(SearchError::Database)?
+        {
+            results.push(SearchResult::exact(
+                SearchResultItem::WrapperTransaction(WrapperTransactionMatch {
+                    id,
+                    block_height: block_height as u64,
+                }),
+            ));
+        }
+
+        if let Some((id, wrapper_id)) = self
+            .search_repo
+            .find_inner_tx(hash.clone())
+            .await
+            .map_err(SearchError::Database)?
+        {
+            results.push(SearchResult::exact(
+                SearchResultItem::InnerTransaction(InnerTransactionMatch {
+                    id,
+                    wrapper_id,
+                }),
+            ));
+        }
+
+        if let Some((height, hash)) = self
+            .search_repo
+            .find_block_by_hash(hash)
+            .await
+            .map_err(SearchError::Database)?
+        {
+            results.push(SearchResult::exact(SearchResultItem::Block(
+                BlockMatch {
+                    height: height as u64,
+                    hash,
+                },
+            )));
+        }
+
+        Ok(results)
+    }
+
+    async fn search_number(
+        &self,
+        number: i32,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let mut results = vec![];
+
+        if let Some((height, hash)) = self
+            .search_repo
+            .find_block_by_height(number)
+            .await
+            .map_err(SearchError::Database)?
+        {
+            results.push(SearchResult::exact(SearchResultItem::Block(
+                BlockMatch {
+                    height: height as u64,
+                    hash,
+                },
+            )));
+        }
+
+        if let Some((id, content)) = self
+            .search_repo
+            .find_proposal(number)
+            .await
+            .map_err(SearchError::Database)?
+        {
+            results.push(SearchResult::exact(SearchResultItem::Proposal(
+                ProposalMatch::from(id, &content),
+            )));
+        }
+
+        Ok(results)
+    }
+
+    async fn search_transparent_address(
+        &self,
+        address: String,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let address = address.to_lowercase();
+        let mut results = vec![SearchResult::exact(SearchResultItem::Address(
+            AddressMatch {
+                address: address.clone(),
+                kind: AddressKind::Transparent,
+            },
+        ))];
+
+        if let Some((address, name, email)) = self
+            .search_repo
+            .find_validator_by_address(address)
+            .await
+            .map_err(SearchError::Database)?
+        {
+            results.push(SearchResult::exact(SearchResultItem::Validator(
+                ValidatorMatch {
+                    address,
+                    name,
+                    email,
+                },
+            )));
+        }
+
+        Ok(results)
+    }
+
+    async fn search_text(
+        &self,
+        pattern: String,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let validators = self
+            .search_repo
+            .search_validators(pattern.clone(), SEARCH_MAX_RESULTS)
+            .await
+            .map_err(SearchError::Database)?
+            .into_iter()
+            .map(|(address, name, email, name_score, email_score)| {
+                SearchResult {
+                    item: SearchResultItem::Validator(ValidatorMatch {
+                        address,
+                        name,
+                        email,
+                    }),
+                    score: name_score
+                        .unwrap_or_default()
+                        .max(email_score.unwrap_or_default()),
+                }
+            });
+
+        let proposals = self
+            .search_repo
+            .search_proposals(pattern, SEARCH_MAX_RESULTS)
+            .await
+            .map_err(SearchError::Database)?
+            .into_iter()
+            .map(|(id, content, score)| SearchResult {
+                item: SearchResultItem::Proposal(ProposalMatch::from(
+                    id, &content,
+                )),
+                score,
+            });
+
+        Ok(validators.chain(proposals).collect())
+    }
+}
+
+fn is_hash(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Best matches first, keeping at most `SEARCH_MAX_RESULTS`
+fn rank(mut results: Vec<SearchResult>) -> Vec<SearchResult> {
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(SEARCH_MAX_RESULTS as usize);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(id: i32, score: f32) -> SearchResult {
+        SearchResult {
+            item: SearchResultItem::Proposal(ProposalMatch::from(id, "{}")),
+            score,
+        }
+    }
+
+    fn proposal_id(result: &SearchResult) -> &str {
+        match &result.item {
+            SearchResultItem::Proposal(proposal) => &proposal.id,
+            _ => panic!("Expected a proposal"),
+        }
+    }
+
+    #[test]
+    fn test_is_hash() {
+        let hash = "a".repeat(64);
+        assert!(is_hash(&hash));
+        assert!(is_hash(&"0123456789ABCDEFabcdef".repeat(3)[..64]));
+
+        assert!(!is_hash(&"a".repeat(63)));
+        assert!(!is_hash(&"a".repeat(65)));
+        assert!(!is_hash(&format!("{}g", "a".repeat(63))));
+        assert!(!is_hash("tnam1qxgzrwqn9qny9fzd7xnlrdkf7hhj9ecyx5mv3sgw"));
+    }
+
+    #[test]
+    fn test_rank_orders_by_score() {
+        let results =
+            rank(vec![proposal(1, 0.3), proposal(2, 1.0), proposal(3, 0.7)]);
+
+        let ids = results.iter().map(proposal_id).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["2", "3", "1"]);
+    }
+
+    #[test]
+    fn test_rank_keeps_best_results() {
+        let results = rank(
+            (0..SEARCH_MAX_RESULTS as i32 + 5)
+                .map(|id| proposal(id, id as f32 / 100.0))
+                .collect(),
+        );
+
+        assert_eq!(results.len(), SEARCH_MAX_RESULTS as usize);
+        assert_eq!(proposal_id(&results[0]), "24");
+        assert_eq!(proposal_id(results.last().unwrap()), "5");
+    }
+
+    #[test]
+    fn test_proposal_match_title() {
+        let proposal = ProposalMatch::from(3, r#"{"title":"Upgrade"}"#);
+        assert_eq!(proposal.id, "3");
+        assert_eq!(proposal.title.as_deref(), Some("Upgrade"));
+
+        assert!(ProposalMatch::from(3, "not json").title.is_none());
+        assert!(ProposalMatch::from(3, r#"{"title":1}"#).title.is_none());
+    }
+}
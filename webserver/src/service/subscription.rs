@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::appstate::AppState;
+use crate::constant::{
+    SUBSCRIPTION_CHANNEL_CAPACITY, SUBSCRIPTION_MAX_BLOCKS_PER_POLL,
+    SUBSCRIPTION_POLL_INTERVAL_SECS,
+};
+use crate::dto::subscription::{SubscriptionQueryParams, SubscriptionTopic};
+use crate::repository::subscription::{
+    SubscriptionRepository, SubscriptionRepositoryTrait,
+};
+use crate::response::block::Block;
+use crate::response::governance::ProposalStatus;
+use crate::response::subscription::{ProposalStatusChange, SubscriptionEvent};
+use crate::response::transaction::{
+    InnerTransaction, TransactionHistory, WrapperTransaction,
+};
+
+/// Last indexed state that was already broadcasted to the subscribers
+struct PollState {
+    block_height: i32,
+    transaction_height: i32,
+    proposals: HashMap<i32, ProposalStatus>,
+}
+
+#[derive(Clone)]
+pub struct SubscriptionService {
+    pub subscription_repo: SubscriptionRepository,
+    sender: broadcast::Sender<SubscriptionEvent>,
+}
+
+impl SubscriptionService {
+    pub fn new(app_state: AppState) -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY);
+
+        Self {
+            subscription_repo: SubscriptionRepository::new(app_state),
+            sender,
+        }
+    }
+
+    /// Polls the database for newly indexed data and broadcasts it to every
+    /// subscriber. A single poller is shared by all the connections.
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            SUBSCRIPTION_POLL_INTERVAL_SECS,
+        ));
+        let mut state: Option<PollState> = None;
+
+        loop {
+            interval.tick().await;
+
+            // Nobody is listening, start again from the tip once somebody
+            // subscribes
+            if self.sender.receiver_count() == 0 {
+                state = None;
+                continue;
+            }
+
+            let result = match state.as_mut() {
+                Some(state) => self.poll(state).await,
+                None => self.init_state().await.map(|new_state| {
+                    state = Some(new_state);
+                }),
+            };
+
+            if let Err(e) = result {
+                tracing::warn!("Error polling subscription events: {}", e);
+            }
+        }
+    }
+
+    pub fn subscribe(
+        &self,
+        params: SubscriptionQueryParams,
+    ) -> impl Stream<Item = SubscriptionEvent> + use<> {
+        let receiver = self.sender.subscribe();
+
+        futures::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            "Subscriber lagged behind, skipped {} events",
+                            skipped
+                        );
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .filter(move |event| {
+            futures::future::ready(is_subscribed(&params, event))
+        })
+    }
+
+    async fn init_state(&self) -> Result<PollState, String> {
+        let block_height = self
+            .subscription_repo
+            .find_last_block_height()
+            .await?
+            .unwrap_or_default();
+        let transaction_height = self
+            .subscription_repo
+            .find_last_transaction_height()
+            .await?
+            .unwrap_or_default();
+        let proposals = self
+            .subscription_repo
+            .find_proposals_result()
+            .await?
+            .into_iter()
+            .map(|(id, result)| (id, ProposalStatus::from(result)))
+            .collect();
+
+        Ok(PollState {
+            block_height,
+            transaction_height,
+            proposals,
+        })
+    }
+
+    async fn poll(&self, state: &mut PollState) -> Result<(), String> {
+        self.poll_blocks(state).await?;
+        self.poll_transactions(state).await?;
+        self.poll_proposals(state).await
+    }
+
+    async fn poll_blocks(&self, state: &mut PollState) -> Result<(), String> {
+        // Also load the last broadcasted block to fill in the parent hash
+        let blocks = self
+            .subscription_repo
+            .find_blocks_after(
+                state.block_height - 1,
+                SUBSCRIPTION_MAX_BLOCKS_PER_POLL as i64 + 1,
+            )
+            .await?;
+
+        let mut prev_block = None;
+        for block in blocks {
+            let height = block.height;
+            if height > state.block_height {
+                self.send(SubscriptionEvent::Block(Block::from(
+                    block.clone(),
+                    prev_block,
+                    vec![],
+                )));
+                state.block_height = height;
+            }
+            prev_block = Some(block);
+        }
+
+        Ok(())
+    }
+
+    async fn poll_transactions(
+        &self,
+        state: &mut PollState,
+    ) -> Result<(), String> {
+        let Some(last_height) = self
+            .subscription_repo
+            .find_last_transaction_height()
+            .await?
+        else {
+            return Ok(());
+        };
+        let from_height = state.transaction_height;
+        let to_height = last_height
+            .min(from_height.saturating_add(SUBSCRIPTION_MAX_BLOCKS_PER_POLL));
+
+        if to_height <= from_height {
+            return Ok(());
+        }
+
+        let wrapper_txs = self
+            .subscription_repo
+            .find_wrapper_txs_between(from_height, to_height)
+            .await?;
+        let inner_txs = self
+            .subscription_repo
+            .find_inner_txs_between(from_height, to_height)
+            .await?;
+        let history = self
+            .subscription_repo
+            .find_history_between(from_height, to_height)
+            .await?;
+
+        let inner_txs = inner_txs
+            .into_iter()
+            .map(InnerTransaction::from)
+            .collect::<Vec<_>>();
+
+        // Subscribers filtering by kind match the wrapper against its inner
+        // transactions
+        wrapper_txs.into_iter().for_each(|wrapper_tx| {
+            let mut wrapper_tx = WrapperTransaction::from(wrapper_tx);
+            wrapper_tx.inner_transactions = inner_txs
+                .iter()
+                .filter(|inner_tx| inner_tx.wrapper_id == wrapper_tx.tx_id)
+                .map(InnerTransaction::to_short)
+                .collect();
+            self.send(SubscriptionEvent::WrapperTransaction(wrapper_tx))
+        });
+        inner_txs.into_iter().for_each(|inner_tx| {
+            self.send(SubscriptionEvent::InnerTransaction(inner_tx))
+        });
+        history
+            .into_iter()
+            .for_each(|(history, inner_tx, block_height)| {
+                self.send(SubscriptionEvent::AddressActivity(
+                    TransactionHistory::from(history, inner_tx, block_height),
+                ))
+            });
+
+        state.transaction_height = to_height;
+
+        Ok(())
+    }
+
+    async fn poll_proposals(
+        &self,
+        state: &mut PollState,
+    ) -> Result<(), String> {
+        let proposals = self.subscription_repo.find_proposals_result().await?;
+
+        for (id, result) in proposals {
+            let status = ProposalStatus::from(result);
+            if state.proposals.get(&id) == Some(&status) {
+                continue;
+            }
+
+            self.send(SubscriptionEvent::ProposalStatus(
+                ProposalStatusChange {
+                    proposal_id: id as u64,
+                    status: status.clone(),
+                },
+            ));
+            state.proposals.insert(id, status);
+        }
+
+        Ok(())
+    }
+
+    fn send(&self, event: SubscriptionEvent) {
+        // Only fails when there are no subscribers left
+        let _ = self.sender.send(event);
+    }
+}
+
+fn is_subscribed(
+    params: &SubscriptionQueryParams,
+    event: &SubscriptionEvent,
+) -> bool {
+    match event {
+        SubscriptionEvent::Block(_) => {
+            params.topics.contains(&SubscriptionTopic::Blocks)
+        }
+        SubscriptionEvent::WrapperTransaction(wrapper_tx) => {
+            params.topics.contains(&SubscriptionTopic::Transactions)
+                && (params.kinds.is_empty()
+                    || wrapper_tx
+                        .inner_transactions
+                        .iter()
+                        .any(|inner_tx| params.kinds.contains(&inner_tx.kind)))
+        }
+        SubscriptionEvent::InnerTransaction(inner_tx) => {
+            params.topics.contains(&SubscriptionTopic::Transactions)
+                && (params.kinds.is_empty()
+                    || params.kinds.contains(&inner_tx.kind))
+        }
+        SubscriptionEvent::AddressActivity(history) => {
+            params.topics.contains(&SubscriptionTopic::AddressActivity)
+                && params.addresses.contains(&history.target)
+        }
+        SubscriptionEvent::ProposalStatus(_) => {
+            params.topics.contains(&SubscriptionTopic::Proposals)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::transaction::{
+        ShortInnerTransaction, TransactionKind, TransactionResult,
+        TrasactionHistoryKind,
+    };
+
+    fn params(
+        topics: Vec<SubscriptionTopic>,
+        kinds: Vec<TransactionKind>,
+        addresses: Vec<&str>,
+    ) -> SubscriptionQueryParams {
+        SubscriptionQueryParams {
+            topics,
+            kinds,
+            addresses: addresses.into_iter().map(String::from).collect(),
+        }
+    }
+
+    fn inner_tx(kind: TransactionKind) -> InnerTransaction {
+        InnerTransaction {
+            tx_id: "inner".to_string(),
+            wrapper_id: "wrapper".to_string(),
+            kind,
+            data: None,
+            memo: None,
+            exit_code: TransactionResult::Applied,
+        }
+    }
+
+    fn wrapper_tx(kinds: Vec<TransactionKind>) -> WrapperTransaction {
+        WrapperTransaction {
+            tx_id: "wrapper".to_string(),
+            fee_payer: "tnam1payer".to_string(),
+            fee_token: "tnam1token".to_string(),
+            gas_limit: 50_000,
+            gas_used: None,
+            amount_per_gas_unit: None,
+            block_height: 1,
+            inner_transactions: kinds
+                .into_iter()
+                .map(|kind| ShortInnerTransaction {
+                    tx_id: "inner".to_string(),
+                    kind,
+                    data: None,
+                    memo: None,
+                    exit_code: TransactionResult::Applied,
+                })
+                .collect(),
+            exit_code: TransactionResult::Applied,
+            atomic: true,
+        }
+    }
+
+    #[test]
+    fn test_is_subscribed_topics() {
+        let event = SubscriptionEvent::ProposalStatus(ProposalStatusChange {
+            proposal_id: 1,
+            status: ProposalStatus::Pending,
+        });
+
+        assert!(is_subscribed(
+            &params(vec![SubscriptionTopic::Proposals], vec![], vec![]),
+            &event
+        ));
+        assert!(!is_subscribed(
+            &params(vec![SubscriptionTopic::Blocks], vec![], vec![]),
+            &event
+        ));
+    }
+
+    #[test]
+    fn test_is_subscribed_wrapper_without_kinds() {
+        let params =
+            params(vec![SubscriptionTopic::Transactions], vec![], vec![]);
+
+        assert!(is_subscribed(
+            &params,
+            &SubscriptionEvent::WrapperTransaction(wrapper_tx(vec![]))
+        ));
+    }
+
+    #[test]
+    fn test_is_subscribed_wrapper_matches_inner_kinds() {
+        let params = params(
+            vec![SubscriptionTopic::Transactions],
+            vec![TransactionKind::TransparentTransfer],
+            vec![],
+        );
+
+        assert!(is_subscribed(
+            &params,
+            &SubscriptionEvent::WrapperTransaction(wrapper_tx(vec![
+                TransactionKind::RevealPk,
+                TransactionKind::TransparentTransfer,
+            ]))
+        ));
+        assert!(!is_subscribed(
+            &params,
+            &SubscriptionEvent::WrapperTransaction(wrapper_tx(vec![
+                TransactionKind::Bond
+            ]))
+        ));
+        assert!(!is_subscribed(
+            &params,
+            &SubscriptionEvent::WrapperTransaction(wrapper_tx(vec![]))
+        ));
+    }
+
+    #[test]
+    fn test_is_subscribed_inner_kinds() {
+        let params = params(
+            vec![SubscriptionTopic::Transactions],
+            vec![TransactionKind::Bond],
+            vec![],
+        );
+
+        assert!(is_subscribed(
+            &params,
+            &SubscriptionEvent::InnerTransaction(inner_tx(
+                TransactionKind::Bond
+            ))
+        ));
+        assert!(!is_subscribed(
+            &params,
+            &SubscriptionEvent::InnerTransaction(inner_tx(
+                TransactionKind::Unbond
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_is_subscribed_address_activity() {
+        let params = params(
+            vec![SubscriptionTopic::AddressActivity],
+            vec![],
+            vec!["tnam1watched"],
+        );
+        let activity = |target: &str| {
+            SubscriptionEvent::AddressActivity(TransactionHistory {
+                tx: inner_tx(TransactionKind::TransparentTransfer),
+                target: target.to_string(),
+                kind: TrasactionHistoryKind::Received,
+                block_height: 1,
+            })
+        };
+
+        assert!(is_subscribed(&params, &activity("tnam1watched")));
+        assert!(!is_subscribed(&params, &activity("tnam1other")));
+    }
+}
@@ -1,11 +1,29 @@
+use chrono::NaiveDateTime;
+use futures::{Stream, StreamExt};
+use orm::transactions::{
+    InnerTransactionDb, TransactionHistoryDb, TransactionHistoryKindDb,
+    TransactionKindDb, TransactionResultDb,
+};
+
 use crate::appstate::AppState;
+use crate::constant::{EXPORT_BATCH_SIZE, ITEM_PER_PAGE};
+use crate::dto::transaction::{
+    ExportFormat, TransactionHistoryExportQueryParams,
+    TransactionHistoryQueryParams,
+};
 use crate::error::transaction::TransactionError;
 use crate::repository::tranasaction::{
-    TransactionRepository, TransactionRepositoryTrait,
+    TransactionHistoryFilters, TransactionRepository,
+    TransactionRepositoryTrait,
 };
 use crate::response::transaction::{
-    InnerTransaction, TransactionHistory, WrapperTransaction,
+    ExportedFees, InnerTransaction, TransactionHistory,
+    TransactionHistoryExportRow, TransactionResult, TrasactionHistoryKind,
+    WrapperTransaction,
 };
+use crate::service::utils::{decode_cursor, encode_cursor};
+
+type TransactionHistoryRow = (TransactionHistoryDb, InnerTransactionDb, i32);
 
 #[derive(Clone)]
 pub struct TransactionService {
@@ -60,21 +78,335 @@ impl TransactionService {
 
     pub async fn get_addresses_history(
         &self,
-        addresses: Vec<String>,
+        query: TransactionHistoryQueryParams,
         page: u64,
-    ) -> Result<(Vec<TransactionHistory>, u64, u64), TransactionError> {
+    ) -> Result<
+        (Vec<TransactionHistory>, u64, u64, Option<String>),
+        TransactionError,
+    > {
+        let filters = map_filters(&query)?;
+
         let (txs, total_pages, total_items) = self
             .transaction_repo
-            .find_addresses_history(addresses, page as i64)
+            .find_addresses_history(query.addresses, filters, page as i64)
             .await
             .map_err(TransactionError::Database)?;
 
+        let next_cursor = if (page as i64) < total_pages {
+            txs.last().map(Self::history_cursor)
+        } else {
+            None
+        };
+
         Ok((
             txs.into_iter()
                 .map(|(h, t, bh)| TransactionHistory::from(h, t, bh))
                 .collect(),
             total_pages as u64,
             total_items as u64,
+            next_cursor,
         ))
     }
+
+    pub async fn get_addresses_history_by_cursor(
+        &self,
+        query: TransactionHistoryQueryParams,
+        cursor: String,
+    ) -> Result<(Vec<TransactionHistory>, Option<String>), TransactionError>
+    {
+        let filters = map_filters(&query)?;
+
+        let after = decode_cursor(&cursor, 3)
+            .and_then(|parts| match parts.as_slice() {
+                [block_height, inner_tx_id, id] => Some((
+                    block_height.parse::<i32>().ok()?,
+                    inner_tx_id.clone(),
+                    id.parse::<i32>().ok()?,
+                )),
+                _ => None,
+            })
+            .ok_or(TransactionError::InvalidCursor(cursor))?;
+
+        let mut txs = self
+            .transaction_repo
+            .find_addresses_history_by_cursor(
+                query.addresses,
+                filters,
+                Some(after),
+                ITEM_PER_PAGE as i64 + 1,
+            )
+            .await
+            .map_err(TransactionError::Database)?;
+
+        let next_cursor = if txs.len() > ITEM_PER_PAGE as usize {
+            txs.truncate(ITEM_PER_PAGE as usize);
+            txs.last().map(Self::history_cursor)
+        } else {
+            None
+        };
+
+        Ok((
+            txs.into_iter()
+                .map(|(h, t, bh)| TransactionHistory::from(h, t, bh))
+                .collect(),
+            next_cursor,
+        ))
+    }
+
+    /// Streams the history in batches, so that the response doesn't have to
+    /// be buffered whatever the size of the history
+    pub fn export_addresses_history(
+        &self,
+        query: TransactionHistoryExportQueryParams,
+    ) -> Result<
+        impl Stream<Item = Result<String, TransactionError>> + use<>,
+        TransactionError,
+    > {
+        let from_timestamp =
+            query.from_timestamp.map(to_datetime).transpose()?;
+        let to_timestamp = query.to_timestamp.map(to_datetime).transpose()?;
+        let transaction_repo = self.transaction_repo.clone();
+        let addresses = query.addresses;
+        let format = query.format;
+
+        let header = match format {
+            ExportFormat::Csv => {
+                Some(TransactionHistoryExportRow::CSV_HEADER.to_string())
+            }
+            ExportFormat::Jsonl => None,
+        };
+
+        // The state is the position to continue from, None once the last
+        // batch was sent, and the fees already exported
+        let batches = futures::stream::try_unfold(
+            Some((None, ExportedFees::default())),
+            move |state| {
+                let transaction_repo = transaction_repo.clone();
+                let addresses = addresses.clone();
+                let format = format.clone();
+
+                async move {
+                    let Some((after, mut exported_fees)) = state else {
+                        return Ok(None);
+                    };
+
+                    let rows = transaction_repo
+                        .find_addresses_history_export(
+                            addresses,
+                            from_timestamp,
+                            to_timestamp,
+                            after,
+                            EXPORT_BATCH_SIZE,
+                        )
+                        .await
+                        .map_err(TransactionError::Database)?;
+
+                    if rows.is_empty() {
+                        return Ok(None);
+                    }
+
+                    let next =
+                        (rows.len() as i64 == EXPORT_BATCH_SIZE).then(|| {
+                            rows.last().map(|(history, _, wrapper_tx, _)| {
+                                (
+                                    wrapper_tx.block_height,
+                                    history.inner_tx_id.clone(),
+                                    history.id,
+                                )
+                            })
+                        });
+
+                    let chunk = rows
+                        .into_iter()
+                        .flat_map(
+                            |(history, inner_tx, wrapper_tx, timestamp)| {
+                                TransactionHistoryExportRow::from(
+                                    history, inner_tx, wrapper_tx, timestamp,
+                                )
+                            },
+                        )
+                        .map(|mut row| {
+                            exported_fees.dedup(&mut row);
+                            row
+                        })
+                        .map(|row| match format {
+                            ExportFormat::Csv => row.to_csv_record(),
+                            ExportFormat::Jsonl => serde_json::to_string(&row)
+                                .map(|line| line + "\n")
+                                .unwrap_or_default(),
+                        })
+                        .collect::<String>();
+
+                    Ok(Some((chunk, next.map(|next| (next, exported_fees)))))
+                }
+            },
+        );
+
+        Ok(futures::stream::iter(header.map(Ok)).chain(batches))
+    }
+
+    fn history_cursor(
+        (history, _, block_height): &TransactionHistoryRow,
+    ) -> String {
+        encode_cursor(&[
+            block_height.to_string(),
+            history.inner_tx_id.clone(),
+            history.id.to_string(),
+        ])
+    }
+}
+
+fn to_datetime(timestamp: i64) -> Result<NaiveDateTime, TransactionError> {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|datetime| datetime.naive_utc())
+        .ok_or(TransactionError::InvalidTimestamp(timestamp))
+}
+
+fn map_filters(
+    query: &TransactionHistoryQueryParams,
+) -> Result<TransactionHistoryFilters, TransactionError> {
+    Ok(TransactionHistoryFilters {
+        kinds: query
+            .kinds
+            .iter()
+            .cloned()
+            .map(TransactionKindDb::from)
+            .collect(),
+        token: query.token.as_ref().map(|token| token.to_lowercase()),
+        from_height: query.from_height.map(to_height).transpose()?,
+        to_height: query.to_height.map(to_height).transpose()?,
+        from_timestamp: query.from_timestamp.map(to_datetime).transpose()?,
+        to_timestamp: query.to_timestamp.map(to_datetime).transpose()?,
+        direction: query.direction.clone().map(|direction| match direction {
+            TrasactionHistoryKind::Received => {
+                TransactionHistoryKindDb::Received
+            }
+            TrasactionHistoryKind::Sent => TransactionHistoryKindDb::Sent,
+        }),
+        exit_code: query.exit_code.clone().map(|exit_code| match exit_code {
+            TransactionResult::Applied => TransactionResultDb::Applied,
+            TransactionResult::Rejected => TransactionResultDb::Rejected,
+        }),
+    })
+}
+
+fn to_height(height: u64) -> Result<i32, TransactionError> {
+    i32::try_from(height).map_err(|_| TransactionError::InvalidHeight(height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::transaction::TransactionKind;
+
+    fn query() -> TransactionHistoryQueryParams {
+        TransactionHistoryQueryParams {
+            page: None,
+            addresses: vec!["tnam1address".to_string()],
+            cursor: None,
+            kinds: vec![],
+            token: None,
+            from_height: None,
+            to_height: None,
+            from_timestamp: None,
+            to_timestamp: None,
+            direction: None,
+            exit_code: None,
+        }
+    }
+
+    #[test]
+    fn test_map_filters_empty() {
+        let filters = map_filters(&query()).unwrap();
+
+        assert!(filters.kinds.is_empty());
+        assert!(filters.token.is_none());
+        assert!(filters.from_height.is_none());
+        assert!(filters.to_height.is_none());
+        assert!(filters.from_timestamp.is_none());
+        assert!(filters.to_timestamp.is_none());
+        assert!(filters.direction.is_none());
+        assert!(filters.exit_code.is_none());
+    }
+
+    #[test]
+    fn test_map_filters() {
+        let filters = map_filters(&TransactionHistoryQueryParams {
+            kinds: vec![
+                TransactionKind::TransparentTransfer,
+                TransactionKind::IbcShieldingTransfer,
+            ],
+            token: Some("TNAM1Token".to_string()),
+            from_height: Some(10),
+            to_height: Some(20),
+            from_timestamp: Some(1_700_000_000),
+            to_timestamp: Some(1_700_003_600),
+            direction: Some(TrasactionHistoryKind::Sent),
+            exit_code: Some(TransactionResult::Rejected),
+            ..query()
+        })
+        .unwrap();
+
+        assert!(matches!(
+            filters.kinds.as_slice(),
+            [
+                TransactionKindDb::TransparentTransfer,
+                TransactionKindDb::IbcShieldingTransfer
+            ]
+        ));
+        assert_eq!(filters.token.as_deref(), Some("tnam1token"));
+        assert_eq!(filters.from_height, Some(10));
+        assert_eq!(filters.to_height, Some(20));
+        assert_eq!(
+            filters.from_timestamp.unwrap().and_utc().timestamp(),
+            1_700_000_000
+        );
+        assert_eq!(
+            filters.to_timestamp.unwrap().and_utc().timestamp(),
+            1_700_003_600
+        );
+        assert!(matches!(
+            filters.direction,
+            Some(TransactionHistoryKindDb::Sent)
+        ));
+        assert!(matches!(
+            filters.exit_code,
+            Some(TransactionResultDb::Rejected)
+        ));
+    }
+
+    #[test]
+    fn test_map_filters_out_of_range() {
+        let result = map_filters(&TransactionHistoryQueryParams {
+            from_height: Some(i32::MAX as u64),
+            to_height: Some(i32::MAX as u64 + 1),
+            ..query()
+        });
+        assert!(matches!(
+            result,
+            Err(TransactionError::InvalidHeight(height)) if height == i32::MAX as u64 + 1
+        ));
+
+        let result = map_filters(&TransactionHistoryQueryParams {
+            from_timestamp: Some(i64::MAX),
+            ..query()
+        });
+        assert!(matches!(result, Err(TransactionError::InvalidTimestamp(_))));
+    }
+
+    #[test]
+    fn test_kind_into_db() {
+        assert!(matches!(
+            TransactionKindDb::from(TransactionKind::Bond),
+            TransactionKindDb::Bond
+        ));
+        assert!(matches!(
+            TransactionKindDb::from(TransactionKind::UpdateStewardCommission),
+            TransactionKindDb::UpdateStewardCommission
+        ));
+        assert!(matches!(
+            TransactionKindDb::from(TransactionKind::Unknown),
+            TransactionKindDb::Unknown
+        ));
+    }
 }
@@ -5,3 +5,61 @@ pub fn raw_amount_to_nam(raw_amount: String) -> String {
         .expect("raw_amount is not a valid string")
         .to_string_native()
 }
+
+/// Encodes the components of a keyset pagination position into an opaque
+/// cursor.
+pub fn encode_cursor(parts: &[String]) -> String {
+    String::from_utf8(subtle_encoding::hex::encode(parts.join(":")))
+        .expect("hex encoding is valid utf8")
+}
+
+/// Decodes a cursor created by `encode_cursor`, checking that it has the
+/// expected number of components.
+pub fn decode_cursor(cursor: &str, len: usize) -> Option<Vec<String>> {
+    let decoded = subtle_encoding::hex::decode(cursor).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let parts = decoded
+        .split(':')
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    (parts.len() == len).then_some(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trip() {
+        let parts = vec!["1200".to_string(), "ab".repeat(32)];
+
+        let cursor = encode_cursor(&parts);
+
+        assert!(cursor.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(decode_cursor(&cursor, 2), Some(parts));
+    }
+
+    #[test]
+    fn decode_cursor_rejects_non_hex() {
+        assert_eq!(decode_cursor("not-a-cursor", 2), None);
+        assert_eq!(decode_cursor("abc", 1), None);
+    }
+
+    #[test]
+    fn decode_cursor_rejects_non_utf8() {
+        let cursor =
+            String::from_utf8(subtle_encoding::hex::encode([0xff, 0xfe]))
+                .unwrap();
+
+        assert_eq!(decode_cursor(&cursor, 1), None);
+    }
+
+    #[test]
+    fn decode_cursor_rejects_wrong_part_count() {
+        let cursor = encode_cursor(&["1".to_string(), "2".to_string()]);
+
+        assert_eq!(decode_cursor(&cursor, 1), None);
+        assert_eq!(decode_cursor(&cursor, 3), None);
+    }
+}
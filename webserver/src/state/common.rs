@@ -1,9 +1,13 @@
-use namada_sdk::tendermint_rpc::HttpClient;
+use std::sync::Arc;
+
+use shared::client::RpcClient;
 
 use crate::appstate::AppState;
 use crate::config::AppConfig;
+use crate::service::account::AccountService;
 use crate::service::balance::BalanceService;
 use crate::service::block::BlockService;
+use crate::service::cache::CacheService;
 use crate::service::chain::ChainService;
 use crate::service::crawler_state::CrawlerStateService;
 use crate::service::gas::GasService;
@@ -13,6 +17,8 @@ use crate::service::masp::MaspService;
 use crate::service::pgf::PgfService;
 use crate::service::pos::PosService;
 use crate::service::revealed_pk::RevealedPkService;
+use crate::service::search::SearchService;
+use crate::service::subscription::SubscriptionService;
 use crate::service::transaction::TransactionService;
 
 #[derive(Clone)]
@@ -29,12 +35,20 @@ pub struct CommonState {
     pub crawler_state_service: CrawlerStateService,
     pub ibc_service: IbcService,
     pub masp_service: MaspService,
-    pub client: HttpClient,
+    pub cache_service: CacheService,
+    pub subscription_service: SubscriptionService,
+    pub account_service: AccountService,
+    pub search_service: SearchService,
+    pub client: Arc<RpcClient>,
     pub config: AppConfig,
 }
 
 impl CommonState {
-    pub fn new(client: HttpClient, config: AppConfig, data: AppState) -> Self {
+    pub fn new(
+        client: Arc<RpcClient>,
+        config: AppConfig,
+        data: AppState,
+    ) -> Self {
         Self {
             block_service: BlockService::new(data.clone()),
             pos_service: PosService::new(data.clone()),
@@ -47,7 +61,11 @@ impl CommonState {
             transaction_service: TransactionService::new(data.clone()),
             crawler_state_service: CrawlerStateService::new(data.clone()),
             ibc_service: IbcService::new(data.clone()),
-            masp_service: MaspService::new(data),
+            masp_service: MaspService::new(data.clone()),
+            subscription_service: SubscriptionService::new(data.clone()),
+            account_service: AccountService::new(data.clone()),
+            search_service: SearchService::new(data.clone()),
+            cache_service: CacheService::new(data, config.cache_ttl),
             client,
             config,
         }